@@ -1,9 +1,9 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
 use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[sea_orm(table_name = "activities")]
 pub struct Model {
@@ -13,10 +13,37 @@ pub struct Model {
     pub body: Option<String>,
     pub column_id: Option<i32>,
     pub ordinal: i32,
+    pub board_id: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub archived_at: Option<TimeDateTimeWithTimeZone>,
+    pub start_date: Option<TimeDate>,
+    pub due_date: Option<TimeDate>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<TimeDateTimeWithTimeZone>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<TimeDateTimeWithTimeZone>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub column_entered_at: Option<TimeDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::activity_comments::Entity")]
+    ActivityComments,
+    #[sea_orm(has_many = "super::activity_field_values::Entity")]
+    ActivityFieldValues,
+    #[sea_orm(has_many = "super::attachments::Entity")]
+    Attachments,
+    #[sea_orm(
+        belongs_to = "super::boards::Entity",
+        from = "Column::BoardId",
+        to = "super::boards::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Boards,
+    #[sea_orm(has_many = "super::checklist_items::Entity")]
+    ChecklistItems,
     #[sea_orm(
         belongs_to = "super::columns::Entity",
         from = "Column::ColumnId",
@@ -25,6 +52,40 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Columns,
+    #[sea_orm(has_many = "super::recurrence_occurrences::Entity")]
+    RecurrenceOccurrences,
+    #[sea_orm(has_many = "super::recurrences::Entity")]
+    Recurrences,
+}
+
+impl Related<super::activity_comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ActivityComments.def()
+    }
+}
+
+impl Related<super::activity_field_values::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ActivityFieldValues.def()
+    }
+}
+
+impl Related<super::attachments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
+impl Related<super::boards::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Boards.def()
+    }
+}
+
+impl Related<super::checklist_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChecklistItems.def()
+    }
 }
 
 impl Related<super::columns::Entity> for Entity {
@@ -33,6 +94,18 @@ impl Related<super::columns::Entity> for Entity {
     }
 }
 
+impl Related<super::recurrence_occurrences::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecurrenceOccurrences.def()
+    }
+}
+
+impl Related<super::recurrences::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Recurrences.def()
+    }
+}
+
 impl Related<super::category_tags::Entity> for Entity {
     fn to() -> RelationDef {
         super::activity_tags::Relation::CategoryTags.def()
@@ -1,9 +1,10 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
+use super::sea_orm_active_enums::{SelectionMode, TagPalette};
 use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[sea_orm(table_name = "categories")]
 pub struct Model {
@@ -11,10 +12,24 @@ pub struct Model {
     pub id: i32,
     pub name: String,
     pub ordinal: i32,
+    pub board_id: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub deleted_at: Option<TimeDateTimeWithTimeZone>,
+    pub palette: TagPalette,
+    pub selection: SelectionMode,
+    pub is_required: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::boards::Entity",
+        from = "Column::BoardId",
+        to = "super::boards::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Boards,
     #[sea_orm(has_many = "super::category_tags::Entity")]
     CategoryTags,
 }
@@ -25,4 +40,10 @@ impl Related<super::category_tags::Entity> for Entity {
     }
 }
 
+impl Related<super::boards::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Boards.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
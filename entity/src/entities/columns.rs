@@ -1,9 +1,9 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
 use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[sea_orm(table_name = "columns")]
 pub struct Model {
@@ -11,12 +11,28 @@ pub struct Model {
     pub id: i32,
     pub name: String,
     pub ordinal: i32,
+    pub board_id: i32,
+    pub is_done: bool,
+    pub wip_limit: Option<i32>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub deleted_at: Option<TimeDateTimeWithTimeZone>,
+    pub requires_triage: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::boards::Entity",
+        from = "Column::BoardId",
+        to = "super::boards::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Boards,
     #[sea_orm(has_many = "super::activities::Entity")]
     Activities,
+    #[sea_orm(has_many = "super::recurrences::Entity")]
+    Recurrences,
 }
 
 impl Related<super::activities::Entity> for Entity {
@@ -25,4 +41,16 @@ impl Related<super::activities::Entity> for Entity {
     }
 }
 
+impl Related<super::boards::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Boards.def()
+    }
+}
+
+impl Related<super::recurrences::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Recurrences.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,8 +1,20 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
 pub mod activities;
+pub mod activity_comments;
+pub mod activity_field_values;
+pub mod activity_relations;
 pub mod activity_tags;
+pub mod attachments;
+pub mod boards;
 pub mod categories;
 pub mod category_tags;
+pub mod checklist_items;
 pub mod columns;
+pub mod custom_field_options;
+pub mod custom_fields;
+pub mod events;
 pub mod prelude;
+pub mod recurrence_occurrences;
+pub mod recurrences;
+pub mod sea_orm_active_enums;
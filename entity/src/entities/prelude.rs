@@ -1,7 +1,18 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
 pub use super::activities::Entity as Activities;
+pub use super::activity_comments::Entity as ActivityComments;
+pub use super::activity_field_values::Entity as ActivityFieldValues;
+pub use super::activity_relations::Entity as ActivityRelations;
 pub use super::activity_tags::Entity as ActivityTags;
+pub use super::attachments::Entity as Attachments;
+pub use super::boards::Entity as Boards;
 pub use super::categories::Entity as Categories;
 pub use super::category_tags::Entity as CategoryTags;
+pub use super::checklist_items::Entity as ChecklistItems;
 pub use super::columns::Entity as Columns;
+pub use super::custom_field_options::Entity as CustomFieldOptions;
+pub use super::custom_fields::Entity as CustomFields;
+pub use super::events::Entity as Events;
+pub use super::recurrence_occurrences::Entity as RecurrenceOccurrences;
+pub use super::recurrences::Entity as Recurrences;
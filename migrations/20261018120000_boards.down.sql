@@ -0,0 +1,70 @@
+CREATE TEMP TABLE saved_columns AS SELECT * FROM columns;
+CREATE TEMP TABLE saved_activities AS SELECT * FROM activities;
+CREATE TEMP TABLE saved_categories AS SELECT * FROM categories;
+CREATE TEMP TABLE saved_category_tags AS SELECT * FROM category_tags;
+CREATE TEMP TABLE saved_activity_tags AS SELECT * FROM activity_tags;
+
+DROP TABLE activity_tags;
+DROP TABLE category_tags;
+DROP TABLE categories;
+DROP TABLE activities;
+DROP TABLE columns;
+DROP TABLE boards;
+
+CREATE TABLE columns (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    ordinal INT NOT NULL
+);
+
+CREATE TABLE activities (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    body TEXT,
+    column_id INTEGER,
+    ordinal INT NOT NULL,
+    FOREIGN KEY (column_id) REFERENCES columns(id) ON DELETE SET NULL
+);
+
+CREATE TABLE categories (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    ordinal INT NOT NULL
+);
+
+CREATE TABLE category_tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    tag_name TEXT NOT NULL,
+    category_id INTEGER,
+    color INTEGER NOT NULL,
+    ordinal INT NOT NULL,
+    UNIQUE (tag_name, category_id),
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE
+);
+
+CREATE TABLE activity_tags (
+    activity_id INTEGER NOT NULL,
+    category_tag_id INTEGER NOT NULL,
+    PRIMARY KEY (activity_id, category_tag_id),
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE CASCADE,
+    FOREIGN KEY (category_tag_id) REFERENCES category_tags(id) ON DELETE CASCADE
+);
+
+INSERT INTO columns (id, name, ordinal) SELECT id, name, ordinal FROM saved_columns;
+
+INSERT INTO activities (id, name, body, column_id, ordinal)
+SELECT id, name, body, column_id, ordinal FROM saved_activities;
+
+INSERT INTO categories (id, name, ordinal) SELECT id, name, ordinal FROM saved_categories;
+
+INSERT INTO category_tags (id, tag_name, category_id, color, ordinal)
+SELECT id, tag_name, category_id, color, ordinal FROM saved_category_tags;
+
+INSERT INTO activity_tags (activity_id, category_tag_id)
+SELECT activity_id, category_tag_id FROM saved_activity_tags;
+
+DROP TABLE saved_activity_tags;
+DROP TABLE saved_category_tags;
+DROP TABLE saved_categories;
+DROP TABLE saved_activities;
+DROP TABLE saved_columns;
@@ -0,0 +1 @@
+ALTER TABLE activities ADD COLUMN archived_at TEXT;
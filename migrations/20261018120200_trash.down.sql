@@ -0,0 +1,7 @@
+DELETE FROM category_tags WHERE deleted_at IS NOT NULL;
+DELETE FROM categories WHERE deleted_at IS NOT NULL;
+DELETE FROM columns WHERE deleted_at IS NOT NULL;
+
+ALTER TABLE category_tags DROP COLUMN deleted_at;
+ALTER TABLE categories DROP COLUMN deleted_at;
+ALTER TABLE columns DROP COLUMN deleted_at;
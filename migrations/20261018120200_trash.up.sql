@@ -0,0 +1,3 @@
+ALTER TABLE columns ADD COLUMN deleted_at TEXT;
+ALTER TABLE categories ADD COLUMN deleted_at TEXT;
+ALTER TABLE category_tags ADD COLUMN deleted_at TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE activities DROP COLUMN due_date;
+ALTER TABLE activities DROP COLUMN start_date;
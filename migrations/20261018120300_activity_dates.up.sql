@@ -0,0 +1,2 @@
+ALTER TABLE activities ADD COLUMN start_date TEXT;
+ALTER TABLE activities ADD COLUMN due_date TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE activities DROP COLUMN column_entered_at;
+ALTER TABLE activities DROP COLUMN updated_at;
+ALTER TABLE activities DROP COLUMN created_at;
@@ -0,0 +1,10 @@
+ALTER TABLE activities ADD COLUMN created_at TEXT;
+ALTER TABLE activities ADD COLUMN updated_at TEXT;
+ALTER TABLE activities ADD COLUMN column_entered_at TEXT;
+
+-- The real times are unknown for existing activities, so the time of the migration is used instead.
+UPDATE activities
+SET
+    created_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now'),
+    updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now'),
+    column_entered_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now');
@@ -0,0 +1,8 @@
+CREATE TABLE checklist_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    activity_id INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    done BOOLEAN NOT NULL DEFAULT FALSE,
+    ordinal INT NOT NULL,
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+DROP TABLE activity_relations;
+
+ALTER TABLE columns DROP COLUMN is_done;
@@ -0,0 +1,11 @@
+ALTER TABLE columns ADD COLUMN is_done BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE activity_relations (
+    activity_id INTEGER NOT NULL,
+    related_activity_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    PRIMARY KEY (activity_id, related_activity_id),
+    CHECK (activity_id <> related_activity_id),
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE CASCADE,
+    FOREIGN KEY (related_activity_id) REFERENCES activities(id) ON DELETE CASCADE
+);
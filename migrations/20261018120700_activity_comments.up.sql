@@ -0,0 +1,9 @@
+CREATE TABLE activity_comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    activity_id INTEGER NOT NULL,
+    author TEXT NOT NULL,
+    text TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE CASCADE
+);
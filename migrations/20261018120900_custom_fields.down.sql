@@ -0,0 +1,3 @@
+DROP TABLE activity_field_values;
+DROP TABLE custom_field_options;
+DROP TABLE custom_fields;
@@ -0,0 +1,30 @@
+CREATE TABLE custom_fields (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    board_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    ordinal INT NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+);
+
+CREATE TABLE custom_field_options (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    field_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    ordinal INT NOT NULL,
+    FOREIGN KEY (field_id) REFERENCES custom_fields(id) ON DELETE CASCADE
+);
+
+-- Only the column matching the kind of the field is set.
+CREATE TABLE activity_field_values (
+    activity_id INTEGER NOT NULL,
+    field_id INTEGER NOT NULL,
+    number_value REAL,
+    text_value TEXT,
+    date_value TEXT,
+    option_id INTEGER,
+    PRIMARY KEY (activity_id, field_id),
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE CASCADE,
+    FOREIGN KEY (field_id) REFERENCES custom_fields(id) ON DELETE CASCADE,
+    FOREIGN KEY (option_id) REFERENCES custom_field_options(id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- Maximum number of activities in the column, or NULL for no limit.
+ALTER TABLE columns ADD COLUMN wip_limit INTEGER;
@@ -0,0 +1,2 @@
+DROP TABLE recurrence_occurrences;
+DROP TABLE recurrences;
@@ -0,0 +1,23 @@
+CREATE TABLE recurrences (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    template_id INTEGER NOT NULL,
+    column_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    -- Bit mask of weekdays for weekly rules, with Monday as the lowest bit.
+    weekdays INTEGER,
+    day_of_month INTEGER,
+    interval_days INTEGER,
+    start_date TEXT NOT NULL,
+    FOREIGN KEY (template_id) REFERENCES activities(id) ON DELETE CASCADE,
+    FOREIGN KEY (column_id) REFERENCES columns(id) ON DELETE CASCADE
+);
+
+-- Occurrences are kept after their activity is deleted, so that it is not generated again.
+CREATE TABLE recurrence_occurrences (
+    recurrence_id INTEGER NOT NULL,
+    date TEXT NOT NULL,
+    activity_id INTEGER,
+    PRIMARY KEY (recurrence_id, date),
+    FOREIGN KEY (recurrence_id) REFERENCES recurrences(id) ON DELETE CASCADE,
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE SET NULL
+);
@@ -0,0 +1,2 @@
+DROP INDEX events_entity_idx;
+DROP TABLE events;
@@ -0,0 +1,12 @@
+-- Events are kept after their entity is deleted, so there are no foreign keys.
+CREATE TABLE events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    entity TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    before TEXT,
+    after TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX events_entity_idx ON events (entity, entity_id);
@@ -0,0 +1,2 @@
+-- Strategy used to pick colors of new tags of the category: 'hash', 'fixed' or 'spaced'.
+ALTER TABLE categories ADD COLUMN palette TEXT NOT NULL DEFAULT 'hash';
@@ -0,0 +1,2 @@
+-- Whether an activity can hold one ('single') or many ('multi') tags of the category.
+ALTER TABLE categories ADD COLUMN selection TEXT NOT NULL DEFAULT 'multi';
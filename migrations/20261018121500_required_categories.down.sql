@@ -0,0 +1,2 @@
+ALTER TABLE columns DROP COLUMN requires_triage;
+ALTER TABLE categories DROP COLUMN is_required;
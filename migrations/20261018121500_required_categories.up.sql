@@ -0,0 +1,4 @@
+-- Every activity of the board should hold a tag of a required category.
+ALTER TABLE categories ADD COLUMN is_required BOOLEAN NOT NULL DEFAULT FALSE;
+-- Activities missing a tag of a required category cannot be moved into the column.
+ALTER TABLE columns ADD COLUMN requires_triage BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,10 @@
+DROP TRIGGER activity_search_activity_insert;
+DROP TRIGGER activity_search_activity_update;
+DROP TRIGGER activity_search_activity_delete;
+DROP TRIGGER activity_search_comment_insert;
+DROP TRIGGER activity_search_comment_update;
+DROP TRIGGER activity_search_comment_delete;
+DROP TRIGGER activity_search_checklist_insert;
+DROP TRIGGER activity_search_checklist_update;
+DROP TRIGGER activity_search_checklist_delete;
+DROP TABLE activity_search;
@@ -0,0 +1,91 @@
+-- Full-text index of activities. The rowid of a row is the id of its activity.
+-- Rows are rebuilt by the triggers below whenever an activity, its comments or its checklist change.
+CREATE VIRTUAL TABLE activity_search USING fts5(
+    name,
+    body,
+    comments,
+    checklist,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO activity_search (rowid, name, body, comments, checklist)
+SELECT id, name, COALESCE(body, ''),
+    (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+    (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+FROM activities;
+
+CREATE TRIGGER activity_search_activity_insert AFTER INSERT ON activities BEGIN
+    DELETE FROM activity_search WHERE rowid = NEW.id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER activity_search_activity_update AFTER UPDATE OF name, body ON activities BEGIN
+    DELETE FROM activity_search WHERE rowid = NEW.id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER activity_search_activity_delete AFTER DELETE ON activities BEGIN
+    DELETE FROM activity_search WHERE rowid = OLD.id;
+END;
+
+CREATE TRIGGER activity_search_comment_insert AFTER INSERT ON activity_comments BEGIN
+    DELETE FROM activity_search WHERE rowid = NEW.activity_id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = NEW.activity_id;
+END;
+
+CREATE TRIGGER activity_search_comment_update AFTER UPDATE OF text ON activity_comments BEGIN
+    DELETE FROM activity_search WHERE rowid = NEW.activity_id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = NEW.activity_id;
+END;
+
+CREATE TRIGGER activity_search_comment_delete AFTER DELETE ON activity_comments BEGIN
+    DELETE FROM activity_search WHERE rowid = OLD.activity_id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = OLD.activity_id;
+END;
+
+CREATE TRIGGER activity_search_checklist_insert AFTER INSERT ON checklist_items BEGIN
+    DELETE FROM activity_search WHERE rowid = NEW.activity_id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = NEW.activity_id;
+END;
+
+CREATE TRIGGER activity_search_checklist_update AFTER UPDATE OF text ON checklist_items BEGIN
+    DELETE FROM activity_search WHERE rowid = NEW.activity_id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = NEW.activity_id;
+END;
+
+CREATE TRIGGER activity_search_checklist_delete AFTER DELETE ON checklist_items BEGIN
+    DELETE FROM activity_search WHERE rowid = OLD.activity_id;
+    INSERT INTO activity_search (rowid, name, body, comments, checklist)
+    SELECT id, name, COALESCE(body, ''),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM activity_comments WHERE activity_id = activities.id),
+        (SELECT COALESCE(GROUP_CONCAT(text, ' '), '') FROM checklist_items WHERE activity_id = activities.id)
+    FROM activities WHERE id = OLD.activity_id;
+END;
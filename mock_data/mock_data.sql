@@ -1,21 +1,21 @@
-INSERT INTO "columns" ("id", "name", "ordinal")
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "is_done")
 VALUES
-    (1, 'New', 0),
-    (2, 'In progress', 1),
-    (3, 'Done', 2);
+    (1, 'New', 0, 1, FALSE),
+    (2, 'In progress', 1, 1, FALSE),
+    (3, 'Done', 2, 1, TRUE);
 
-INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal")
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
 VALUES
-    (1, 'Profit', 'The $$$ will start rolling in', 1, 0),
-    (2, 'Avoid burnout', 'Remember kids, burnout is unhealthy', 1, 1),
-    (3, 'Hustle more', 'Stay focused, like a sigma', 2, 0),
-    (4, 'Have an idea', 'Come up with a killer idea', 3, 0),
-    (5, 'Get motivated', 'Watch motivational videos at 3am', 3, 1);
+    (1, 'Profit', 'The $$$ will start rolling in', 1, 0, 1),
+    (2, 'Avoid burnout', 'Remember kids, burnout is unhealthy', 1, 1, 1),
+    (3, 'Hustle more', 'Stay focused, like a sigma', 2, 0, 1),
+    (4, 'Have an idea', 'Come up with a killer idea', 3, 0, 1),
+    (5, 'Get motivated', 'Watch motivational videos at 3am', 3, 1, 1);
 
-INSERT INTO "categories" ("id", "name", "ordinal")
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id")
 VALUES
-    (1, 'Size', 0),
-    (2, 'Priority', 1);
+    (1, 'Size', 0, 1),
+    (2, 'Priority', 1, 1);
 
 INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
 VALUES
@@ -1,6 +1,19 @@
 pub mod activity;
+pub mod attachments;
+pub mod boards;
+pub mod bulk;
 pub mod category;
+pub mod checklist;
 pub mod columns;
+pub mod comments;
+pub mod custom_fields;
 pub mod fetch;
+pub mod history;
+pub mod recurrence;
+pub mod relations;
+pub mod search;
 pub mod splashscreen;
+pub mod swimlanes;
 pub mod tags;
+pub mod trash;
+pub mod undo;
@@ -1,12 +1,20 @@
 use std::collections::HashMap;
 
-use anyhow::Context;
 use entity::activities;
 use sea_orm::DbConn;
 use serde::{Deserialize, Serialize};
 use tauri::State;
+use time::{Date, Duration, OffsetDateTime};
 
-use crate::{database::activity::Mutation, errors::AppError};
+use crate::{
+    database::{
+        activity::{Mutation, Query},
+        attachments,
+        undo::UndoStack,
+    },
+    errors::AppError,
+    utils::file_store::FileStore,
+};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,23 +22,183 @@ pub struct CreateActivityInput {
     pub name: String,
     pub body: Option<String>,
     pub column_id: i32,
+    pub start_date: Option<Date>,
+    pub due_date: Option<Date>,
+    /// Puts the activity into the column even if that exceeds the work-in-progress limit of the column.
+    #[serde(default)]
+    pub allow_over_limit: bool,
+}
+
+/// Column whose work-in-progress limit was exceeded because the limit was overridden.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WipLimitBreachOutput {
+    pub column_id: i32,
+    pub limit: i32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateActivityOutput {
+    #[serde(flatten)]
+    pub activity: activities::Model,
+    pub wip_limit_breach: Option<WipLimitBreachOutput>,
 }
 
 #[tauri::command]
 pub async fn create_activity(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: CreateActivityInput,
+) -> Result<CreateActivityOutput, AppError> {
+    stack
+        .record(db.inner(), Mutation::create_activity(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateActivityInput {
+    pub id: i32,
+    /// Column of the copy; `None` keeps the column of the original.
+    pub column_id: Option<i32>,
+    /// Appended to the name of the original; `None` means " (copy)".
+    pub name_suffix: Option<String>,
+}
+
+#[tauri::command]
+pub async fn duplicate_activity(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: DuplicateActivityInput,
 ) -> Result<activities::Model, AppError> {
-    let model = Mutation::create_activity(db.inner(), data).await?;
-    Ok(model)
+    stack
+        .record(db.inner(), Mutation::duplicate_activity(db.inner(), data))
+        .await
 }
 
 #[tauri::command]
-pub async fn delete_activity<'a>(db: State<'a, DbConn>, id: i32) -> Result<(), AppError> {
-    Mutation::delete_activity_by_id(db.inner(), id)
+pub async fn delete_activity<'a>(
+    db: State<'a, DbConn>,
+    stack: State<'a, UndoStack>,
+    store: State<'a, FileStore>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::delete_activity_by_id(db.inner(), id))
+        .await?;
+    attachments::Mutation::collect_garbage(
+        db.inner(),
+        store.inner(),
+        &stack.attachment_hashes().await,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn archive_activity(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::archive_activity_by_id(db.inner(), id))
         .await
-        .with_context(|| "failed to delete activity")?;
-    Ok(())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnarchiveActivityInput {
+    pub id: i32,
+    pub column_id: Option<i32>,
+    pub new_ord: i32,
+}
+
+#[tauri::command]
+pub async fn unarchive_activity(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UnarchiveActivityInput,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::unarchive_activity_by_id(db.inner(), data),
+        )
+        .await
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedActivityOutput {
+    pub name: String,
+    pub body: Option<String>,
+    pub tags: Vec<i32>,
+    pub board_id: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub archived_at: OffsetDateTime,
+}
+
+#[tauri::command]
+pub async fn list_archived_activities(
+    db: State<'_, DbConn>,
+    board_id: Option<i32>,
+) -> Result<HashMap<i32, ArchivedActivityOutput>, AppError> {
+    let activities = Query::all_archived_activities(db.inner(), board_id).await?;
+    Ok(activities)
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DueActivityOutput {
+    pub id: i32,
+    pub name: String,
+    pub column_id: Option<i32>,
+    pub board_id: i32,
+    pub start_date: Option<Date>,
+    pub due_date: Date,
+}
+
+/// Fetches activities with a due date earlier than `today`, the earliest first.
+///
+/// `today` is the current date in the local time zone of the user.
+#[tauri::command]
+pub async fn list_overdue_activities(
+    db: State<'_, DbConn>,
+    board_id: Option<i32>,
+    today: Date,
+) -> Result<Vec<DueActivityOutput>, AppError> {
+    let activities = Query::overdue_activities(db.inner(), board_id, today).await?;
+    Ok(activities)
+}
+
+/// Fetches activities that are due on `today`.
+///
+/// `today` is the current date in the local time zone of the user.
+#[tauri::command]
+pub async fn list_activities_due_today(
+    db: State<'_, DbConn>,
+    board_id: Option<i32>,
+    today: Date,
+) -> Result<Vec<DueActivityOutput>, AppError> {
+    let activities = Query::activities_due_between(db.inner(), board_id, today, today).await?;
+    Ok(activities)
+}
+
+/// Fetches activities that are due between `today` and `days` days later inclusive, the earliest first.
+///
+/// `today` is the current date in the local time zone of the user.
+/// A negative number of `days` is rejected when the arguments are deserialized.
+#[tauri::command]
+pub async fn list_activities_due_within(
+    db: State<'_, DbConn>,
+    board_id: Option<i32>,
+    today: Date,
+    days: u32,
+) -> Result<Vec<DueActivityOutput>, AppError> {
+    let last_day = today.saturating_add(Duration::days(days.into()));
+    let activities = Query::activities_due_between(db.inner(), board_id, today, last_day).await?;
+    Ok(activities)
 }
 
 #[derive(Serialize, Debug)]
@@ -99,17 +267,22 @@ pub struct UpdateActivityContentInput {
     pub id: i32,
     pub name: String,
     pub body: Option<String>,
+    pub start_date: Option<Date>,
+    pub due_date: Option<Date>,
 }
 
 #[tauri::command]
 pub async fn update_activity_content(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateActivityContentInput,
 ) -> Result<(), AppError> {
-    Mutation::update_activity_content_by_id(db.inner(), data)
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_activity_content_by_id(db.inner(), data),
+        )
         .await
-        .context("failed to update activity content")?;
-    Ok(())
 }
 
 #[derive(Deserialize)]
@@ -118,17 +291,24 @@ pub struct UpdateActivityColumnInput {
     pub id: i32,
     pub column_id: Option<i32>,
     pub new_ord: i32,
+    /// Moves the activity into the column even if that exceeds the work-in-progress limit of the column.
+    #[serde(default)]
+    pub allow_over_limit: bool,
 }
 
+/// Returns the exceeded limit of the target column if `allow_over_limit` was needed for the move.
 #[tauri::command]
 pub async fn update_activity_column(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateActivityColumnInput,
-) -> Result<(), AppError> {
-    Mutation::update_activity_column_by_id(&db, data)
+) -> Result<Option<WipLimitBreachOutput>, AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_activity_column_by_id(db.inner(), data),
+        )
         .await
-        .context("failed to update activity column")?;
-    Ok(())
 }
 
 #[derive(Deserialize)]
@@ -142,9 +322,12 @@ pub struct AddTagToActivityInput {
 #[tauri::command]
 pub async fn add_tag_to_activity(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: AddTagToActivityInput,
 ) -> Result<(), AppError> {
-    Mutation::add_tag_to_activity(db.inner(), data).await
+    stack
+        .record(db.inner(), Mutation::add_tag_to_activity(db.inner(), data))
+        .await
 }
 
 #[derive(Deserialize)]
@@ -158,7 +341,13 @@ pub struct RemoveTagFromActivityInput {
 #[tauri::command]
 pub async fn remove_tag_from_activity(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: RemoveTagFromActivityInput,
 ) -> Result<(), AppError> {
-    Mutation::remove_tag_from_activity(db.inner(), data).await
+    stack
+        .record(
+            db.inner(),
+            Mutation::remove_tag_from_activity(db.inner(), data),
+        )
+        .await
 }
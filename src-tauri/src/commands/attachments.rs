@@ -0,0 +1,65 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use entity::attachments;
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use time::OffsetDateTime;
+
+use crate::{
+    database::{
+        attachments::{Mutation, Query},
+        undo::UndoStack,
+    },
+    errors::AppError,
+    utils::file_store::FileStore,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddAttachmentInput {
+    pub activity_id: i32,
+    pub path: PathBuf,
+}
+
+#[tauri::command]
+pub async fn add_attachment(
+    db: State<'_, DbConn>,
+    store: State<'_, FileStore>,
+    data: AddAttachmentInput,
+) -> Result<attachments::Model, AppError> {
+    Mutation::insert_attachment(db.inner(), store.inner(), data).await
+}
+
+#[tauri::command]
+pub async fn delete_attachment(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    store: State<'_, FileStore>,
+    id: i32,
+) -> Result<(), AppError> {
+    let keep = stack.attachment_hashes().await;
+    Mutation::delete_attachment_by_id(db.inner(), store.inner(), &keep, id).await
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentOutput {
+    pub name: String,
+    pub size: i64,
+    pub mime_type: String,
+    pub path: PathBuf,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[tauri::command]
+pub async fn list_attachments(
+    db: State<'_, DbConn>,
+    store: State<'_, FileStore>,
+    activity_id: i32,
+) -> Result<HashMap<i32, AttachmentOutput>, AppError> {
+    let attachments =
+        Query::all_activity_attachments(db.inner(), store.inner(), activity_id).await?;
+    Ok(attachments)
+}
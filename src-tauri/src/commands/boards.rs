@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use entity::boards;
+use sea_orm::DbConn;
+use serde::Deserialize;
+use tauri::State;
+
+use crate::{
+    commands::fetch::BoardOutput,
+    database::{
+        attachments,
+        boards::{Mutation, Query},
+        undo::UndoStack,
+    },
+    errors::AppError,
+    utils::file_store::FileStore,
+};
+
+#[tauri::command]
+pub async fn create_board(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    name: String,
+) -> Result<boards::Model, AppError> {
+    stack
+        .record(db.inner(), Mutation::insert_board(db.inner(), name))
+        .await
+}
+
+#[tauri::command]
+pub async fn fetch_boards(db: State<'_, DbConn>) -> Result<HashMap<i32, BoardOutput>, AppError> {
+    let boards = Query::all_boards(db.inner()).await?;
+    Ok(boards)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameBoardInput {
+    pub id: i32,
+    pub new_name: String,
+}
+
+#[tauri::command]
+pub async fn rename_board(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: RenameBoardInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::update_board_name(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBoardOrdinalInput {
+    pub board_id: i32,
+    pub new_ord: i32,
+}
+
+#[tauri::command]
+pub async fn update_board_ordinal(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateBoardOrdinalInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::update_board_ordinal(db.inner(), data))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_board(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    store: State<'_, FileStore>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .discard(Mutation::delete_board_by_id(db.inner(), id))
+        .await?;
+    attachments::Mutation::collect_garbage(
+        db.inner(),
+        store.inner(),
+        &stack.attachment_hashes().await,
+    )
+    .await
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{
+    database::{attachments, bulk::Mutation, undo::UndoStack},
+    errors::AppError,
+    utils::file_store::FileStore,
+};
+
+/// Outcome of a bulk operation for a single activity.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase", tag = "status", content = "error")]
+pub enum BulkItemOutput {
+    Done,
+    /// The activity was left as it was for the given reason; other activities are still processed.
+    Skipped(AppError),
+}
+
+/// Outcomes of a bulk operation associated with activity ids.
+pub type BulkOutput = HashMap<i32, BulkItemOutput>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkMoveActivitiesInput {
+    pub ids: Vec<i32>,
+    pub column_id: i32,
+    /// Position of the first moved activity in the column.
+    pub new_ord: i32,
+}
+
+#[tauri::command]
+pub async fn bulk_move_activities(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: BulkMoveActivitiesInput,
+) -> Result<BulkOutput, AppError> {
+    stack
+        .record(db.inner(), Mutation::move_activities(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTagInput {
+    pub ids: Vec<i32>,
+    pub tag_id: i32,
+}
+
+#[tauri::command]
+pub async fn bulk_add_tag(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: BulkTagInput,
+) -> Result<BulkOutput, AppError> {
+    stack
+        .record(db.inner(), Mutation::add_tag(db.inner(), data))
+        .await
+}
+
+#[tauri::command]
+pub async fn bulk_remove_tag(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: BulkTagInput,
+) -> Result<BulkOutput, AppError> {
+    stack
+        .record(db.inner(), Mutation::remove_tag(db.inner(), data))
+        .await
+}
+
+#[tauri::command]
+pub async fn bulk_archive_activities(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    ids: Vec<i32>,
+) -> Result<BulkOutput, AppError> {
+    stack
+        .record(db.inner(), Mutation::archive_activities(db.inner(), ids))
+        .await
+}
+
+#[tauri::command]
+pub async fn bulk_delete_activities<'a>(
+    db: State<'a, DbConn>,
+    stack: State<'a, UndoStack>,
+    store: State<'a, FileStore>,
+    ids: Vec<i32>,
+) -> Result<BulkOutput, AppError> {
+    let out = stack
+        .record(db.inner(), Mutation::delete_activities(db.inner(), ids))
+        .await?;
+    attachments::Mutation::collect_garbage(
+        db.inner(),
+        store.inner(),
+        &stack.attachment_hashes().await,
+    )
+    .await?;
+    Ok(out)
+}
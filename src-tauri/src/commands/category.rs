@@ -1,18 +1,34 @@
 use std::collections::HashMap;
 
-use entity::categories;
+use entity::{
+    categories,
+    sea_orm_active_enums::{SelectionMode, TagPalette},
+};
 use sea_orm::DbConn;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-use crate::{database::category::Mutation, errors::AppError};
+use crate::{
+    database::{
+        category::{Mutation, Query},
+        undo::UndoStack,
+    },
+    errors::AppError,
+};
 
 #[tauri::command]
 pub async fn create_category(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    board_id: i32,
     name: String,
 ) -> Result<categories::Model, AppError> {
-    Mutation::insert_category(db.inner(), name).await
+    stack
+        .record(
+            db.inner(),
+            Mutation::insert_category(db.inner(), board_id, name),
+        )
+        .await
 }
 
 #[derive(Serialize)]
@@ -22,6 +38,8 @@ pub struct TagData {
     pub tag: String,
     pub ordinal: i32,
     pub color: String,
+    /// Text color readable on top of `color`.
+    pub text_color: String,
 }
 
 #[derive(Serialize, Default)]
@@ -59,9 +77,99 @@ pub struct UpdateCategoryNameInput {
 #[tauri::command]
 pub async fn update_category_name(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateCategoryNameInput,
 ) -> Result<(), AppError> {
-    Mutation::update_category_name(db.inner(), data).await
+    stack
+        .record(db.inner(), Mutation::update_category_name(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategoryPaletteInput {
+    pub category_id: i32,
+    pub palette: TagPalette,
+}
+
+#[tauri::command]
+pub async fn update_category_palette(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateCategoryPaletteInput,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_category_palette(db.inner(), data),
+        )
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategorySelectionInput {
+    pub category_id: i32,
+    pub selection: SelectionMode,
+    /// Whether to remove conflicting tags from activities instead of reporting them.
+    pub resolve: bool,
+}
+
+#[tauri::command]
+pub async fn update_category_selection(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateCategorySelectionInput,
+) -> Result<Vec<i32>, AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_category_selection(db.inner(), data),
+        )
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategoryRequiredInput {
+    pub category_id: i32,
+    pub is_required: bool,
+}
+
+#[tauri::command]
+pub async fn update_category_required(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateCategoryRequiredInput,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_category_required(db.inner(), data),
+        )
+        .await
+}
+
+/// Fetches the activities of the board that miss a tag of a required category,
+/// mapped to the ids of the missing categories.
+#[tauri::command]
+pub async fn list_activities_missing_required_tags(
+    db: State<'_, DbConn>,
+    board_id: i32,
+) -> Result<HashMap<i32, Vec<i32>>, AppError> {
+    let res = Query::activities_missing_required_tags(db.inner(), board_id).await?;
+    Ok(res)
+}
+
+#[tauri::command]
+pub async fn recolor_category(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::recolor_category(db.inner(), id))
+        .await
 }
 
 #[derive(Deserialize)]
@@ -72,6 +180,56 @@ pub struct UpdateCategoryOrdinalInput {
 }
 
 #[tauri::command]
-pub async fn delete_category(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
-    Mutation::delete_category_by_id(db.inner(), id).await
+pub async fn update_category_ordinal(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateCategoryOrdinalInput,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_category_ordinal(db.inner(), data),
+        )
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCategoryOrderInput {
+    pub board_id: i32,
+    /// Ids of all categories of the board outside the trash, in their new order.
+    pub ids: Vec<i32>,
+}
+
+#[tauri::command]
+pub async fn set_category_order(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: SetCategoryOrderInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::set_category_order(db.inner(), data))
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_category(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::delete_category_by_id(db.inner(), id))
+        .await
+}
+
+#[tauri::command]
+pub async fn restore_category(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::restore_category_by_id(db.inner(), id))
+        .await
 }
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use entity::checklist_items;
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{
+    database::checklist::{Mutation, Query},
+    errors::AppError,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChecklistItemInput {
+    pub activity_id: i32,
+    pub text: String,
+}
+
+#[tauri::command]
+pub async fn create_checklist_item(
+    db: State<'_, DbConn>,
+    data: CreateChecklistItemInput,
+) -> Result<checklist_items::Model, AppError> {
+    Mutation::insert_checklist_item(db.inner(), data).await
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItemOutput {
+    pub text: String,
+    pub done: bool,
+    pub ordinal: i32,
+}
+
+#[tauri::command]
+pub async fn fetch_checklist(
+    db: State<'_, DbConn>,
+    activity_id: i32,
+) -> Result<HashMap<i32, ChecklistItemOutput>, AppError> {
+    let items = Query::all_checklist_items(db.inner(), activity_id).await?;
+    Ok(items)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChecklistItemTextInput {
+    pub id: i32,
+    pub text: String,
+}
+
+#[tauri::command]
+pub async fn update_checklist_item_text(
+    db: State<'_, DbConn>,
+    data: UpdateChecklistItemTextInput,
+) -> Result<(), AppError> {
+    Mutation::update_checklist_item_text(db.inner(), data).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChecklistItemDoneInput {
+    pub id: i32,
+    pub done: bool,
+}
+
+#[tauri::command]
+pub async fn update_checklist_item_done(
+    db: State<'_, DbConn>,
+    data: UpdateChecklistItemDoneInput,
+) -> Result<(), AppError> {
+    Mutation::update_checklist_item_done(db.inner(), data).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChecklistItemOrdinalInput {
+    pub id: i32,
+    pub new_ord: i32,
+}
+
+#[tauri::command]
+pub async fn update_checklist_item_ordinal(
+    db: State<'_, DbConn>,
+    data: UpdateChecklistItemOrdinalInput,
+) -> Result<(), AppError> {
+    Mutation::update_checklist_item_ordinal(db.inner(), data).await
+}
+
+#[tauri::command]
+pub async fn delete_checklist_item(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
+    Mutation::delete_checklist_item_by_id(db.inner(), id).await
+}
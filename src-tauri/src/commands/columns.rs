@@ -3,14 +3,24 @@ use sea_orm::DbConn;
 use serde::Deserialize;
 use tauri::State;
 
-use crate::{database::columns::Mutation, errors::AppError};
+use crate::{
+    database::{columns::Mutation, undo::UndoStack},
+    errors::AppError,
+};
 
 #[tauri::command]
 pub async fn create_column(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    board_id: i32,
     name: String,
 ) -> Result<columns::Model, AppError> {
-    Mutation::insert_column(db.inner(), name).await
+    stack
+        .record(
+            db.inner(),
+            Mutation::insert_column(db.inner(), board_id, name),
+        )
+        .await
 }
 
 #[derive(Deserialize)]
@@ -21,13 +31,93 @@ pub struct RenameColumnInput {
 }
 
 #[tauri::command]
-pub async fn rename_column(db: State<'_, DbConn>, data: RenameColumnInput) -> Result<(), AppError> {
-    Mutation::update_column_name(db.inner(), data).await
+pub async fn rename_column(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: RenameColumnInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::update_column_name(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateColumnDoneInput {
+    pub id: i32,
+    pub is_done: bool,
+}
+
+#[tauri::command]
+pub async fn update_column_done(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateColumnDoneInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::update_column_done(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateColumnTriageInput {
+    pub id: i32,
+    pub requires_triage: bool,
+}
+
+#[tauri::command]
+pub async fn update_column_triage(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateColumnTriageInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::update_column_triage(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateColumnWipLimitInput {
+    pub id: i32,
+    pub wip_limit: Option<i32>,
+}
+
+#[tauri::command]
+pub async fn update_column_wip_limit(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: UpdateColumnWipLimitInput,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_column_wip_limit(db.inner(), data),
+        )
+        .await
+}
+
+#[tauri::command]
+pub async fn delete_column(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::delete_column_by_id(db.inner(), id))
+        .await
 }
 
 #[tauri::command]
-pub async fn delete_column(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
-    Mutation::delete_column_by_id(db.inner(), id).await
+pub async fn restore_column(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::restore_column_by_id(db.inner(), id))
+        .await
 }
 
 #[derive(Deserialize)]
@@ -40,7 +130,32 @@ pub struct UpdateColumnOrdinalInput {
 #[tauri::command]
 pub async fn update_column_ordinal(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateColumnOrdinalInput,
 ) -> Result<(), AppError> {
-    Mutation::update_column_ordinal(db.inner(), data).await
+    stack
+        .record(
+            db.inner(),
+            Mutation::update_column_ordinal(db.inner(), data),
+        )
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetColumnOrderInput {
+    pub board_id: i32,
+    /// Ids of all columns of the board outside the trash, in their new order.
+    pub ids: Vec<i32>,
+}
+
+#[tauri::command]
+pub async fn set_column_order(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: SetColumnOrderInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::set_column_order(db.inner(), data))
+        .await
 }
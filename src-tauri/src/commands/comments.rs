@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use entity::activity_comments;
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use time::OffsetDateTime;
+
+use crate::{
+    database::comments::{Mutation, Query},
+    errors::AppError,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddCommentInput {
+    pub activity_id: i32,
+    pub author: String,
+    pub text: String,
+}
+
+#[tauri::command]
+pub async fn add_comment(
+    db: State<'_, DbConn>,
+    data: AddCommentInput,
+) -> Result<activity_comments::Model, AppError> {
+    Mutation::insert_comment(db.inner(), data).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditCommentInput {
+    pub id: i32,
+    pub text: String,
+}
+
+#[tauri::command]
+pub async fn edit_comment(db: State<'_, DbConn>, data: EditCommentInput) -> Result<(), AppError> {
+    Mutation::update_comment_text(db.inner(), data).await
+}
+
+#[tauri::command]
+pub async fn delete_comment(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
+    Mutation::delete_comment_by_id(db.inner(), id).await
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentOutput {
+    pub author: String,
+    pub text: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[tauri::command]
+pub async fn list_comments(
+    db: State<'_, DbConn>,
+    activity_id: i32,
+) -> Result<HashMap<i32, CommentOutput>, AppError> {
+    let comments = Query::all_activity_comments(db.inner(), activity_id).await?;
+    Ok(comments)
+}
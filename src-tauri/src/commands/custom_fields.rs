@@ -0,0 +1,82 @@
+use entity::{custom_field_options, custom_fields, sea_orm_active_enums::FieldKind};
+use sea_orm::DbConn;
+use serde::Deserialize;
+use tauri::State;
+
+use crate::{database::custom_fields::Mutation, errors::AppError};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCustomFieldInput {
+    pub board_id: i32,
+    pub name: String,
+    pub kind: FieldKind,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn create_custom_field(
+    db: State<'_, DbConn>,
+    data: CreateCustomFieldInput,
+) -> Result<custom_fields::Model, AppError> {
+    Mutation::insert_custom_field(db.inner(), data).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameCustomFieldInput {
+    pub id: i32,
+    pub new_name: String,
+}
+
+#[tauri::command]
+pub async fn rename_custom_field(
+    db: State<'_, DbConn>,
+    data: RenameCustomFieldInput,
+) -> Result<(), AppError> {
+    Mutation::update_custom_field_name(db.inner(), data).await
+}
+
+#[tauri::command]
+pub async fn delete_custom_field(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
+    Mutation::delete_custom_field_by_id(db.inner(), id).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddCustomFieldOptionInput {
+    pub field_id: i32,
+    pub name: String,
+}
+
+#[tauri::command]
+pub async fn add_custom_field_option(
+    db: State<'_, DbConn>,
+    data: AddCustomFieldOptionInput,
+) -> Result<custom_field_options::Model, AppError> {
+    Mutation::insert_custom_field_option(db.inner(), data).await
+}
+
+#[tauri::command]
+pub async fn delete_custom_field_option(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
+    Mutation::delete_custom_field_option_by_id(db.inner(), id).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetActivityFieldValueInput {
+    pub activity_id: i32,
+    pub field_id: i32,
+    /// A number, a string, a `YYYY-MM-DD` date or an option id, depending on the kind of the field.
+    /// `null` clears the value.
+    pub value: serde_json::Value,
+}
+
+#[tauri::command]
+pub async fn set_activity_field_value(
+    db: State<'_, DbConn>,
+    data: SetActivityFieldValueInput,
+) -> Result<(), AppError> {
+    Mutation::set_activity_field_value(db.inner(), data).await
+}
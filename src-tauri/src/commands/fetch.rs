@@ -1,19 +1,32 @@
 use std::collections::HashMap;
 
+use entity::sea_orm_active_enums::{FieldKind, SelectionMode, TagPalette};
 use sea_orm::DbConn;
 use serde::Serialize;
 use tauri::State;
+use time::{Date, OffsetDateTime};
 
 use crate::{
-    database::{activity, category, columns, tags},
+    database::{activity, boards, category, columns, custom_fields, tags},
     errors::AppError,
 };
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardOutput {
+    pub name: String,
+    pub ordinal: i32,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColumnOutput {
     pub name: String,
     pub ordinal: i32,
+    pub board_id: i32,
+    pub is_done: bool,
+    pub wip_limit: Option<i32>,
+    pub requires_triage: bool,
     pub activities: Vec<i32>,
 }
 
@@ -22,6 +35,8 @@ pub struct ColumnOutput {
 pub struct CategoryTagOutput {
     pub name: String,
     pub color: String,
+    /// Text color readable on top of `color`.
+    pub text_color: String,
     pub category_id: i32,
     pub ordinal: i32,
 }
@@ -31,6 +46,8 @@ pub struct CategoryTagOutput {
 pub struct OtherTagOutput {
     pub name: String,
     pub color: String,
+    /// Text color readable on top of `color`.
+    pub text_color: String,
     pub ordinal: i32,
 }
 
@@ -40,6 +57,25 @@ pub struct CategoryOutput {
     pub name: String,
     pub ordinal: i32,
     pub tags: Vec<i32>,
+    pub board_id: i32,
+    pub palette: TagPalette,
+    pub selection: SelectionMode,
+    pub is_required: bool,
+}
+
+#[derive(Serialize, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistProgressOutput {
+    pub completed: i32,
+    pub total: i32,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityRelationsOutput {
+    pub blocks: Vec<i32>,
+    pub blocked_by: Vec<i32>,
+    pub related: Vec<i32>,
 }
 
 #[derive(Serialize)]
@@ -49,6 +85,19 @@ pub struct ActivityOutput {
     pub body: Option<String>,
     pub ordinal: i32,
     pub tags: Vec<i32>,
+    pub board_id: i32,
+    pub start_date: Option<Date>,
+    pub due_date: Option<Date>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub column_entered_at: Option<OffsetDateTime>,
+    pub checklist: ChecklistProgressOutput,
+    pub relations: ActivityRelationsOutput,
+    pub comment_count: i32,
+    pub field_values: HashMap<i32, serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -59,33 +108,99 @@ pub struct ColumnActivityOutput {
     pub ordinal: i32,
     pub tags: Vec<i32>,
     pub column_id: i32,
+    pub start_date: Option<Date>,
+    pub due_date: Option<Date>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub column_entered_at: Option<OffsetDateTime>,
+    pub checklist: ChecklistProgressOutput,
+    pub relations: ActivityRelationsOutput,
+    pub comment_count: i32,
+    pub field_values: HashMap<i32, serde_json::Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldOutput {
+    pub name: String,
+    pub kind: FieldKind,
+    pub ordinal: i32,
+    pub board_id: i32,
+    pub options: Vec<i32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldOptionOutput {
+    pub name: String,
+    pub ordinal: i32,
+    pub field_id: i32,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FetchOutput {
+    boards: HashMap<i32, BoardOutput>,
     columns: HashMap<i32, ColumnOutput>,
     activities: HashMap<i32, ColumnActivityOutput>,
     other_activities: HashMap<i32, ActivityOutput>,
     categories: HashMap<i32, CategoryOutput>,
     category_tags: HashMap<i32, CategoryTagOutput>,
     other_tags: HashMap<i32, OtherTagOutput>,
+    custom_fields: HashMap<i32, CustomFieldOutput>,
+    field_options: HashMap<i32, FieldOptionOutput>,
 }
 
 #[tauri::command]
 pub async fn fetch_all(db: State<'_, DbConn>) -> Result<FetchOutput, AppError> {
-    let activities = activity::Query::all_column_activities(&db).await?;
-    let other_activities = activity::Query::all_other_activities(&db).await?;
-    let columns = columns::Query::all_columns(&db).await?;
-    let (categories, category_tags) = category::Query::all_with_category_tags(&db).await?;
+    let boards = boards::Query::all_boards(&db).await?;
+    let activities = activity::Query::all_column_activities(&db, None).await?;
+    let other_activities = activity::Query::all_other_activities(&db, None).await?;
+    let columns = columns::Query::all_columns(&db, None).await?;
+    let (categories, category_tags) = category::Query::all_with_category_tags(&db, None).await?;
+    let other_tags = tags::Query::all_other_tags(&db).await?;
+    let (custom_fields, field_options) = custom_fields::Query::all_with_options(&db, None).await?;
+
+    Ok(FetchOutput {
+        boards,
+        columns,
+        activities,
+        other_activities,
+        categories,
+        category_tags,
+        other_tags,
+        custom_fields,
+        field_options,
+    })
+}
+
+/// Fetches the contents of a single board.
+///
+/// Tags without a category are not owned by any board, so all of them are returned.
+#[tauri::command]
+pub async fn fetch_board(db: State<'_, DbConn>, board_id: i32) -> Result<FetchOutput, AppError> {
+    let boards = boards::Query::board_by_id(&db, board_id).await?;
+    let activities = activity::Query::all_column_activities(&db, Some(board_id)).await?;
+    let other_activities = activity::Query::all_other_activities(&db, Some(board_id)).await?;
+    let columns = columns::Query::all_columns(&db, Some(board_id)).await?;
+    let (categories, category_tags) =
+        category::Query::all_with_category_tags(&db, Some(board_id)).await?;
     let other_tags = tags::Query::all_other_tags(&db).await?;
+    let (custom_fields, field_options) =
+        custom_fields::Query::all_with_options(&db, Some(board_id)).await?;
 
     Ok(FetchOutput {
+        boards,
         columns,
         activities,
         other_activities,
         categories,
         category_tags,
         other_tags,
+        custom_fields,
+        field_options,
     })
 }
@@ -0,0 +1,31 @@
+use entity::sea_orm_active_enums::{EventAction, EventEntity};
+use sea_orm::DbConn;
+use serde::Serialize;
+use tauri::State;
+use time::OffsetDateTime;
+
+use crate::{database::history::Query, errors::AppError};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EventOutput {
+    pub id: i32,
+    pub entity: EventEntity,
+    pub entity_id: i32,
+    pub action: EventAction,
+    /// The entity before the mutation, or `None` if it did not exist.
+    pub before: Option<serde_json::Value>,
+    /// The entity after the mutation, or `None` if it no longer exists.
+    pub after: Option<serde_json::Value>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[tauri::command]
+pub async fn get_activity_history(
+    db: State<'_, DbConn>,
+    id: i32,
+) -> Result<Vec<EventOutput>, AppError> {
+    let events = Query::activity_history(db.inner(), id).await?;
+    Ok(events)
+}
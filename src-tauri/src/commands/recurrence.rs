@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use entity::{recurrences, sea_orm_active_enums::RecurrenceKind};
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use time::{Date, Weekday};
+
+use crate::{
+    database::recurrence::{Mutation, Query},
+    errors::AppError,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRecurrenceInput {
+    /// The activity whose name, body and tags are copied to every generated activity.
+    pub template_id: i32,
+    /// The column the generated activities are put into.
+    pub column_id: i32,
+    pub kind: RecurrenceKind,
+    /// Required for weekly rules.
+    #[serde(default)]
+    pub weekdays: Vec<Weekday>,
+    /// Required for monthly rules. Months shorter than the day use their last day instead.
+    pub day_of_month: Option<i32>,
+    /// Required for rules repeating every N days.
+    pub interval_days: Option<i32>,
+    /// The first day on which an activity may be generated.
+    pub start_date: Date,
+}
+
+#[tauri::command]
+pub async fn create_recurrence(
+    db: State<'_, DbConn>,
+    data: CreateRecurrenceInput,
+) -> Result<recurrences::Model, AppError> {
+    Mutation::insert_recurrence(db.inner(), data).await
+}
+
+#[tauri::command]
+pub async fn delete_recurrence(db: State<'_, DbConn>, id: i32) -> Result<(), AppError> {
+    Mutation::delete_recurrence_by_id(db.inner(), id).await
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceOutput {
+    pub template_id: i32,
+    pub column_id: i32,
+    pub kind: RecurrenceKind,
+    pub weekdays: Vec<Weekday>,
+    pub day_of_month: Option<i32>,
+    pub interval_days: Option<i32>,
+    pub start_date: Date,
+}
+
+#[tauri::command]
+pub async fn list_recurrences(
+    db: State<'_, DbConn>,
+) -> Result<HashMap<i32, RecurrenceOutput>, AppError> {
+    let recurrences = Query::all_recurrences(db.inner()).await?;
+    Ok(recurrences)
+}
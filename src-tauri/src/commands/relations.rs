@@ -0,0 +1,37 @@
+use entity::sea_orm_active_enums::RelationKind;
+use sea_orm::DbConn;
+use serde::Deserialize;
+use tauri::State;
+
+use crate::{database::relations::Mutation, errors::AppError};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkActivitiesInput {
+    pub activity_id: i32,
+    pub related_activity_id: i32,
+    pub kind: RelationKind,
+}
+
+#[tauri::command]
+pub async fn link_activities(
+    db: State<'_, DbConn>,
+    data: LinkActivitiesInput,
+) -> Result<(), AppError> {
+    Mutation::link_activities(db.inner(), data).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlinkActivitiesInput {
+    pub activity_id: i32,
+    pub related_activity_id: i32,
+}
+
+#[tauri::command]
+pub async fn unlink_activities(
+    db: State<'_, DbConn>,
+    data: UnlinkActivitiesInput,
+) -> Result<(), AppError> {
+    Mutation::unlink_activities(db.inner(), data).await
+}
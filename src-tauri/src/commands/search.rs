@@ -0,0 +1,39 @@
+use sea_orm::{DbConn, FromQueryResult};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{database::search::Query, errors::AppError};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchActivitiesInput {
+    /// Words and `"quoted phrases"` that must all match; a trailing `*` matches any text starting with the term.
+    pub query: String,
+    pub board_id: Option<i32>,
+    /// Maximum number of hits, 50 by default.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, FromQueryResult, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHitOutput {
+    pub activity_id: i32,
+    pub board_id: i32,
+    /// `None` means the stash.
+    pub column_id: Option<i32>,
+    /// Name of the activity escaped for HTML, with the matched terms wrapped in `<mark>` tags.
+    pub name: String,
+    /// Fragment of the best matching text escaped for HTML, with the matched terms wrapped in `<mark>` tags.
+    pub snippet: String,
+    /// Relevance of the hit; lower is better.
+    pub rank: f64,
+}
+
+#[tauri::command]
+pub async fn search_activities(
+    db: State<'_, DbConn>,
+    data: SearchActivitiesInput,
+) -> Result<Vec<SearchHitOutput>, AppError> {
+    let hits = Query::search_activities(db.inner(), data).await?;
+    Ok(hits)
+}
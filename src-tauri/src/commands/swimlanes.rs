@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::{
+    database::{
+        swimlanes::{Mutation, Query},
+        undo::UndoStack,
+    },
+    errors::AppError,
+};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SwimlaneOutput {
+    /// `None` for the lane of activities without a tag of the category.
+    pub tag_id: Option<i32>,
+    /// Activity ids associated with column ids, ordered by their ordinals.
+    pub cells: HashMap<i32, Vec<i32>>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SwimlanesOutput {
+    pub category_id: i32,
+    pub board_id: i32,
+    /// Lanes ordered like the tags of the category, followed by the lane without a tag.
+    pub lanes: Vec<SwimlaneOutput>,
+}
+
+#[tauri::command]
+pub async fn fetch_swimlanes(
+    db: State<'_, DbConn>,
+    category_id: i32,
+) -> Result<SwimlanesOutput, AppError> {
+    Query::swimlanes(db.inner(), category_id).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveActivityToLaneInput {
+    pub id: i32,
+    pub category_id: i32,
+    /// `None` moves the activity to the lane without a tag.
+    pub tag_id: Option<i32>,
+}
+
+#[tauri::command]
+pub async fn move_activity_to_lane(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: MoveActivityToLaneInput,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::move_activity_to_lane(db.inner(), data),
+        )
+        .await
+}
@@ -2,7 +2,11 @@ use sea_orm::DbConn;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-use crate::{database::tags::Mutation, errors::AppError, utils::coloring::rgb_int_to_string};
+use crate::{
+    database::{tags::Mutation, undo::UndoStack},
+    errors::AppError,
+    utils::coloring::{rgb_int_to_string, text_color},
+};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,20 +22,26 @@ pub struct CreateTagOutput {
     pub tag_name: String,
     pub category_id: Option<i32>,
     pub color: String,
+    /// Text color readable on top of `color`.
+    pub text_color: String,
     pub ordinal: i32,
 }
 
 #[tauri::command]
 pub async fn create_tag(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: CreateTagInput,
 ) -> Result<CreateTagOutput, AppError> {
-    let model = Mutation::create_tag(db.inner(), data).await?;
+    let model = stack
+        .record(db.inner(), Mutation::create_tag(db.inner(), data))
+        .await?;
     Ok(CreateTagOutput {
         id: model.id,
         tag_name: model.tag_name,
         category_id: model.category_id,
         color: rgb_int_to_string(model.color),
+        text_color: text_color(model.color).into(),
         ordinal: model.ordinal,
     })
 }
@@ -40,9 +50,21 @@ pub async fn create_tag(
 #[serde(rename_all = "camelCase")]
 pub struct AttachTagToCategoryInput {
     pub category_tag_id: i32,
+    /// `None` makes the tag a non-category tag.
     pub category_id: Option<i32>,
 }
 
+#[tauri::command]
+pub async fn attach_tag_to_category(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: AttachTagToCategoryInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::update_tag_category(db.inner(), data))
+        .await
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTagNameInput {
@@ -53,9 +75,12 @@ pub struct UpdateTagNameInput {
 #[tauri::command]
 pub async fn update_tag_name(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateTagNameInput,
 ) -> Result<(), AppError> {
-    Mutation::update_tag_name(db.inner(), data).await
+    stack
+        .record(db.inner(), Mutation::update_tag_name(db.inner(), data))
+        .await
 }
 
 #[derive(Deserialize)]
@@ -68,9 +93,32 @@ pub struct UpdateTagOrdinalInput {
 #[tauri::command]
 pub async fn update_tag_ordinal(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateTagOrdinalInput,
 ) -> Result<(), AppError> {
-    Mutation::update_tag_ordinal(db.inner(), data).await
+    stack
+        .record(db.inner(), Mutation::update_tag_ordinal(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTagOrderInput {
+    /// `None` means the non-category tags.
+    pub category_id: Option<i32>,
+    /// Ids of all tags of the category outside the trash, in their new order.
+    pub ids: Vec<i32>,
+}
+
+#[tauri::command]
+pub async fn set_tag_order(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: SetTagOrderInput,
+) -> Result<(), AppError> {
+    stack
+        .record(db.inner(), Mutation::set_tag_order(db.inner(), data))
+        .await
 }
 
 #[derive(Deserialize)]
@@ -83,12 +131,59 @@ pub struct UpdateTagColorInput {
 #[tauri::command]
 pub async fn update_tag_color(
     db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
     data: UpdateTagColorInput,
 ) -> Result<(), AppError> {
-    Mutation::update_tag_color(db.inner(), data).await
+    stack
+        .record(db.inner(), Mutation::update_tag_color(db.inner(), data))
+        .await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTagsInput {
+    pub source_ids: Vec<i32>,
+    pub target_id: i32,
+}
+
+/// Merges the source tags into the target tag and deletes the source tags.
+///
+/// Returns the number of activities whose tags changed.
+#[tauri::command]
+pub async fn merge_tags(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    data: MergeTagsInput,
+) -> Result<u64, AppError> {
+    stack
+        .record(db.inner(), Mutation::merge_tags(db.inner(), data))
+        .await
 }
 
 #[tauri::command]
-pub async fn delete_tag(db: State<'_, DbConn>, category_tag_id: i32) -> Result<(), AppError> {
-    Mutation::delete_tag_by_id(db.inner(), category_tag_id).await
+pub async fn delete_tag(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    category_tag_id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::delete_tag_by_id(db.inner(), category_tag_id),
+        )
+        .await
+}
+
+#[tauri::command]
+pub async fn restore_tag(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    category_tag_id: i32,
+) -> Result<(), AppError> {
+    stack
+        .record(
+            db.inner(),
+            Mutation::restore_tag_by_id(db.inner(), category_tag_id),
+        )
+        .await
 }
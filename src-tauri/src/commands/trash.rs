@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use sea_orm::DbConn;
+use serde::Serialize;
+use tauri::State;
+use time::OffsetDateTime;
+
+use crate::{
+    database::{category, columns, tags, trash::Mutation, undo::UndoStack},
+    errors::AppError,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedColumnOutput {
+    pub name: String,
+    pub board_id: i32,
+    pub activities: Vec<i32>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub deleted_at: OffsetDateTime,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedCategoryOutput {
+    pub name: String,
+    pub board_id: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub deleted_at: OffsetDateTime,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedTagOutput {
+    pub name: String,
+    pub category_id: Option<i32>,
+    pub color: String,
+    /// Text color readable on top of `color`.
+    pub text_color: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub deleted_at: OffsetDateTime,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashOutput {
+    pub columns: HashMap<i32, TrashedColumnOutput>,
+    pub categories: HashMap<i32, TrashedCategoryOutput>,
+    pub tags: HashMap<i32, TrashedTagOutput>,
+}
+
+#[tauri::command]
+pub async fn list_trash(db: State<'_, DbConn>) -> Result<TrashOutput, AppError> {
+    let columns = columns::Query::all_trashed_columns(&db).await?;
+    let categories = category::Query::all_trashed_categories(&db).await?;
+    let tags = tags::Query::all_trashed_tags(&db).await?;
+
+    Ok(TrashOutput {
+        columns,
+        categories,
+        tags,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeTrashOutput {
+    pub columns: u64,
+    pub categories: u64,
+    pub tags: u64,
+}
+
+/// Permanently deletes everything that has been in the trash for more than `older_than_days` days.
+///
+/// Returns the number of purged items of each kind. Purging cannot be undone and clears the undo history.
+#[tauri::command]
+pub async fn purge_trash(
+    db: State<'_, DbConn>,
+    stack: State<'_, UndoStack>,
+    older_than_days: i64,
+) -> Result<PurgeTrashOutput, AppError> {
+    stack
+        .discard(Mutation::purge_trash(db.inner(), older_than_days))
+        .await
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use entity::{activities, boards, categories, category_tags, columns};
+use sea_orm::DbConn;
+use serde::Serialize;
+use tauri::State;
+
+use crate::{
+    database::{
+        attachments,
+        undo::{Mutation, UndoStack},
+    },
+    errors::AppError,
+    utils::file_store::FileStore,
+};
+
+/// State of the entities affected by an undone or redone operation, associated with their ids.
+///
+/// `None` means that the entity no longer exists.
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoOutput {
+    pub activities: HashMap<i32, Option<activities::Model>>,
+    pub columns: HashMap<i32, Option<columns::Model>>,
+    pub categories: HashMap<i32, Option<categories::Model>>,
+    pub tags: HashMap<i32, Option<category_tags::Model>>,
+    pub boards: HashMap<i32, Option<boards::Model>>,
+}
+
+/// Reverts the latest operation on the board.
+///
+/// Returns `None` if there is nothing to undo.
+#[tauri::command]
+pub async fn undo<'a>(
+    db: State<'a, DbConn>,
+    stack: State<'a, UndoStack>,
+    store: State<'a, FileStore>,
+) -> Result<Option<UndoOutput>, AppError> {
+    let out = Mutation::undo(db.inner(), stack.inner()).await?;
+    attachments::Mutation::collect_garbage(
+        db.inner(),
+        store.inner(),
+        &stack.attachment_hashes().await,
+    )
+    .await?;
+    Ok(out)
+}
+
+/// Reapplies the latest undone operation.
+///
+/// Returns `None` if there is nothing to redo.
+#[tauri::command]
+pub async fn redo<'a>(
+    db: State<'a, DbConn>,
+    stack: State<'a, UndoStack>,
+    store: State<'a, FileStore>,
+) -> Result<Option<UndoOutput>, AppError> {
+    let out = Mutation::redo(db.inner(), stack.inner()).await?;
+    attachments::Mutation::collect_garbage(
+        db.inner(),
+        store.inner(),
+        &stack.attachment_hashes().await,
+    )
+    .await?;
+    Ok(out)
+}
@@ -3,20 +3,77 @@ use std::collections::HashMap;
 use anyhow::Context;
 use sea_orm::{sea_query::SimpleExpr, DbConn};
 
-use ::entity::{activities, activities::Entity as Activity, activity_tags, category_tags};
+use ::entity::{
+    activities,
+    activities::Entity as Activity,
+    activity_comments, activity_field_values, activity_relations, activity_tags, attachments,
+    categories, category_tags, checklist_items, columns, custom_field_options,
+    recurrence_occurrences, recurrences,
+    sea_orm_active_enums::{EventAction, EventEntity, SelectionMode},
+};
 use sea_orm::*;
+use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime};
 
 use crate::{
     commands::{
         activity::{
-            AddTagToActivityInput, CreateActivityInput, RemoveTagFromActivityInput,
-            UpdateActivityColumnInput, UpdateActivityContentInput,
+            AddTagToActivityInput, ArchivedActivityOutput, CreateActivityInput,
+            CreateActivityOutput, DueActivityOutput, DuplicateActivityInput,
+            RemoveTagFromActivityInput, UnarchiveActivityInput, UpdateActivityColumnInput,
+            UpdateActivityContentInput, WipLimitBreachOutput,
         },
         fetch::{ActivityOutput, ColumnActivityOutput},
     },
+    database::{category, checklist, comments, custom_fields, history, relations, tags},
     errors::AppError,
+    utils::timestamp,
 };
 
+/// Suffix appended to the name of a duplicated activity unless another one is given.
+const DUPLICATE_NAME_SUFFIX: &str = " (copy)";
+
+/// State of a deleted activity together with the rows deleted along with it.
+///
+/// Stored in the history as the state before the deletion, so that the deletion can be reverted,
+/// and in the undo stack as the state after the creation once the creation is reverted, so that it can be reapplied.
+/// Tag links are not included, since their removal is recorded as separate events.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivitySnapshot {
+    #[serde(flatten)]
+    pub activity: activities::Model,
+    #[serde(default)]
+    pub checklist: Vec<checklist_items::Model>,
+    #[serde(default)]
+    pub comments: Vec<activity_comments::Model>,
+    #[serde(default)]
+    pub relations: Vec<activity_relations::Model>,
+    #[serde(default)]
+    pub field_values: Vec<activity_field_values::Model>,
+    #[serde(default)]
+    pub attachments: Vec<attachments::Model>,
+    #[serde(default)]
+    pub recurrences: Vec<recurrences::Model>,
+    #[serde(default)]
+    pub occurrences: Vec<recurrence_occurrences::Model>,
+}
+
+impl From<activities::Model> for ActivitySnapshot {
+    fn from(activity: activities::Model) -> Self {
+        Self {
+            activity,
+            checklist: Vec::new(),
+            comments: Vec::new(),
+            relations: Vec::new(),
+            field_values: Vec::new(),
+            attachments: Vec::new(),
+            recurrences: Vec::new(),
+            occurrences: Vec::new(),
+        }
+    }
+}
+
 pub struct Query;
 
 impl Query {
@@ -24,7 +81,7 @@ impl Query {
     ///
     /// Returns `Ok(None)` if the activity is not found.
     pub async fn find_activity_by_id(
-        db: &DbConn,
+        db: &impl ConnectionTrait,
         id: i32,
     ) -> Result<Option<activities::Model>, DbErr> {
         let tasks = Activity::find_by_id(id).one(db).await?;
@@ -33,13 +90,34 @@ impl Query {
 
     /// Fetches all activities from all columns that have column ids (all activities excluding those from the stash).
     ///
+    /// Activities of columns in the trash and tags in the trash are skipped.
+    /// If `board_id` is `Some`, only the activities of that board are fetched.
+    ///
     /// Returns all found activities associated with their ids and with the id of the column that contains it.
     pub async fn all_column_activities(
         db: &DbConn,
+        board_id: Option<i32>,
     ) -> Result<HashMap<i32, ColumnActivityOutput>, DbErr> {
+        let trashed_tags = tags::Query::trashed_tag_ids(db).await?;
+        let checklists = checklist::Query::progress_by_activity(db).await?;
+        let relations = relations::Query::relations_by_activity(db).await?;
+        let comment_counts = comments::Query::comment_count_by_activity(db).await?;
+        let field_values = custom_fields::Query::values_by_activity(db).await?;
         let res = Activity::find()
             .find_with_related(category_tags::Entity)
             .filter(Condition::any().add(activities::Column::ColumnId.is_not_null()))
+            .filter(
+                activities::Column::ColumnId.not_in_subquery(
+                    columns::Entity::find()
+                        .select_only()
+                        .column(columns::Column::Id)
+                        .filter(columns::Column::DeletedAt.is_not_null())
+                        .into_query(),
+                ),
+            )
+            .apply_if(board_id, |query, board_id| {
+                query.filter(activities::Column::BoardId.eq(board_id))
+            })
             .order_by_asc(activities::Column::Ordinal)
             .all(db)
             .await?;
@@ -53,8 +131,21 @@ impl Query {
                         name: activity.name,
                         body: activity.body,
                         ordinal: activity.ordinal,
-                        tags: tags.into_iter().map(|tag| tag.id).collect(),
+                        tags: tags
+                            .into_iter()
+                            .map(|tag| tag.id)
+                            .filter(|id| !trashed_tags.contains(id))
+                            .collect(),
                         column_id: activity.column_id.unwrap(),
+                        start_date: activity.start_date,
+                        due_date: activity.due_date,
+                        created_at: activity.created_at,
+                        updated_at: activity.updated_at,
+                        column_entered_at: activity.column_entered_at,
+                        checklist: checklists.get(&activity.id).copied().unwrap_or_default(),
+                        relations: relations.get(&activity.id).cloned().unwrap_or_default(),
+                        comment_count: comment_counts.get(&activity.id).copied().unwrap_or(0),
+                        field_values: field_values.get(&activity.id).cloned().unwrap_or_default(),
                     },
                 );
                 acc
@@ -64,11 +155,26 @@ impl Query {
 
     /// Fetches all activities outside the columns that have their ids (from the stash).
     ///
+    /// Archived activities are not part of the stash.
+    /// If `board_id` is `Some`, only the stash of that board is fetched.
+    ///
     /// Returns all stash activities associated with their ids.
-    pub async fn all_other_activities(db: &DbConn) -> Result<HashMap<i32, ActivityOutput>, DbErr> {
+    pub async fn all_other_activities(
+        db: &DbConn,
+        board_id: Option<i32>,
+    ) -> Result<HashMap<i32, ActivityOutput>, DbErr> {
+        let trashed_tags = tags::Query::trashed_tag_ids(db).await?;
+        let checklists = checklist::Query::progress_by_activity(db).await?;
+        let relations = relations::Query::relations_by_activity(db).await?;
+        let comment_counts = comments::Query::comment_count_by_activity(db).await?;
+        let field_values = custom_fields::Query::values_by_activity(db).await?;
         let res = Activity::find()
             .find_with_related(category_tags::Entity)
             .filter(Condition::any().add(activities::Column::ColumnId.is_null()))
+            .filter(activities::Column::ArchivedAt.is_null())
+            .apply_if(board_id, |query, board_id| {
+                query.filter(activities::Column::BoardId.eq(board_id))
+            })
             .order_by_asc(activities::Column::Ordinal)
             .all(db)
             .await?;
@@ -82,7 +188,21 @@ impl Query {
                         name: activity.name,
                         body: activity.body,
                         ordinal: activity.ordinal,
-                        tags: tags.into_iter().map(|tag| tag.id).collect(),
+                        tags: tags
+                            .into_iter()
+                            .map(|tag| tag.id)
+                            .filter(|id| !trashed_tags.contains(id))
+                            .collect(),
+                        board_id: activity.board_id,
+                        start_date: activity.start_date,
+                        due_date: activity.due_date,
+                        created_at: activity.created_at,
+                        updated_at: activity.updated_at,
+                        column_entered_at: activity.column_entered_at,
+                        checklist: checklists.get(&activity.id).copied().unwrap_or_default(),
+                        relations: relations.get(&activity.id).cloned().unwrap_or_default(),
+                        comment_count: comment_counts.get(&activity.id).copied().unwrap_or(0),
+                        field_values: field_values.get(&activity.id).cloned().unwrap_or_default(),
                     },
                 );
                 acc
@@ -90,10 +210,130 @@ impl Query {
         Ok(out)
     }
 
+    /// Fetches all archived activities, most recently archived first.
+    ///
+    /// If `board_id` is `Some`, only the archived activities of that board are fetched.
+    ///
+    /// Returns all archived activities associated with their ids.
+    pub async fn all_archived_activities(
+        db: &DbConn,
+        board_id: Option<i32>,
+    ) -> Result<HashMap<i32, ArchivedActivityOutput>, DbErr> {
+        let trashed_tags = tags::Query::trashed_tag_ids(db).await?;
+        let res = Activity::find()
+            .find_with_related(category_tags::Entity)
+            .filter(activities::Column::ArchivedAt.is_not_null())
+            .apply_if(board_id, |query, board_id| {
+                query.filter(activities::Column::BoardId.eq(board_id))
+            })
+            .order_by_desc(activities::Column::ArchivedAt)
+            .all(db)
+            .await?;
+
+        let out = res
+            .into_iter()
+            .fold(HashMap::new(), |mut acc, (activity, tags)| {
+                acc.insert(
+                    activity.id,
+                    ArchivedActivityOutput {
+                        name: activity.name,
+                        body: activity.body,
+                        tags: tags
+                            .into_iter()
+                            .map(|tag| tag.id)
+                            .filter(|id| !trashed_tags.contains(id))
+                            .collect(),
+                        board_id: activity.board_id,
+                        archived_at: activity.archived_at.unwrap(),
+                    },
+                );
+                acc
+            });
+        Ok(out)
+    }
+
+    /// Fetches activities with a due date earlier than `today`, ordered by the due date.
+    ///
+    /// Archived activities and activities of columns in the trash are skipped.
+    /// If `board_id` is `Some`, only the activities of that board are fetched.
+    pub async fn overdue_activities(
+        db: &DbConn,
+        board_id: Option<i32>,
+        today: Date,
+    ) -> Result<Vec<DueActivityOutput>, DbErr> {
+        Self::due_activities(db, board_id, activities::Column::DueDate.lt(today)).await
+    }
+
+    /// Fetches activities with a due date between `first_day` and `last_day` inclusive, ordered by the due date.
+    ///
+    /// Archived activities and activities of columns in the trash are skipped.
+    /// If `board_id` is `Some`, only the activities of that board are fetched.
+    pub async fn activities_due_between(
+        db: &DbConn,
+        board_id: Option<i32>,
+        first_day: Date,
+        last_day: Date,
+    ) -> Result<Vec<DueActivityOutput>, DbErr> {
+        Self::due_activities(
+            db,
+            board_id,
+            activities::Column::DueDate.between(first_day, last_day),
+        )
+        .await
+    }
+
+    /// Helper function that fetches live activities with a due date matching `due_date_filter`.
+    async fn due_activities(
+        db: &DbConn,
+        board_id: Option<i32>,
+        due_date_filter: SimpleExpr,
+    ) -> Result<Vec<DueActivityOutput>, DbErr> {
+        let res = Activity::find()
+            .filter(activities::Column::DueDate.is_not_null())
+            .filter(due_date_filter)
+            .filter(activities::Column::ArchivedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(activities::Column::ColumnId.is_null())
+                    .add(
+                        activities::Column::ColumnId.not_in_subquery(
+                            columns::Entity::find()
+                                .select_only()
+                                .column(columns::Column::Id)
+                                .filter(columns::Column::DeletedAt.is_not_null())
+                                .into_query(),
+                        ),
+                    ),
+            )
+            .apply_if(board_id, |query, board_id| {
+                query.filter(activities::Column::BoardId.eq(board_id))
+            })
+            .order_by_asc(activities::Column::DueDate)
+            .order_by_asc(activities::Column::Id)
+            .all(db)
+            .await?;
+
+        let out = res
+            .into_iter()
+            .map(|activity| DueActivityOutput {
+                id: activity.id,
+                name: activity.name,
+                column_id: activity.column_id,
+                board_id: activity.board_id,
+                start_date: activity.start_date,
+                due_date: activity.due_date.unwrap(),
+            })
+            .collect();
+        Ok(out)
+    }
+
     /// Fetches the ordinal of the activity that has a given id.
     ///
     /// Returns a `RowNotFound` error if the activity with a given id is not found.
-    async fn get_ordinal_from_id(db: &DbConn, activity_id: i32) -> Result<i32, AppError> {
+    async fn get_ordinal_from_id(
+        db: &impl ConnectionTrait,
+        activity_id: i32,
+    ) -> Result<i32, AppError> {
         let res = activities::Entity::find_by_id(activity_id)
             .one(db)
             .await
@@ -106,7 +346,10 @@ impl Query {
     ///
     /// Returns a `RowNotFound` error if the activity with a given id is not found and
     /// `Ok(None)` if the activity is in the stash.
-    async fn get_column_id_from_activity_id(db: &DbConn, id: i32) -> Result<Option<i32>, AppError> {
+    async fn get_column_id_from_activity_id(
+        db: &impl ConnectionTrait,
+        id: i32,
+    ) -> Result<Option<i32>, AppError> {
         let res = activities::Entity::find_by_id(id)
             .one(db)
             .await
@@ -115,6 +358,52 @@ impl Query {
 
         Ok(res.column_id)
     }
+
+    /// Checks whether the activity with a given id is archived.
+    ///
+    /// Returns a `RowNotFound` error if the activity with a given id is not found.
+    async fn is_archived(db: &impl ConnectionTrait, id: i32) -> Result<bool, AppError> {
+        let res = activities::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to get archived_at")?
+            .ok_or(AppError::RowNotFound)?;
+
+        Ok(res.archived_at.is_some())
+    }
+
+    /// Fetches the id of the board that owns the activity with a given id.
+    ///
+    /// Returns a `RowNotFound` error if the activity with a given id is not found.
+    async fn get_board_id_from_activity_id(
+        db: &impl ConnectionTrait,
+        id: i32,
+    ) -> Result<i32, AppError> {
+        let res = activities::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to get board_id")?
+            .ok_or(AppError::RowNotFound)?;
+
+        Ok(res.board_id)
+    }
+
+    /// Fetches the id of the board that owns the column with a given id.
+    ///
+    /// Returns a `RowNotFound` error if the column with a given id is not found or is in the trash.
+    async fn get_board_id_from_column_id(
+        db: &impl ConnectionTrait,
+        column_id: i32,
+    ) -> Result<i32, AppError> {
+        let res = columns::Entity::find_by_id(column_id)
+            .filter(columns::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to get board_id of the column")?
+            .ok_or(AppError::RowNotFound)?;
+
+        Ok(res.board_id)
+    }
 }
 
 pub struct Mutation;
@@ -122,25 +411,163 @@ pub struct Mutation;
 impl Mutation {
     /// Creates an activity, and returns that activity with its newly created id.
     ///
+    /// The activity belongs to the same board as the column it is created in.
+    ///
+    /// Returns `Err(InvalidDateRange)` if the start date is later than the due date,
+    /// `Err(MissingRequiredTags)` if the column requires triage and the board has required categories,
+    /// and `Err(WipLimitExceeded)` if the column already holds as many activities as its limit allows,
+    /// unless `data.allow_over_limit` is set; the exceeded limit is then reported in the output.
     /// This also updates ordinals of the activities in a given column to maintain valid ordering.
     pub async fn create_activity(
         db: &DbConn,
         data: CreateActivityInput,
-    ) -> Result<activities::Model, AppError> {
+    ) -> Result<CreateActivityOutput, AppError> {
+        check_date_range(data.start_date, data.due_date)?;
+        let board_id = Query::get_board_id_from_column_id(db, data.column_id).await?;
+        let now = timestamp::now();
         let activity: activities::ActiveModel = activities::ActiveModel {
             name: Set(data.name),
             body: Set(data.body),
+            start_date: Set(data.start_date),
+            due_date: Set(data.due_date),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            column_entered_at: Set(Some(now)),
             ordinal: Set(0),
             column_id: Set(Some(data.column_id)),
+            board_id: Set(board_id),
             ..Default::default()
         };
 
         let tr = db.begin().await.context("failed to begin transaction")?;
-        Self::right_shift_ordinals(db, 0, Some(data.column_id)).await?;
+        let wip_limit_breach =
+            Self::check_wip_limit(&tr, Some(data.column_id), data.allow_over_limit).await?;
+        Self::right_shift_ordinals(&tr, 0, Some(data.column_id), board_id).await?;
         let res = activity
             .insert(&tr)
             .await
             .context("failed to insert activity")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            res.id,
+            EventAction::Create,
+            None,
+            Some(&res),
+        )
+        .await?;
+        Self::check_triage_complete(&tr, res.id, Some(data.column_id)).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(CreateActivityOutput {
+            activity: res,
+            wip_limit_breach,
+        })
+    }
+
+    /// Creates a copy of the activity with id given in `data` and returns it with its newly created id.
+    ///
+    /// The copy gets the name of the original followed by the suffix from `data`, and the body, dates,
+    /// tags and checklist of the original. In the column of the original, it is put directly after the original;
+    /// in any other column, or if the original is archived, it is put at the top.
+    ///
+    /// Returns `Err(RowNotFound)` if the activity or the target column does not exist,
+    /// `Err(MissingRequiredTags)` if the target column requires triage and the copy misses a tag of a required category,
+    /// and `Err(WipLimitExceeded)` if the target column already holds as many activities as its limit allows.
+    pub async fn duplicate_activity(
+        db: &DbConn,
+        data: DuplicateActivityInput,
+    ) -> Result<activities::Model, AppError> {
+        let original = Query::find_activity_by_id(db, data.id)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let column_id = data.column_id.or(original.column_id);
+        let board_id = match column_id {
+            Some(column_id) => Query::get_board_id_from_column_id(db, column_id).await?,
+            None => original.board_id,
+        };
+        let ordinal = if column_id == original.column_id && original.archived_at.is_none() {
+            original.ordinal + 1
+        } else {
+            0
+        };
+        let links = activity_tags::Entity::find()
+            .filter(activity_tags::Column::ActivityId.eq(original.id))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+        let checklist = checklist_items::Entity::find()
+            .filter(checklist_items::Column::ActivityId.eq(original.id))
+            .all(db)
+            .await
+            .context("failed to select checklist items")?;
+
+        let now = timestamp::now();
+        let suffix = data.name_suffix.as_deref().unwrap_or(DUPLICATE_NAME_SUFFIX);
+        let activity = activities::ActiveModel {
+            name: Set(format!("{}{suffix}", original.name)),
+            body: Set(original.body),
+            start_date: Set(original.start_date),
+            due_date: Set(original.due_date),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            column_entered_at: Set(Some(now)),
+            ordinal: Set(ordinal),
+            column_id: Set(column_id),
+            board_id: Set(board_id),
+            ..Default::default()
+        };
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::check_wip_limit(&tr, column_id, false).await?;
+        Self::right_shift_ordinals(&tr, ordinal, column_id, board_id).await?;
+        let res = activity
+            .insert(&tr)
+            .await
+            .context("failed to insert activity")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            res.id,
+            EventAction::Create,
+            None,
+            Some(&res),
+        )
+        .await?;
+        for link in links {
+            let model = activity_tags::ActiveModel {
+                activity_id: Set(res.id),
+                category_tag_id: Set(link.category_tag_id),
+            };
+            let link = model
+                .insert(&tr)
+                .await
+                .context("failed to insert activity_tag")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                res.id,
+                EventAction::AddTag,
+                None,
+                Some(&link),
+            )
+            .await?;
+        }
+        for item in checklist {
+            let model = checklist_items::ActiveModel {
+                activity_id: Set(res.id),
+                text: Set(item.text),
+                done: Set(item.done),
+                ordinal: Set(item.ordinal),
+                ..Default::default()
+            };
+            model
+                .insert(&tr)
+                .await
+                .context("failed to insert checklist item")?;
+        }
+        Self::check_triage_complete(&tr, res.id, column_id).await?;
         tr.commit().await.context("failed to commit transaction")?;
 
         Ok(res)
@@ -149,73 +576,537 @@ impl Mutation {
     /// Deletes an activity, given its id.
     ///
     /// This also updates ordinals of the activities in a given column to maintain valid ordering.
-    pub async fn delete_activity_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
-        let deleted_ord = Query::get_ordinal_from_id(db, id).await?;
-        let column_id = Query::get_column_id_from_activity_id(db, id).await?;
+    /// Archived activities do not occupy any position, so deleting them shifts no ordinals.
+    /// Removal of its tag links is recorded in the history before the deletion itself,
+    /// and the deletion keeps everything deleted along with the activity, see `ActivitySnapshot`.
+    pub async fn delete_activity_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let activity = Query::find_activity_by_id(db, id)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let snapshot = Self::snapshot(db, activity).await?;
+        let activity = &snapshot.activity;
+        let links = activity_tags::Entity::find()
+            .filter(activity_tags::Column::ActivityId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
         let tr = db.begin().await.context("failed to begin transaction")?;
+        for link in links.iter() {
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                id,
+                EventAction::RemoveTag,
+                Some(link),
+                None,
+            )
+            .await?;
+        }
         let _ = Activity::delete_by_id(id)
             .exec(&tr)
             .await
             .context("failed to delete activity")?;
-        Self::left_shift_ordinals(&tr, deleted_ord, column_id).await?;
+        if activity.archived_at.is_none() {
+            Self::left_shift_ordinals(&tr, activity.ordinal, activity.column_id, activity.board_id)
+                .await?;
+        }
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            id,
+            EventAction::Delete,
+            Some(&snapshot),
+            None,
+        )
+        .await?;
         tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
-    /// Updates activity content (like name or body).
+    /// Inserts a previously deleted activity with all its fields, including its id,
+    /// together with the rows deleted along with it.
     ///
-    /// Returns `Err(RowNotFound)` if no activity with id given in `data` is found.
-    pub async fn update_activity_content_by_id(
+    /// Ordinals of the activities in its column are shifted to make room for it, unless it is archived.
+    /// Relations to activities, values of custom fields and recurrence rules for columns that no longer exist are skipped.
+    /// Used to revert the deletion of an activity; its tag links have to be restored separately.
+    pub async fn reinsert_activity(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        snapshot: ActivitySnapshot,
+    ) -> Result<(), AppError> {
+        let ActivitySnapshot {
+            activity,
+            checklist,
+            comments,
+            relations,
+            field_values,
+            attachments,
+            recurrences,
+            occurrences,
+        } = snapshot;
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        if activity.archived_at.is_none() {
+            Self::right_shift_ordinals(
+                &tr,
+                activity.ordinal,
+                activity.column_id,
+                activity.board_id,
+            )
+            .await?;
+        }
+        let res = activity
+            .into_active_model()
+            .reset_all()
+            .insert(&tr)
+            .await
+            .context("failed to insert activity")?;
+        for item in checklist {
+            item.into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert checklist item")?;
+        }
+        for comment in comments {
+            comment
+                .into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert comment")?;
+        }
+        for attachment in attachments {
+            attachment
+                .into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert attachment")?;
+        }
+        for relation in relations {
+            let other_id = if relation.activity_id == res.id {
+                relation.related_activity_id
+            } else {
+                relation.activity_id
+            };
+            if Query::find_activity_by_id(&tr, other_id)
+                .await
+                .context("failed to select related activity")?
+                .is_none()
+            {
+                continue;
+            }
+            relation
+                .into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert relation")?;
+        }
+        for value in field_values {
+            let field = ::entity::custom_fields::Entity::find_by_id(value.field_id)
+                .one(&tr)
+                .await
+                .context("failed to select custom field")?;
+            let option = match value.option_id {
+                Some(option_id) => custom_field_options::Entity::find_by_id(option_id)
+                    .one(&tr)
+                    .await
+                    .context("failed to select custom field option")?
+                    .map(|_| ()),
+                None => Some(()),
+            };
+            if field.is_none() || option.is_none() {
+                continue;
+            }
+            value
+                .into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert custom field value")?;
+        }
+        let mut restored_recurrences = Vec::new();
+        for recurrence in recurrences {
+            let column = columns::Entity::find_by_id(recurrence.column_id)
+                .one(&tr)
+                .await
+                .context("failed to select column")?;
+            if column.is_none() {
+                continue;
+            }
+            restored_recurrences.push(recurrence.id);
+            recurrence
+                .into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert recurrence")?;
+        }
+        for mut occurrence in occurrences {
+            if !restored_recurrences.contains(&occurrence.recurrence_id) {
+                continue;
+            }
+            if let Some(activity_id) = occurrence.activity_id {
+                occurrence.activity_id = Query::find_activity_by_id(&tr, activity_id)
+                    .await
+                    .context("failed to select generated activity")?
+                    .map(|activity| activity.id);
+            }
+            occurrence
+                .into_active_model()
+                .reset_all()
+                .insert(&tr)
+                .await
+                .context("failed to insert occurrence")?;
+        }
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            res.id,
+            EventAction::Restore,
+            None,
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Helper function that collects the rows that are deleted together with the activity.
+    pub(crate) async fn snapshot(
+        db: &impl ConnectionTrait,
+        activity: activities::Model,
+    ) -> Result<ActivitySnapshot, AppError> {
+        let id = activity.id;
+        let checklist = checklist_items::Entity::find()
+            .filter(checklist_items::Column::ActivityId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select checklist items")?;
+        let comments = activity_comments::Entity::find()
+            .filter(activity_comments::Column::ActivityId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select comments")?;
+        let relations = activity_relations::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(activity_relations::Column::ActivityId.eq(id))
+                    .add(activity_relations::Column::RelatedActivityId.eq(id)),
+            )
+            .all(db)
+            .await
+            .context("failed to select relations")?;
+        let field_values = activity_field_values::Entity::find()
+            .filter(activity_field_values::Column::ActivityId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select custom field values")?;
+        let attachments = attachments::Entity::find()
+            .filter(attachments::Column::ActivityId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select attachments")?;
+        let recurrences = recurrences::Entity::find()
+            .filter(recurrences::Column::TemplateId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select recurrences")?;
+        let occurrences = recurrence_occurrences::Entity::find()
+            .filter(
+                recurrence_occurrences::Column::RecurrenceId
+                    .is_in(recurrences.iter().map(|recurrence| recurrence.id)),
+            )
+            .all(db)
+            .await
+            .context("failed to select occurrences")?;
+
+        Ok(ActivitySnapshot {
+            activity,
+            checklist,
+            comments,
+            relations,
+            field_values,
+            attachments,
+            recurrences,
+            occurrences,
+        })
+    }
+
+    /// Archives an activity, given its id.
+    ///
+    /// The activity is removed from its column or the stash, and the ordinals of the activities after it are shifted
+    /// to close the gap. Its content and tags are kept.
+    ///
+    /// Returns `Err(ActivityArchived)` if the activity is already archived.
+    pub async fn archive_activity_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let activity = Query::find_activity_by_id(db, id)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        if activity.archived_at.is_some() {
+            return Err(AppError::ActivityArchived);
+        }
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::left_shift_ordinals(&tr, activity.ordinal, activity.column_id, activity.board_id)
+            .await?;
+
+        let mut record = activity.clone().into_active_model();
+        record.set(activities::Column::ColumnId, None::<i32>.into());
+        record.set(activities::Column::Ordinal, 0.into());
+        record.set(
+            activities::Column::ArchivedAt,
+            Some(timestamp::now()).into(),
+        );
+        let res = Activity::update(record)
+            .exec(&tr)
+            .await
+            .context("failed to update record")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            id,
+            EventAction::Archive,
+            Some(&activity),
+            Some(&res),
+        )
+        .await?;
+
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Restores an archived activity to the position given in `data`.
+    ///
+    /// Restoring to a column of another board moves the activity to that board.
+    /// Restoring to the stash (`column_id` equal to `None`) keeps it on its current board.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no archived activity with the given id
+    /// and `Err(ActivityBlocked)` if the target column is marked as done and the activity is blocked by unfinished activities.
+    /// Returns `Err(WipLimitExceeded)` if the target column already holds as many activities as its limit allows
+    /// and `Err(MissingRequiredTags)` if the target column requires triage and the activity misses a tag of a required category.
+    pub async fn unarchive_activity_by_id(
         db: &DbConn,
+        data: UnarchiveActivityInput,
+    ) -> Result<(), AppError> {
+        Query::find_activity_by_id(db, data.id)
+            .await
+            .context("failed to select activity")?
+            .filter(|activity| activity.archived_at.is_some())
+            .ok_or(AppError::RowNotFound)?;
+        if let Some(column_id) = data.column_id {
+            Query::get_board_id_from_column_id(db, column_id).await?;
+        }
+        Self::check_not_blocked(db, data.id, data.column_id).await?;
+        Self::check_triage_complete(db, data.id, data.column_id).await?;
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::check_wip_limit(&tr, data.column_id, false).await?;
+        Self::unarchive_activity_unchecked(&tr, data).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Restores an archived activity like `unarchive_activity_by_id`, but without checking whether the target column accepts it.
+    ///
+    /// Used to revert archiving, which brings the activity back to a position it already had.
+    pub(crate) async fn unarchive_activity_unchecked(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        data: UnarchiveActivityInput,
+    ) -> Result<(), AppError> {
+        let activity = Query::find_activity_by_id(db, data.id)
+            .await
+            .context("failed to select activity")?
+            .filter(|activity| activity.archived_at.is_some())
+            .ok_or(AppError::RowNotFound)?;
+        let board_id = match data.column_id {
+            Some(column_id) => Query::get_board_id_from_column_id(db, column_id).await?,
+            None => activity.board_id,
+        };
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, data.new_ord, data.column_id, board_id).await?;
+
+        let mut record = activity.clone().into_active_model();
+        record.set(activities::Column::ColumnId, data.column_id.into());
+        record.set(activities::Column::Ordinal, data.new_ord.into());
+        record.set(activities::Column::BoardId, board_id.into());
+        record.set(
+            activities::Column::ArchivedAt,
+            None::<OffsetDateTime>.into(),
+        );
+        record.set(
+            activities::Column::ColumnEnteredAt,
+            Some(timestamp::now()).into(),
+        );
+        let res = Activity::update(record)
+            .exec(&tr)
+            .await
+            .context("failed to update record")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            data.id,
+            EventAction::Unarchive,
+            Some(&activity),
+            Some(&res),
+        )
+        .await?;
+
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Updates activity content (like name, body or dates).
+    ///
+    /// Returns `Err(RowNotFound)` if no activity with id given in `data` is found
+    /// and `Err(InvalidDateRange)` if the start date is later than the due date.
+    pub async fn update_activity_content_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
         data: UpdateActivityContentInput,
     ) -> Result<(), AppError> {
-        let mut record = Activity::find_by_id(data.id)
+        check_date_range(data.start_date, data.due_date)?;
+        let before = Activity::find_by_id(data.id)
             .one(db)
             .await
             .context("failed to find the model with id")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        let mut record = before.clone().into_active_model();
 
         record.set(activities::Column::Name, data.name.into());
         record.set(activities::Column::Body, data.body.into());
+        record.set(activities::Column::StartDate, data.start_date.into());
+        record.set(activities::Column::DueDate, data.due_date.into());
+        record.set(activities::Column::UpdatedAt, Some(timestamp::now()).into());
 
-        Activity::update(record)
-            .exec(db)
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = Activity::update(record)
+            .exec(&tr)
             .await
             .context("failed to update record")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
     /// Updates the position (ordinal) of an activity based on its id.
     ///
     /// This changes the ordinal of a given activity and changes ordinals of activities occuring later to match the new position of the activity.
+    ///
+    /// Moving the activity to a column of another board moves the activity to that board.
+    /// Moving it to the stash (`column_id` equal to `None`) keeps it on its current board.
+    /// The time the activity entered its column is only reset when it moves to another column.
+    ///
+    /// Returns `Err(ActivityArchived)` if the activity is archived; use `unarchive_activity_by_id` instead.
+    /// Returns `Err(ActivityBlocked)` if the activity is moved into a column marked as done while it is blocked by unfinished activities
+    /// and `Err(WipLimitExceeded)` if it is moved into a column that already holds as many activities as its limit allows,
+    /// unless `data.allow_over_limit` is set; the exceeded limit is then returned.
+    /// Returns `Err(MissingRequiredTags)` if it is moved into a column requiring triage while it misses a tag of a required category.
     pub async fn update_activity_column_by_id(
         db: &DbConn,
         data: UpdateActivityColumnInput,
-    ) -> Result<(), AppError> {
+    ) -> Result<Option<WipLimitBreachOutput>, AppError> {
+        if Query::is_archived(db, data.id).await? {
+            return Err(AppError::ActivityArchived);
+        }
         let old_column_id = Query::get_column_id_from_activity_id(db, data.id).await?;
-        let old_ord = Query::get_ordinal_from_id(db, data.id).await?;
+        if let Some(column_id) = data.column_id {
+            Query::get_board_id_from_column_id(db, column_id).await?;
+        }
+        if data.column_id != old_column_id {
+            Self::check_not_blocked(db, data.id, data.column_id).await?;
+            Self::check_triage_complete(db, data.id, data.column_id).await?;
+        }
 
         let tr = db.begin().await.context("failed to begin transaction")?;
-        Self::left_shift_ordinals(&tr, old_ord, old_column_id).await?;
-        Self::right_shift_ordinals(&tr, data.new_ord, data.column_id).await?;
+        let wip_limit_breach = if data.column_id != old_column_id {
+            Self::check_wip_limit(&tr, data.column_id, data.allow_over_limit).await?
+        } else {
+            None
+        };
+        Self::move_activity_unchecked(&tr, data.id, data.column_id, data.new_ord).await?;
+        tr.commit().await.context("failed to commit transaction")?;
 
-        let mut record = Activity::find_by_id(data.id)
+        Ok(wip_limit_breach)
+    }
+
+    /// Moves an activity like `update_activity_column_by_id`, but without checking whether the target column accepts it.
+    ///
+    /// Used to revert moves, which bring the activity back to a position it already had.
+    pub(crate) async fn move_activity_unchecked(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+        column_id: Option<i32>,
+        new_ord: i32,
+    ) -> Result<(), AppError> {
+        if Query::is_archived(db, id).await? {
+            return Err(AppError::ActivityArchived);
+        }
+        let old_column_id = Query::get_column_id_from_activity_id(db, id).await?;
+        let old_ord = Query::get_ordinal_from_id(db, id).await?;
+        let old_board_id = Query::get_board_id_from_activity_id(db, id).await?;
+        let new_board_id = match column_id {
+            Some(column_id) => Query::get_board_id_from_column_id(db, column_id).await?,
+            None => old_board_id,
+        };
+
+        let before = Activity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to find the model with id")?
+            .ok_or(AppError::RowNotFound)?;
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::left_shift_ordinals(&tr, old_ord, old_column_id, old_board_id).await?;
+        Self::right_shift_ordinals(&tr, new_ord, column_id, new_board_id).await?;
+
+        let mut record = Activity::find_by_id(id)
             .one(&tr)
             .await
             .context("failed to find the model with id")?
             .ok_or(AppError::RowNotFound)?
             .into_active_model();
-        record.set(activities::Column::Ordinal, data.new_ord.into());
-        record.set(activities::Column::ColumnId, data.column_id.into());
+        record.set(activities::Column::Ordinal, new_ord.into());
+        record.set(activities::Column::ColumnId, column_id.into());
+        record.set(activities::Column::BoardId, new_board_id.into());
+        let now = timestamp::now();
+        record.set(activities::Column::UpdatedAt, Some(now).into());
+        if column_id != old_column_id {
+            record.set(activities::Column::ColumnEnteredAt, Some(now).into());
+        }
 
-        Activity::update(record)
+        let res = Activity::update(record)
             .exec(&tr)
             .await
             .context("failed to update record")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            id,
+            EventAction::Move,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
 
         tr.commit().await.context("failed to commit transaction")?;
-
         Ok(())
     }
 
@@ -234,22 +1125,14 @@ impl Mutation {
                     ),
             )
             .filter(category_tags::Column::TagName.eq(data.tag_name))
+            .filter(category_tags::Column::DeletedAt.is_null())
             .one(db)
             .await
             .context("failed to select category tag id")?
             .ok_or(AppError::RowNotFound)?
             .id;
 
-        let model = activity_tags::ActiveModel {
-            activity_id: Set(data.id),
-            category_tag_id: Set(category_tag_id),
-        };
-
-        model
-            .insert(db)
-            .await
-            .context("failed to insert activity_tag")?;
-        Ok(())
+        Self::link_tag(db, data.id, category_tag_id).await
     }
 
     pub async fn remove_tag_from_activity(
@@ -267,30 +1150,237 @@ impl Mutation {
                     ),
             )
             .filter(category_tags::Column::TagName.eq(data.tag_name))
+            .filter(category_tags::Column::DeletedAt.is_null())
             .one(db)
             .await
             .context("failed to select category tag id")?
             .ok_or(AppError::RowNotFound)?
             .id;
 
-        activity_tags::Entity::delete_many()
+        Self::unlink_tag(db, data.id, category_tag_id).await
+    }
+
+    /// Links the tag with id `category_tag_id` to the activity with id `activity_id`.
+    ///
+    /// If the tag belongs to a single-select category, it replaces the other tag of that category held by the activity.
+    /// Unlike `add_tag_to_activity`, this also links tags in the trash.
+    pub async fn link_tag(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        activity_id: i32,
+        category_tag_id: i32,
+    ) -> Result<(), AppError> {
+        let model = activity_tags::ActiveModel {
+            activity_id: Set(activity_id),
+            category_tag_id: Set(category_tag_id),
+        };
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::unlink_exclusive_tags(&tr, activity_id, category_tag_id).await?;
+        let res = model
+            .insert(&tr)
+            .await
+            .context("failed to insert activity_tag")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            activity_id,
+            EventAction::AddTag,
+            None,
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Removes the link between the tag with id `category_tag_id` and the activity with id `activity_id`, if it exists.
+    pub async fn unlink_tag(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        activity_id: i32,
+        category_tag_id: i32,
+    ) -> Result<(), AppError> {
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = activity_tags::Entity::delete_many()
             .filter(activity_tags::Column::CategoryTagId.eq(category_tag_id))
-            .filter(activity_tags::Column::ActivityId.eq(data.id))
-            .exec(db)
+            .filter(activity_tags::Column::ActivityId.eq(activity_id))
+            .exec(&tr)
             .await
             .context("failed to delete activity_tag")?;
+        if res.rows_affected > 0 {
+            let before = activity_tags::Model {
+                activity_id,
+                category_tag_id,
+            };
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                activity_id,
+                EventAction::RemoveTag,
+                Some(&before),
+                None,
+            )
+            .await?;
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Helper function that makes room for the tag with id `category_tag_id` on the activity with id `activity_id`.
+    ///
+    /// If the tag belongs to a single-select category, removes the links between the activity
+    /// and the other tags of that category outside the trash. Otherwise does nothing.
+    pub(crate) async fn unlink_exclusive_tags(
+        db: &impl ConnectionTrait,
+        activity_id: i32,
+        category_tag_id: i32,
+    ) -> Result<(), AppError> {
+        let category = category_tags::Entity::find_by_id(category_tag_id)
+            .find_also_related(categories::Entity)
+            .one(db)
+            .await
+            .context("failed to select category tag")?
+            .ok_or(AppError::RowNotFound)?
+            .1;
+        let Some(category) =
+            category.filter(|category| category.selection == SelectionMode::Single)
+        else {
+            return Ok(());
+        };
+
+        let links = activity_tags::Entity::find()
+            .inner_join(category_tags::Entity)
+            .filter(activity_tags::Column::ActivityId.eq(activity_id))
+            .filter(activity_tags::Column::CategoryTagId.ne(category_tag_id))
+            .filter(category_tags::Column::CategoryId.eq(category.id))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+        for link in links {
+            activity_tags::Entity::delete_many()
+                .filter(activity_tags::Column::ActivityId.eq(activity_id))
+                .filter(activity_tags::Column::CategoryTagId.eq(link.category_tag_id))
+                .exec(db)
+                .await
+                .context("failed to delete activity_tag")?;
+            history::Mutation::record(
+                db,
+                EventEntity::Activity,
+                activity_id,
+                EventAction::RemoveTag,
+                Some(&link),
+                None,
+            )
+            .await?;
+        }
+        Ok(())
+    }
 
+    /// Helper function that refuses to put an activity into a column marked as done
+    /// while the activity is blocked by unfinished activities.
+    ///
+    /// A `None` value in `column_id` means the stash, which is never done.
+    pub(crate) async fn check_not_blocked(
+        db: &DbConn,
+        id: i32,
+        column_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let Some(column_id) = column_id else {
+            return Ok(());
+        };
+        let is_done = columns::Entity::find_by_id(column_id)
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?
+            .is_done;
+        if is_done && relations::Query::has_unfinished_blockers(db, id).await? {
+            return Err(AppError::ActivityBlocked);
+        }
         Ok(())
     }
 
+    /// Helper function that refuses to put an activity into a column requiring triage
+    /// while the activity misses a tag of a required category of the board of the column.
+    ///
+    /// A `None` value in `column_id` means the stash, which never requires triage.
+    pub(crate) async fn check_triage_complete(
+        db: &impl ConnectionTrait,
+        id: i32,
+        column_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let Some(column_id) = column_id else {
+            return Ok(());
+        };
+        let column = columns::Entity::find_by_id(column_id)
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        if !column.requires_triage {
+            return Ok(());
+        }
+        let mut missing = category::Query::missing_required_categories(db, column.board_id, &[id])
+            .await
+            .context("failed to select required categories")?;
+        match missing.remove(&id) {
+            Some(category_ids) => Err(AppError::MissingRequiredTags { category_ids }),
+            None => Ok(()),
+        }
+    }
+
+    /// Helper function that refuses to put another activity into a column that already holds
+    /// as many activities as its work-in-progress limit allows.
+    ///
+    /// If `allow_over_limit` is set, the activity is allowed in and the exceeded limit is returned instead.
+    /// Should be called with the transaction that puts the activity into the column, so that the count stays valid.
+    ///
+    /// A `None` value in `column_id` means the stash, which has no limit. Archived activities are not counted.
+    async fn check_wip_limit(
+        db: &impl ConnectionTrait,
+        column_id: Option<i32>,
+        allow_over_limit: bool,
+    ) -> Result<Option<WipLimitBreachOutput>, AppError> {
+        let Some(column_id) = column_id else {
+            return Ok(None);
+        };
+        let wip_limit = columns::Entity::find_by_id(column_id)
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?
+            .wip_limit;
+        let Some(limit) = wip_limit else {
+            return Ok(None);
+        };
+        let count = Activity::find()
+            .filter(activities::Column::ColumnId.eq(column_id))
+            .filter(activities::Column::ArchivedAt.is_null())
+            .count(db)
+            .await
+            .context("failed to determine count of activities")?;
+        if count < limit as u64 {
+            return Ok(None);
+        }
+        if !allow_over_limit {
+            return Err(AppError::WipLimitExceeded { column_id, limit });
+        }
+        Ok(Some(WipLimitBreachOutput { column_id, limit }))
+    }
+
     /// Helper function that subtracts 1 from ordinals of activities starting from the `start_ord` on a given column.
+    ///
+    /// A `None` value in `column_id` means the stash of the board with `board_id`.
     async fn left_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
         column_id: Option<i32>,
+        board_id: i32,
     ) -> Result<(), AppError> {
         activities::Entity::update_many()
             .filter(activities::Column::Ordinal.gt(start_ord))
+            .filter(activities::Column::BoardId.eq(board_id))
+            .filter(activities::Column::ArchivedAt.is_null())
             .filter(
                 Condition::any()
                     .add(activities::Column::ColumnId.eq(column_id))
@@ -312,13 +1402,18 @@ impl Mutation {
     }
 
     /// Helper function that adds 1 to ordinals of activities starting from the `start_ord` on a given column.
-    async fn right_shift_ordinals(
+    ///
+    /// A `None` value in `column_id` means the stash of the board with `board_id`.
+    pub(crate) async fn right_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
         column_id: Option<i32>,
+        board_id: i32,
     ) -> Result<(), AppError> {
         activities::Entity::update_many()
             .filter(activities::Column::Ordinal.gte(start_ord))
+            .filter(activities::Column::BoardId.eq(board_id))
+            .filter(activities::Column::ArchivedAt.is_null())
             .filter(
                 Condition::any()
                     .add(activities::Column::ColumnId.eq(column_id))
@@ -339,3 +1434,13 @@ impl Mutation {
         Ok(())
     }
 }
+
+/// Helper function that checks that the activity does not start after it is due.
+fn check_date_range(start_date: Option<Date>, due_date: Option<Date>) -> Result<(), AppError> {
+    match (start_date, due_date) {
+        (Some(start_date), Some(due_date)) if start_date > due_date => {
+            Err(AppError::InvalidDateRange)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    commands::attachments::{AddAttachmentInput, AttachmentOutput},
+    errors::AppError,
+    utils::{file_store::FileStore, timestamp},
+};
+use anyhow::Context;
+use entity::{
+    activities,
+    attachments::{self, Entity as Attachment},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QuerySelect, Set,
+};
+
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+pub struct Query;
+
+impl Query {
+    /// Fetches all attachments of the activity with `activity_id` associated with their ids.
+    ///
+    /// The path of each attachment points to its copy in `store`.
+    pub async fn all_activity_attachments(
+        db: &DbConn,
+        store: &FileStore,
+        activity_id: i32,
+    ) -> Result<HashMap<i32, AttachmentOutput>, DbErr> {
+        let res = Attachment::find()
+            .filter(attachments::Column::ActivityId.eq(activity_id))
+            .all(db)
+            .await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, attachment| {
+            acc.insert(
+                attachment.id,
+                AttachmentOutput {
+                    path: store.path(&attachment.content_hash),
+                    name: attachment.name,
+                    size: attachment.size,
+                    mime_type: attachment.mime_type,
+                    created_at: attachment.created_at,
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
+    /// Helper function that fetches the content hashes of all attachments.
+    async fn all_content_hashes(db: &DbConn) -> Result<HashSet<String>, AppError> {
+        let res: Vec<String> = Attachment::find()
+            .select_only()
+            .column(attachments::Column::ContentHash)
+            .distinct()
+            .into_tuple()
+            .all(db)
+            .await
+            .context("failed to select content hashes")?;
+        Ok(res.into_iter().collect())
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Copies the file from the path given in `data` to `store` and attaches it to the activity.
+    ///
+    /// The MIME type is guessed from the file contents. Files with the same contents share a single copy.
+    ///
+    /// Returns the created attachment, or `Err(RowNotFound)` if the activity does not exist.
+    pub async fn insert_attachment(
+        db: &DbConn,
+        store: &FileStore,
+        data: AddAttachmentInput,
+    ) -> Result<attachments::Model, AppError> {
+        activities::Entity::find_by_id(data.activity_id)
+            .one(db)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let name = data
+            .path
+            .file_name()
+            .context("attachment path has no file name")?
+            .to_string_lossy()
+            .into_owned();
+        let contents = std::fs::read(&data.path).context("failed to read attachment file")?;
+        let mime_type = infer::get(&contents)
+            .map(|kind| kind.mime_type())
+            .unwrap_or(DEFAULT_MIME_TYPE);
+        let _guard = store.lock().await;
+        let content_hash = store
+            .store(&contents)
+            .context("failed to store attachment file")?;
+
+        let model = attachments::ActiveModel {
+            activity_id: Set(data.activity_id),
+            name: Set(name),
+            size: Set(contents.len() as i64),
+            mime_type: Set(mime_type.to_owned()),
+            content_hash: Set(content_hash),
+            created_at: Set(timestamp::now()),
+            ..Default::default()
+        };
+        let model = model
+            .insert(db)
+            .await
+            .context("failed to insert attachment")?;
+        Ok(model)
+    }
+
+    /// Deletes the attachment with a given id and its file, unless another attachment has the same contents
+    /// or its content hash is in `keep`.
+    ///
+    /// Returns `Err(RowNotFound)` if the attachment does not exist.
+    pub async fn delete_attachment_by_id(
+        db: &DbConn,
+        store: &FileStore,
+        keep: &HashSet<String>,
+        id: i32,
+    ) -> Result<(), AppError> {
+        let res = Attachment::delete_by_id(id)
+            .exec(db)
+            .await
+            .context("failed to delete attachment")?;
+        if res.rows_affected == 0 {
+            return Err(AppError::RowNotFound);
+        }
+        Self::collect_garbage(db, store, keep).await
+    }
+
+    /// Removes files from `store` that no attachment refers to anymore and whose content hash is not in `keep`.
+    ///
+    /// Should be called after deleting anything that owns attachments, like activities or boards.
+    /// `keep` holds the files of deleted attachments that can still be brought back, see `UndoStack::attachment_hashes`.
+    pub async fn collect_garbage(
+        db: &DbConn,
+        store: &FileStore,
+        keep: &HashSet<String>,
+    ) -> Result<(), AppError> {
+        let _guard = store.lock().await;
+        let mut content_hashes = Query::all_content_hashes(db).await?;
+        content_hashes.extend(keep.iter().cloned());
+        store
+            .retain(&content_hashes)
+            .context("failed to remove unused attachment files")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+
+use crate::{
+    commands::{
+        boards::{RenameBoardInput, UpdateBoardOrdinalInput},
+        fetch::BoardOutput,
+    },
+    database::history,
+    errors::AppError,
+};
+use anyhow::Context;
+use entity::{
+    boards::{self, Entity as Board},
+    sea_orm_active_enums::{EventAction, EventEntity},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Helper function to fetch board ordinal based on id.
+    async fn get_ordinal_from_id(db: &impl ConnectionTrait, id: i32) -> Result<i32, AppError> {
+        let res = boards::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to get ordinal from id")?
+            .ok_or(AppError::RowNotFound)?;
+        Ok(res.ordinal)
+    }
+
+    /// Helper function to get a current number of boards.
+    async fn get_board_count(db: &impl ConnectionTrait) -> Result<i32, AppError> {
+        let res = boards::Entity::find()
+            .count(db)
+            .await
+            .context("failed to determine count of boards")?;
+        Ok(res as i32)
+    }
+
+    /// Fetches all persisted boards associated with their ids.
+    pub async fn all_boards(db: &DbConn) -> Result<HashMap<i32, BoardOutput>, DbErr> {
+        let res = Board::find()
+            .order_by_asc(boards::Column::Ordinal)
+            .all(db)
+            .await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, board| {
+            acc.insert(
+                board.id,
+                BoardOutput {
+                    name: board.name,
+                    ordinal: board.ordinal,
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
+    /// Fetches a single board associated with its id.
+    ///
+    /// Returns `Err(RowNotFound)` if the board with the given id does not exist.
+    pub async fn board_by_id(db: &DbConn, id: i32) -> Result<HashMap<i32, BoardOutput>, AppError> {
+        let board = Board::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to select board")?
+            .ok_or(AppError::RowNotFound)?;
+
+        Ok(HashMap::from([(
+            board.id,
+            BoardOutput {
+                name: board.name,
+                ordinal: board.ordinal,
+            },
+        )]))
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Appends board to the end of the board list.
+    ///
+    /// Returns the created board with appropriate id and ordinal.
+    pub async fn insert_board(db: &DbConn, name: String) -> Result<boards::Model, AppError> {
+        let board_count = Query::get_board_count(db).await?;
+        let model = boards::ActiveModel {
+            name: Set(name),
+            ordinal: Set(board_count),
+            ..Default::default()
+        };
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let model = model.insert(&tr).await.context("failed to insert board")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Board,
+            model.id,
+            EventAction::Create,
+            None,
+            Some(&model),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(model)
+    }
+
+    /// Inserts a previously deleted board with all its fields, including its id.
+    ///
+    /// The board is put back to the position it had, or to the end of the list if there are fewer boards now.
+    /// Used to reapply the creation of a board.
+    pub async fn reinsert_board(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        board: boards::Model,
+    ) -> Result<(), AppError> {
+        let board_count = Query::get_board_count(db).await?;
+        let new_ord = board.ordinal.min(board_count);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord).await?;
+        let res = boards::Model {
+            ordinal: new_ord,
+            ..board
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(&tr)
+        .await
+        .context("failed to insert board")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Board,
+            res.id,
+            EventAction::Restore,
+            None,
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Renames the board with id and new name provided in `data`.
+    ///
+    /// Returns `Err(RowNotFound)` if board with the given id does not exist.
+    pub async fn update_board_name(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        data: RenameBoardInput,
+    ) -> Result<(), AppError> {
+        let before = boards::Entity::find_by_id(data.id)
+            .one(db)
+            .await
+            .context("failed to select board")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
+
+        model.name = Set(data.new_name);
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model.update(&tr).await.context("failed to update board")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Board,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Updates the position of the given board in the board list.
+    ///
+    /// Returns `Err(RowNotFound)` if board with id given in `data` does not exist.
+    pub async fn update_board_ordinal(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        data: UpdateBoardOrdinalInput,
+    ) -> Result<(), AppError> {
+        let old_ord = Query::get_ordinal_from_id(db, data.board_id).await?;
+        let before = boards::Entity::find_by_id(data.board_id)
+            .one(db)
+            .await
+            .context("failed to select board")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::left_shift_ordinals(&tr, old_ord).await?;
+        Self::right_shift_ordinals(&tr, data.new_ord).await?;
+
+        model.ordinal = Set(data.new_ord);
+        let res = model.update(&tr).await.context("failed to update board")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Board,
+            data.board_id,
+            EventAction::Move,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// Deletes board with the id equal to `id`.
+    ///
+    /// Columns, activities (including the stash) and categories of the board are deleted with it.
+    /// Updates ordinals to maintain correct order of boards.
+    pub async fn delete_board_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let before = boards::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to select board")?
+            .ok_or(AppError::RowNotFound)?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+
+        boards::Entity::delete_by_id(id)
+            .exec(&tr)
+            .await
+            .context("failed to delete board")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Board,
+            id,
+            EventAction::Delete,
+            Some(&before),
+            None,
+        )
+        .await?;
+
+        Self::left_shift_ordinals(&tr, before.ordinal).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Helper function that decrements ordinals greater than `start_ord`.
+    async fn left_shift_ordinals(
+        db: &impl ConnectionTrait,
+        start_ord: i32,
+    ) -> Result<(), AppError> {
+        boards::Entity::update_many()
+            .filter(boards::Column::Ordinal.gt(start_ord))
+            .col_expr(
+                boards::Column::Ordinal,
+                boards::Column::Ordinal.into_expr().sub(1),
+            )
+            .exec(db)
+            .await
+            .context("failed to left shift ordinals")?;
+        Ok(())
+    }
+
+    /// Helper function that increments ordinals equal to at least `start_ord`.
+    async fn right_shift_ordinals(
+        db: &impl ConnectionTrait,
+        start_ord: i32,
+    ) -> Result<(), AppError> {
+        boards::Entity::update_many()
+            .filter(boards::Column::Ordinal.gte(start_ord))
+            .col_expr(
+                boards::Column::Ordinal,
+                boards::Column::Ordinal.into_expr().add(1),
+            )
+            .exec(db)
+            .await
+            .context("failed to right shift ordinals")?;
+        Ok(())
+    }
+}
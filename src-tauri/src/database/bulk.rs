@@ -0,0 +1,462 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    commands::bulk::{BulkItemOutput, BulkMoveActivitiesInput, BulkOutput, BulkTagInput},
+    database::{activity, history},
+    errors::AppError,
+    utils::timestamp,
+};
+use anyhow::Context;
+use entity::{
+    activities::{self, Entity as Activity},
+    activity_tags, category_tags, columns,
+    sea_orm_active_enums::{EventAction, EventEntity},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Helper function that fetches the activities with given ids, ignoring repeated ids.
+    ///
+    /// Returns the activities in the order of their ids, along with the output
+    /// in which the ids of missing activities are already marked as skipped.
+    async fn find_activities(
+        db: &DbConn,
+        ids: &[i32],
+    ) -> Result<(BulkOutput, Vec<activities::Model>), AppError> {
+        let found = Activity::find()
+            .filter(activities::Column::Id.is_in(ids.to_vec()))
+            .order_by_asc(activities::Column::Id)
+            .all(db)
+            .await
+            .context("failed to select activities")?;
+        let found_ids: HashSet<i32> = found.iter().map(|activity| activity.id).collect();
+        let out = ids
+            .iter()
+            .filter(|id| !found_ids.contains(id))
+            .map(|id| (*id, BulkItemOutput::Skipped(AppError::RowNotFound)))
+            .collect();
+        Ok((out, found))
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Moves the activities with ids given in `data` to a column as a single block starting at `new_ord`.
+    ///
+    /// The moved activities keep the order they had on the board: by the position of their column,
+    /// then by their position in it, with the stash after all columns.
+    /// Moving them to a column of another board moves them to that board.
+    ///
+    /// An activity is skipped if it does not exist, is archived, is blocked by unfinished activities
+    /// while the column is marked as done, misses a tag of a required category while the column requires triage,
+    /// or does not fit into the work-in-progress limit of the column.
+    /// Ordinals of every affected column are compacted once all activities are moved.
+    ///
+    /// Returns `Err(RowNotFound)` if the column does not exist or is in the trash.
+    pub async fn move_activities(
+        db: &DbConn,
+        data: BulkMoveActivitiesInput,
+    ) -> Result<BulkOutput, AppError> {
+        let column = columns::Entity::find_by_id(data.column_id)
+            .filter(columns::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        let (mut out, found) = Query::find_activities(db, &data.ids).await?;
+
+        let mut moved = Vec::new();
+        for activity in found {
+            if activity.archived_at.is_some() {
+                out.insert(
+                    activity.id,
+                    BulkItemOutput::Skipped(AppError::ActivityArchived),
+                );
+                continue;
+            }
+            if activity.column_id != Some(column.id) {
+                let checked = async {
+                    activity::Mutation::check_not_blocked(db, activity.id, Some(column.id)).await?;
+                    activity::Mutation::check_triage_complete(db, activity.id, Some(column.id))
+                        .await
+                };
+                if let Err(error) = checked.await {
+                    out.insert(activity.id, BulkItemOutput::Skipped(error));
+                    continue;
+                }
+            }
+            moved.push(activity);
+        }
+
+        if let Some(limit) = column.wip_limit {
+            let count = Activity::find()
+                .filter(activities::Column::ColumnId.eq(column.id))
+                .filter(activities::Column::ArchivedAt.is_null())
+                .count(db)
+                .await
+                .context("failed to determine count of activities")?;
+            let mut free = (limit.max(0) as u64).saturating_sub(count);
+            moved.retain(|activity| {
+                if activity.column_id == Some(column.id) {
+                    return true;
+                }
+                if free == 0 {
+                    out.insert(
+                        activity.id,
+                        BulkItemOutput::Skipped(AppError::WipLimitExceeded {
+                            column_id: column.id,
+                            limit,
+                        }),
+                    );
+                    return false;
+                }
+                free -= 1;
+                true
+            });
+        }
+
+        let column_ordinals: HashMap<i32, i32> = columns::Entity::find()
+            .all(db)
+            .await
+            .context("failed to select columns")?
+            .into_iter()
+            .map(|column| (column.id, column.ordinal))
+            .collect();
+        moved.sort_by_key(|activity| {
+            let column_ordinal = activity
+                .column_id
+                .and_then(|id| column_ordinals.get(&id).copied())
+                .unwrap_or(i32::MAX);
+            (column_ordinal, activity.ordinal, activity.id)
+        });
+
+        let moved_ids: HashSet<i32> = moved.iter().map(|activity| activity.id).collect();
+        let remaining: Vec<activities::Model> = Activity::find()
+            .filter(activities::Column::ColumnId.eq(column.id))
+            .filter(activities::Column::ArchivedAt.is_null())
+            .order_by_asc(activities::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select activities of column")?
+            .into_iter()
+            .filter(|activity| !moved_ids.contains(&activity.id))
+            .collect();
+        let start = (data.new_ord.max(0) as usize).min(remaining.len());
+        let sources: HashSet<(Option<i32>, i32)> = moved
+            .iter()
+            .filter(|activity| activity.column_id != Some(column.id))
+            .map(|activity| (activity.column_id, activity.board_id))
+            .collect();
+
+        let now = timestamp::now();
+        let mut removed = HashMap::new();
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let count = moved.len();
+        for (index, activity) in remaining.into_iter().enumerate() {
+            let ordinal = if index < start { index } else { index + count };
+            if activity.ordinal != ordinal as i32 {
+                let mut model = activity.into_active_model();
+                model.ordinal = Set(ordinal as i32);
+                model
+                    .update(&tr)
+                    .await
+                    .context("failed to update activity ordinal")?;
+            }
+        }
+        for (offset, before) in moved.into_iter().enumerate() {
+            let mut model = before.clone().into_active_model();
+            model.column_id = Set(Some(column.id));
+            model.ordinal = Set((start + offset) as i32);
+            model.board_id = Set(column.board_id);
+            model.updated_at = Set(Some(now));
+            if before.column_id != Some(column.id) {
+                model.column_entered_at = Set(Some(now));
+            }
+            let res = model
+                .update(&tr)
+                .await
+                .context("failed to update activity")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                res.id,
+                EventAction::Move,
+                Some(&sequential_snapshot(before, &mut removed)),
+                Some(&res),
+            )
+            .await?;
+            out.insert(res.id, BulkItemOutput::Done);
+        }
+        for (column_id, board_id) in sources {
+            compact_ordinals(&tr, column_id, board_id).await?;
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(out)
+    }
+
+    /// Links the tag given in `data` to the activities with ids given in `data`.
+    ///
+    /// Activities that already have the tag are left as they are. An activity is skipped if it does not exist.
+    /// If the tag belongs to a single-select category, it replaces the other tag of that category held by each activity.
+    ///
+    /// Returns `Err(RowNotFound)` if the tag does not exist or is in the trash.
+    pub async fn add_tag(db: &DbConn, data: BulkTagInput) -> Result<BulkOutput, AppError> {
+        category_tags::Entity::find_by_id(data.tag_id)
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select category tag")?
+            .ok_or(AppError::RowNotFound)?;
+        let (mut out, found) = Query::find_activities(db, &data.ids).await?;
+        let linked: HashSet<i32> = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.eq(data.tag_id))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?
+            .into_iter()
+            .map(|link| link.activity_id)
+            .collect();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for activity in found {
+            if !linked.contains(&activity.id) {
+                activity::Mutation::unlink_exclusive_tags(&tr, activity.id, data.tag_id).await?;
+                let model = activity_tags::ActiveModel {
+                    activity_id: Set(activity.id),
+                    category_tag_id: Set(data.tag_id),
+                };
+                let res = model
+                    .insert(&tr)
+                    .await
+                    .context("failed to insert activity_tag")?;
+                history::Mutation::record(
+                    &tr,
+                    EventEntity::Activity,
+                    activity.id,
+                    EventAction::AddTag,
+                    None,
+                    Some(&res),
+                )
+                .await?;
+            }
+            out.insert(activity.id, BulkItemOutput::Done);
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(out)
+    }
+
+    /// Removes the link between the tag given in `data` and the activities with ids given in `data`.
+    ///
+    /// Activities without the tag are left as they are. An activity is skipped if it does not exist.
+    /// Tags in the trash can still be removed, since their links are kept.
+    ///
+    /// Returns `Err(RowNotFound)` if the tag does not exist.
+    pub async fn remove_tag(db: &DbConn, data: BulkTagInput) -> Result<BulkOutput, AppError> {
+        category_tags::Entity::find_by_id(data.tag_id)
+            .one(db)
+            .await
+            .context("failed to select category tag")?
+            .ok_or(AppError::RowNotFound)?;
+        let (mut out, found) = Query::find_activities(db, &data.ids).await?;
+        let links: HashMap<i32, activity_tags::Model> = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.eq(data.tag_id))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?
+            .into_iter()
+            .map(|link| (link.activity_id, link))
+            .collect();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for activity in found {
+            if let Some(link) = links.get(&activity.id) {
+                activity_tags::Entity::delete_many()
+                    .filter(activity_tags::Column::ActivityId.eq(activity.id))
+                    .filter(activity_tags::Column::CategoryTagId.eq(data.tag_id))
+                    .exec(&tr)
+                    .await
+                    .context("failed to delete activity_tag")?;
+                history::Mutation::record(
+                    &tr,
+                    EventEntity::Activity,
+                    activity.id,
+                    EventAction::RemoveTag,
+                    Some(link),
+                    None,
+                )
+                .await?;
+            }
+            out.insert(activity.id, BulkItemOutput::Done);
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(out)
+    }
+
+    /// Archives the activities with given ids.
+    ///
+    /// An activity is skipped if it does not exist or is already archived.
+    /// Ordinals of every affected column are compacted once all activities are archived.
+    pub async fn archive_activities(db: &DbConn, ids: Vec<i32>) -> Result<BulkOutput, AppError> {
+        let (mut out, mut found) = Query::find_activities(db, &ids).await?;
+        found.sort_by_key(|activity| activity.ordinal);
+
+        let now = timestamp::now();
+        let mut sources = HashSet::new();
+        let mut removed = HashMap::new();
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for before in found {
+            if before.archived_at.is_some() {
+                out.insert(
+                    before.id,
+                    BulkItemOutput::Skipped(AppError::ActivityArchived),
+                );
+                continue;
+            }
+            sources.insert((before.column_id, before.board_id));
+            let mut model = before.clone().into_active_model();
+            model.column_id = Set(None);
+            model.ordinal = Set(0);
+            model.archived_at = Set(Some(now));
+            let res = model
+                .update(&tr)
+                .await
+                .context("failed to update activity")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                res.id,
+                EventAction::Archive,
+                Some(&sequential_snapshot(before, &mut removed)),
+                Some(&res),
+            )
+            .await?;
+            out.insert(res.id, BulkItemOutput::Done);
+        }
+        for (column_id, board_id) in sources {
+            compact_ordinals(&tr, column_id, board_id).await?;
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(out)
+    }
+
+    /// Deletes the activities with given ids.
+    ///
+    /// An activity is skipped if it does not exist.
+    /// Ordinals of every affected column are compacted once all activities are deleted.
+    /// Each deletion keeps everything deleted along with the activity, see `ActivitySnapshot`.
+    pub async fn delete_activities(db: &DbConn, ids: Vec<i32>) -> Result<BulkOutput, AppError> {
+        let (mut out, mut found) = Query::find_activities(db, &ids).await?;
+        found.sort_by_key(|activity| activity.ordinal);
+        let found_ids: Vec<i32> = found.iter().map(|activity| activity.id).collect();
+        let links = activity_tags::Entity::find()
+            .filter(activity_tags::Column::ActivityId.is_in(found_ids))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+
+        let mut sources = HashSet::new();
+        let mut removed = HashMap::new();
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for activity in found {
+            for link in links.iter().filter(|link| link.activity_id == activity.id) {
+                history::Mutation::record(
+                    &tr,
+                    EventEntity::Activity,
+                    activity.id,
+                    EventAction::RemoveTag,
+                    Some(link),
+                    None,
+                )
+                .await?;
+            }
+            let id = activity.id;
+            let mut snapshot = activity::Mutation::snapshot(&tr, activity).await?;
+            Activity::delete_by_id(id)
+                .exec(&tr)
+                .await
+                .context("failed to delete activity")?;
+            if snapshot.activity.archived_at.is_none() {
+                sources.insert((snapshot.activity.column_id, snapshot.activity.board_id));
+                snapshot.activity = sequential_snapshot(snapshot.activity, &mut removed);
+            }
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                id,
+                EventAction::Delete,
+                Some(&snapshot),
+                None,
+            )
+            .await?;
+            out.insert(id, BulkItemOutput::Done);
+        }
+        for (column_id, board_id) in sources {
+            compact_ordinals(&tr, column_id, board_id).await?;
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(out)
+    }
+}
+
+/// Helper function that returns the state of an activity as if the activities taken out of its column earlier
+/// in the same operation had been taken out one by one, each closing its gap right away.
+///
+/// `removed` counts the activities taken out of each column so far. Activities of a column must be passed
+/// in the order of their ordinals. Events recorded with such states restore the ordinals of the column exactly
+/// when they are undone in reverse order.
+fn sequential_snapshot(
+    mut activity: activities::Model,
+    removed: &mut HashMap<(Option<i32>, i32), i32>,
+) -> activities::Model {
+    let count = removed
+        .entry((activity.column_id, activity.board_id))
+        .or_insert(0);
+    activity.ordinal -= *count;
+    *count += 1;
+    activity
+}
+
+/// Helper function that renumbers activities in a given column from 0, keeping their order.
+///
+/// A `None` value in `column_id` means the stash of the board with `board_id`.
+async fn compact_ordinals(
+    db: &impl ConnectionTrait,
+    column_id: Option<i32>,
+    board_id: i32,
+) -> Result<(), AppError> {
+    let column_filter = match column_id {
+        Some(column_id) => activities::Column::ColumnId.eq(column_id),
+        None => activities::Column::ColumnId.is_null(),
+    };
+    let res = Activity::find()
+        .filter(activities::Column::BoardId.eq(board_id))
+        .filter(activities::Column::ArchivedAt.is_null())
+        .filter(column_filter)
+        .order_by_asc(activities::Column::Ordinal)
+        .all(db)
+        .await
+        .context("failed to select activities of column")?;
+    for (ordinal, activity) in res.into_iter().enumerate() {
+        let ordinal = ordinal as i32;
+        if activity.ordinal != ordinal {
+            let mut model = activity.into_active_model();
+            model.ordinal = Set(ordinal);
+            model
+                .update(db)
+                .await
+                .context("failed to update activity ordinal")?;
+        }
+    }
+    Ok(())
+}
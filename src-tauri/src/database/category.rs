@@ -1,22 +1,36 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     commands::{
-        category::UpdateCategoryNameInput,
+        category::{
+            SetCategoryOrderInput, UpdateCategoryNameInput, UpdateCategoryOrdinalInput,
+            UpdateCategoryPaletteInput, UpdateCategoryRequiredInput, UpdateCategorySelectionInput,
+        },
         fetch::{CategoryOutput, CategoryTagOutput},
+        trash::TrashedCategoryOutput,
     },
+    database::history,
     errors::AppError,
-    utils::coloring::rgb_int_to_string,
+    utils::{
+        coloring::{
+            candidate_colors, furthest_color, rgb_int_to_string, spaced_colors, text_color,
+        },
+        ordering, timestamp,
+    },
 };
 use anyhow::Context;
 use entity::{
+    activities, activity_tags,
     categories::{self, Entity as Category},
-    category_tags::Entity as CategoryTag,
+    category_tags::{self, Entity as CategoryTag},
+    sea_orm_active_enums::{EventAction, EventEntity, SelectionMode, TagPalette},
 };
 use sea_orm::{
     sea_query::SimpleExpr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr,
-    EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, Set, TransactionTrait, Value,
+    EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QueryTrait, Set,
+    TransactionTrait, Value,
 };
+use time::OffsetDateTime;
 
 pub struct Query;
 
@@ -27,9 +41,13 @@ impl Query {
     /// - the first map associates category ids with their contents
     /// - the second map associates tag ids with their contents.
     ///
+    /// Categories and tags in the trash are skipped.
+    /// If `board_id` is `Some`, only the categories of that board are fetched.
+    ///
     /// Used to fetch persisted data at application startup.
     pub async fn all_with_category_tags(
         db: &DbConn,
+        board_id: Option<i32>,
     ) -> Result<
         (
             HashMap<i32, CategoryOutput>,
@@ -39,18 +57,30 @@ impl Query {
     > {
         let res = Category::find()
             .find_with_related(CategoryTag)
+            .filter(categories::Column::DeletedAt.is_null())
+            .apply_if(board_id, |query, board_id| {
+                query.filter(categories::Column::BoardId.eq(board_id))
+            })
             .all(db)
             .await?;
 
         let mut categories = HashMap::new();
         let mut category_tags = HashMap::new();
         res.into_iter().for_each(|(category, tags)| {
+            let tags: Vec<_> = tags
+                .into_iter()
+                .filter(|tag| tag.deleted_at.is_none())
+                .collect();
             categories.insert(
                 category.id,
                 CategoryOutput {
                     name: category.name,
                     ordinal: category.ordinal,
                     tags: tags.iter().map(|tag| tag.id).collect(),
+                    board_id: category.board_id,
+                    palette: category.palette,
+                    selection: category.selection,
+                    is_required: category.is_required,
                 },
             );
             tags.into_iter().for_each(|tag| {
@@ -59,6 +89,7 @@ impl Query {
                     CategoryTagOutput {
                         name: tag.tag_name,
                         color: rgb_int_to_string(tag.color),
+                        text_color: text_color(tag.color).into(),
                         ordinal: tag.ordinal,
                         category_id: category.id,
                     },
@@ -69,9 +100,35 @@ impl Query {
         Ok((categories, category_tags))
     }
 
+    /// Fetches all categories in the trash associated with their ids.
+    pub async fn all_trashed_categories(
+        db: &DbConn,
+    ) -> Result<HashMap<i32, TrashedCategoryOutput>, DbErr> {
+        let res = Category::find()
+            .filter(categories::Column::DeletedAt.is_not_null())
+            .all(db)
+            .await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, category| {
+            acc.insert(
+                category.id,
+                TrashedCategoryOutput {
+                    name: category.name,
+                    board_id: category.board_id,
+                    deleted_at: category.deleted_at.unwrap(),
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
     /// Helper function used to fetch ordinal of a category based on id.
-    async fn get_ordinal_from_id(db: &DbConn, id: i32) -> Result<i32, AppError> {
+    ///
+    /// Returns `Err(RowNotFound)` if the category does not exist or is in the trash.
+    async fn get_ordinal_from_id(db: &impl ConnectionTrait, id: i32) -> Result<i32, AppError> {
         let res = categories::Entity::find_by_id(id)
+            .filter(categories::Column::DeletedAt.is_null())
             .one(db)
             .await
             .context("failed to get ordinal from id")?
@@ -79,9 +136,110 @@ impl Query {
         Ok(res.ordinal)
     }
 
-    /// Helper function that counts persisted categories.
-    async fn get_category_count(db: &DbConn) -> Result<i32, AppError> {
+    /// Finds the required categories of the board with a given id of which the activities with ids `activity_ids` hold no tag.
+    ///
+    /// Categories and tags in the trash are ignored.
+    ///
+    /// Returns a map from the ids of the activities missing a tag to the ids of the missing categories in the category order.
+    pub async fn missing_required_categories(
+        db: &impl ConnectionTrait,
+        board_id: i32,
+        activity_ids: &[i32],
+    ) -> Result<HashMap<i32, Vec<i32>>, DbErr> {
+        let required: Vec<i32> = Category::find()
+            .filter(categories::Column::BoardId.eq(board_id))
+            .filter(categories::Column::IsRequired.eq(true))
+            .filter(categories::Column::DeletedAt.is_null())
+            .order_by_asc(categories::Column::Ordinal)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+        if required.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let held: HashSet<(i32, Option<i32>)> = activity_tags::Entity::find()
+            .find_also_related(CategoryTag)
+            .filter(activity_tags::Column::ActivityId.is_in(activity_ids.iter().copied()))
+            .filter(category_tags::Column::CategoryId.is_in(required.iter().copied()))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|(link, tag)| Some((link.activity_id, tag?.category_id)))
+            .collect();
+
+        let out = activity_ids
+            .iter()
+            .filter_map(|activity_id| {
+                let missing: Vec<i32> = required
+                    .iter()
+                    .copied()
+                    .filter(|category_id| !held.contains(&(*activity_id, Some(*category_id))))
+                    .collect();
+                (!missing.is_empty()).then_some((*activity_id, missing))
+            })
+            .collect();
+        Ok(out)
+    }
+
+    /// Fetches the activities of the board with a given id that miss a tag of a required category of the board.
+    ///
+    /// Archived activities are skipped.
+    ///
+    /// Returns a map from the ids of the activities to the ids of the missing categories in the category order.
+    pub async fn activities_missing_required_tags(
+        db: &DbConn,
+        board_id: i32,
+    ) -> Result<HashMap<i32, Vec<i32>>, DbErr> {
+        let activity_ids: Vec<i32> = activities::Entity::find()
+            .filter(activities::Column::BoardId.eq(board_id))
+            .filter(activities::Column::ArchivedAt.is_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|activity| activity.id)
+            .collect();
+
+        Self::missing_required_categories(db, board_id, &activity_ids).await
+    }
+
+    /// Helper function that finds activities holding more than one tag of the category with a given id.
+    ///
+    /// Returns the activities in the order of their ids, each with its links to the tags of the category
+    /// in the category order. Tags in the trash are ignored.
+    pub(crate) async fn selection_conflicts(
+        db: &impl ConnectionTrait,
+        id: i32,
+    ) -> Result<Vec<(i32, Vec<activity_tags::Model>)>, AppError> {
+        let links = activity_tags::Entity::find()
+            .inner_join(CategoryTag)
+            .filter(category_tags::Column::CategoryId.eq(id))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .order_by_asc(activity_tags::Column::ActivityId)
+            .order_by_asc(category_tags::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+
+        let mut res: Vec<(i32, Vec<activity_tags::Model>)> = Vec::new();
+        for link in links {
+            match res.last_mut() {
+                Some((activity_id, links)) if *activity_id == link.activity_id => links.push(link),
+                _ => res.push((link.activity_id, vec![link])),
+            }
+        }
+        res.retain(|(_, links)| links.len() > 1);
+        Ok(res)
+    }
+
+    /// Helper function that counts persisted categories on a given board, excluding the trash.
+    async fn get_category_count(db: &impl ConnectionTrait, board_id: i32) -> Result<i32, AppError> {
         let res = categories::Entity::find()
+            .filter(categories::Column::BoardId.eq(board_id))
+            .filter(categories::Column::DeletedAt.is_null())
             .count(db)
             .await
             .context("failed to determine count of columns")?;
@@ -94,17 +252,36 @@ pub struct Mutation;
 impl Mutation {
     /// Inserts the category and returns the inserted category with id and ordinal.
     ///
-    /// The category is inserted to the end of the category list.
-    pub async fn insert_category(db: &DbConn, name: String) -> Result<categories::Model, AppError> {
-        let category_count = Query::get_category_count(db).await?;
+    /// The category is inserted to the end of the category list of the board with `board_id`.
+    pub async fn insert_category(
+        db: &DbConn,
+        board_id: i32,
+        name: String,
+    ) -> Result<categories::Model, AppError> {
+        let category_count = Query::get_category_count(db, board_id).await?;
         let data = categories::ActiveModel {
             name: Set(name),
             ordinal: Set(category_count),
+            board_id: Set(board_id),
             ..Default::default()
         };
 
         // No ordinal gets updated; the function is designed to append category to the end of the list.
-        let res = data.insert(db).await.context("failed to insert category")?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = data
+            .insert(&tr)
+            .await
+            .context("failed to insert category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            res.id,
+            EventAction::Create,
+            None,
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(res)
     }
 
@@ -115,44 +292,519 @@ impl Mutation {
         db: &DbConn,
         data: UpdateCategoryNameInput,
     ) -> Result<(), AppError> {
-        let mut category = categories::Entity::find_by_id(data.id)
+        let before = categories::Entity::find_by_id(data.id)
             .one(db)
             .await
             .context("failed to fetch category")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        let mut category = before.clone().into_active_model();
 
         category.set(categories::Column::Name, data.name.into());
 
-        categories::Entity::update(category)
-            .exec(db)
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = categories::Entity::update(category)
+            .exec(&tr)
+            .await
+            .context("failed to update category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Sets the palette used to pick colors of new tags of the category with id given in `data`.
+    ///
+    /// Colors of the existing tags are kept; `recolor_category` reassigns them.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no category with this id.
+    pub async fn update_category_palette(
+        db: &DbConn,
+        data: UpdateCategoryPaletteInput,
+    ) -> Result<(), AppError> {
+        let before = categories::Entity::find_by_id(data.category_id)
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut category = before.clone().into_active_model();
+        category.palette = Set(data.palette);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = category
+            .update(&tr)
+            .await
+            .context("failed to update category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            data.category_id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Marks the category with id given in `data` as one of which every activity should hold a tag, or unmarks it.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no category with this id.
+    pub async fn update_category_required(
+        db: &DbConn,
+        data: UpdateCategoryRequiredInput,
+    ) -> Result<(), AppError> {
+        let before = categories::Entity::find_by_id(data.category_id)
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut category = before.clone().into_active_model();
+        category.is_required = Set(data.is_required);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = category
+            .update(&tr)
+            .await
+            .context("failed to update category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            data.category_id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Sets whether activities can hold one or many tags of the category with id given in `data`.
+    ///
+    /// When switching to single-select, activities already holding more than one tag of the category conflict with the new mode.
+    /// If `data.resolve` is set, each of them keeps only the first of its tags in the category order.
+    /// Tags in the trash are ignored.
+    ///
+    /// Returns ids of the activities whose conflicting tags were resolved.
+    ///
+    /// Returns `Err(SelectionConflict)` with ids of the conflicting activities if `data.resolve` is not set,
+    /// and `Err(RowNotFound)` if there is no category with this id.
+    pub async fn update_category_selection(
+        db: &DbConn,
+        data: UpdateCategorySelectionInput,
+    ) -> Result<Vec<i32>, AppError> {
+        let before = categories::Entity::find_by_id(data.category_id)
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+
+        let conflicts = match data.selection {
+            SelectionMode::Single => Query::selection_conflicts(db, data.category_id).await?,
+            SelectionMode::Multi => Vec::new(),
+        };
+        if !conflicts.is_empty() && !data.resolve {
+            return Err(AppError::SelectionConflict {
+                activity_ids: conflicts
+                    .iter()
+                    .map(|(activity_id, _)| *activity_id)
+                    .collect(),
+            });
+        }
+
+        let mut category = before.clone().into_active_model();
+        category.selection = Set(data.selection);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for (activity_id, links) in &conflicts {
+            for link in links.iter().skip(1) {
+                activity_tags::Entity::delete_many()
+                    .filter(activity_tags::Column::ActivityId.eq(*activity_id))
+                    .filter(activity_tags::Column::CategoryTagId.eq(link.category_tag_id))
+                    .exec(&tr)
+                    .await
+                    .context("failed to delete activity_tag")?;
+                history::Mutation::record(
+                    &tr,
+                    EventEntity::Activity,
+                    *activity_id,
+                    EventAction::RemoveTag,
+                    Some(link),
+                    None,
+                )
+                .await?;
+            }
+        }
+        let res = category
+            .update(&tr)
+            .await
+            .context("failed to update category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            data.category_id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(conflicts
+            .into_iter()
+            .map(|(activity_id, _)| activity_id)
+            .collect())
+    }
+
+    /// Reassigns colors of all tags of the category with a given id using the palette of the category.
+    ///
+    /// With the `Spaced` palette, tags get evenly spaced hues in their order.
+    /// With other palettes, tags are colored in their order, each with the candidate furthest from the colors assigned before it.
+    /// Tags in the trash are skipped.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no category with this id.
+    pub async fn recolor_category(db: &DbConn, id: i32) -> Result<(), AppError> {
+        let category = categories::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+        let tags = category_tags::Entity::find()
+            .filter(category_tags::Column::CategoryId.eq(id))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .order_by_asc(category_tags::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select category tags")?;
+
+        let colors = match category.palette {
+            TagPalette::Spaced => spaced_colors(tags.len()),
+            palette => tags.iter().fold(Vec::new(), |mut taken, tag| {
+                let candidates = candidate_colors(palette, &tag.tag_name);
+                taken.extend(furthest_color(&candidates, &taken));
+                taken
+            }),
+        };
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for (before, color) in tags.into_iter().zip(colors) {
+            if before.color == color {
+                continue;
+            }
+            let mut model = before.clone().into_active_model();
+            model.color = Set(color);
+            let res = model
+                .update(&tr)
+                .await
+                .context("failed to update category_tags row")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Tag,
+                res.id,
+                EventAction::Update,
+                Some(&before),
+                Some(&res),
+            )
+            .await?;
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Updates the position of the category with id given in `data` in the category list of its board.
+    ///
+    /// This also shifts ordinals of other categories to achieve correct ordering.
+    ///
+    /// Returns `Err(RowNotFound)` if the category does not exist.
+    pub async fn update_category_ordinal(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        data: UpdateCategoryOrdinalInput,
+    ) -> Result<(), AppError> {
+        let old_ord = Query::get_ordinal_from_id(db, data.category_id).await?;
+        let before = categories::Entity::find_by_id(data.category_id)
+            .one(db)
+            .await
+            .context("failed to select category")?
+            .ok_or(AppError::RowNotFound)?;
+        let board_id = before.board_id;
+        let mut model = before.clone().into_active_model();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::left_shift_ordinals(&tr, old_ord, board_id).await?;
+        Self::right_shift_ordinals(&tr, data.new_ord, board_id).await?;
+
+        model.ordinal = Set(data.new_ord);
+        let res = model
+            .update(&tr)
             .await
             .context("failed to update category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            data.category_id,
+            EventAction::Move,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// Puts the categories of the board with id given in `data` in the order of the ids given in `data`.
+    ///
+    /// Returns `Err(InvalidOrder)` if the ids are not a permutation of the ids of the categories outside the trash.
+    pub async fn set_category_order(
+        db: &DbConn,
+        data: SetCategoryOrderInput,
+    ) -> Result<(), AppError> {
+        let res = Category::find()
+            .filter(categories::Column::BoardId.eq(data.board_id))
+            .filter(categories::Column::DeletedAt.is_null())
+            .order_by_asc(categories::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select categories")?;
+        let current: Vec<i32> = res.iter().map(|model| model.id).collect();
+        let moves = ordering::moves_between(&current, &data.ids)?;
+        let models: HashMap<i32, categories::Model> =
+            res.into_iter().map(|model| (model.id, model)).collect();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        history::Mutation::record_moves(
+            &tr,
+            EventEntity::Category,
+            &models,
+            moves,
+            |model, ordinal| categories::Model {
+                ordinal,
+                ..model.clone()
+            },
+        )
+        .await?;
+        for (ordinal, id) in data.ids.iter().enumerate() {
+            let model = &models[id];
+            if model.ordinal != ordinal as i32 {
+                let mut model = model.clone().into_active_model();
+                model.ordinal = Set(ordinal as i32);
+                model
+                    .update(&tr)
+                    .await
+                    .context("failed to update category")?;
+            }
+        }
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
-    /// Deletes category based on id.
+    /// Moves category with a given id to the trash.
     ///
-    /// Shifts ordinals to match the correct order of categories after deletion.
-    pub async fn delete_category_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+    /// Tags of the category and their links to activities are kept, so that restoring the category brings them back.
+    /// Shifts ordinals to match the correct order of the remaining categories.
+    ///
+    /// Returns `Err(RowNotFound)` if the category does not exist or is already in the trash.
+    pub async fn delete_category_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
         let deleted_ord = Query::get_ordinal_from_id(db, id).await?;
+        let before = categories::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+        let board_id = before.board_id;
+        let mut model = before.clone().into_active_model();
+        model.deleted_at = Set(Some(timestamp::now()));
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model
+            .update(&tr)
+            .await
+            .context("failed to move category to the trash")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            id,
+            EventAction::Delete,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        Self::left_shift_ordinals(&tr, deleted_ord, board_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Restores category with a given id from the trash together with its tags.
+    ///
+    /// The category is put back to the position it had when it was deleted, or to the end of the list
+    /// if the board has fewer categories now.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no category with this id in the trash.
+    pub async fn restore_category_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let model = categories::Entity::find_by_id(id)
+            .filter(categories::Column::DeletedAt.is_not_null())
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+        let category_count = Query::get_category_count(db, model.board_id).await?;
+        let new_ord = model.ordinal.min(category_count);
+        let board_id = model.board_id;
+        let before = model.clone();
+        let mut model = model.into_active_model();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord, board_id).await?;
+
+        model.ordinal = Set(new_ord);
+        model.deleted_at = Set(None);
+        let res = model
+            .update(&tr)
+            .await
+            .context("failed to update category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            id,
+            EventAction::Restore,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Permanently deletes the category with a given id, without moving it to the trash first.
+    ///
+    /// This also deletes its tags and their links to activities.
+    /// Shifts ordinals to match the correct order of the remaining categories.
+    /// Used to revert the creation of a category.
+    ///
+    /// Returns `Err(RowNotFound)` if the category does not exist or is in the trash.
+    pub async fn remove_category_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let category = categories::Entity::find_by_id(id)
+            .filter(categories::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to fetch category")?
+            .ok_or(AppError::RowNotFound)?;
+
         let tr = db.begin().await.context("failed to begin transaction")?;
         categories::Entity::delete_by_id(id)
             .exec(&tr)
             .await
             .context("failed to delete category")?;
-        Self::left_shift_ordinals(&tr, deleted_ord).await?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            id,
+            EventAction::Delete,
+            Some(&category),
+            None,
+        )
+        .await?;
+        Self::left_shift_ordinals(&tr, category.ordinal, category.board_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Inserts a previously removed category with all its fields, including its id.
+    ///
+    /// The category is put back to the position it had, or to the end of the list if the board has fewer categories now.
+    /// Used to reapply the creation of a category.
+    pub async fn reinsert_category(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        category: categories::Model,
+    ) -> Result<(), AppError> {
+        let category_count = Query::get_category_count(db, category.board_id).await?;
+        let new_ord = category.ordinal.min(category_count);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord, category.board_id).await?;
+        let res = categories::Model {
+            ordinal: new_ord,
+            ..category
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(&tr)
+        .await
+        .context("failed to insert category")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Category,
+            res.id,
+            EventAction::Restore,
+            None,
+            Some(&res),
+        )
+        .await?;
         tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
-    /// Helper function that decrements ordinals greater than `start_ord`.
+    /// Permanently deletes categories that were moved to the trash before `deleted_before`.
+    ///
+    /// This also deletes tags of the purged categories and their links to activities.
+    ///
+    /// Returns the number of purged categories.
+    pub async fn purge_trashed_categories(
+        db: &impl ConnectionTrait,
+        deleted_before: OffsetDateTime,
+    ) -> Result<u64, AppError> {
+        let purged = categories::Entity::find()
+            .filter(categories::Column::DeletedAt.lt(deleted_before))
+            .all(db)
+            .await
+            .context("failed to select trashed categories")?;
+        for category in purged.iter() {
+            categories::Entity::delete_by_id(category.id)
+                .exec(db)
+                .await
+                .context("failed to purge category")?;
+            history::Mutation::record(
+                db,
+                EventEntity::Category,
+                category.id,
+                EventAction::Purge,
+                Some(category),
+                None,
+            )
+            .await?;
+        }
+        Ok(purged.len() as u64)
+    }
+
+    /// Helper function that decrements ordinals greater than `start_ord` on a given board.
+    ///
+    /// Categories in the trash keep their ordinals.
     async fn left_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
+        board_id: i32,
     ) -> Result<(), AppError> {
         categories::Entity::update_many()
             .filter(categories::Column::Ordinal.gt(start_ord))
+            .filter(categories::Column::BoardId.eq(board_id))
+            .filter(categories::Column::DeletedAt.is_null())
             .col_expr(
                 categories::Column::Ordinal,
                 SimpleExpr::from(categories::Column::Ordinal.into_expr())
@@ -164,14 +816,18 @@ impl Mutation {
         Ok(())
     }
 
-    /// Helper function that increments ordinals equal to at least `start_ord`.
-    #[allow(unused)]
+    /// Helper function that increments ordinals equal to at least `start_ord` on a given board.
+    ///
+    /// Categories in the trash keep their ordinals.
     async fn right_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
+        board_id: i32,
     ) -> Result<(), AppError> {
         categories::Entity::update_many()
             .filter(categories::Column::Ordinal.gte(start_ord))
+            .filter(categories::Column::BoardId.eq(board_id))
+            .filter(categories::Column::DeletedAt.is_null())
             .col_expr(
                 categories::Column::Ordinal,
                 SimpleExpr::from(categories::Column::Ordinal.into_expr())
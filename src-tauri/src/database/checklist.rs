@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use crate::{
+    commands::{
+        checklist::{
+            ChecklistItemOutput, CreateChecklistItemInput, UpdateChecklistItemDoneInput,
+            UpdateChecklistItemOrdinalInput, UpdateChecklistItemTextInput,
+        },
+        fetch::ChecklistProgressOutput,
+    },
+    errors::AppError,
+};
+use anyhow::Context;
+use entity::{
+    activities,
+    checklist_items::{self, Entity as ChecklistItem},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Fetches all checklist items of the activity with `activity_id` associated with their ids.
+    pub async fn all_checklist_items(
+        db: &DbConn,
+        activity_id: i32,
+    ) -> Result<HashMap<i32, ChecklistItemOutput>, DbErr> {
+        let res = ChecklistItem::find()
+            .filter(checklist_items::Column::ActivityId.eq(activity_id))
+            .order_by_asc(checklist_items::Column::Ordinal)
+            .all(db)
+            .await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, item| {
+            acc.insert(
+                item.id,
+                ChecklistItemOutput {
+                    text: item.text,
+                    done: item.done,
+                    ordinal: item.ordinal,
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
+    /// Counts completed and all checklist items of every activity that has a checklist.
+    ///
+    /// Returns the counts associated with activity ids.
+    pub async fn progress_by_activity(
+        db: &DbConn,
+    ) -> Result<HashMap<i32, ChecklistProgressOutput>, DbErr> {
+        let res: Vec<(i32, bool)> = ChecklistItem::find()
+            .select_only()
+            .column(checklist_items::Column::ActivityId)
+            .column(checklist_items::Column::Done)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        let out = res
+            .into_iter()
+            .fold(HashMap::new(), |mut acc, (activity_id, done)| {
+                let progress: &mut ChecklistProgressOutput = acc.entry(activity_id).or_default();
+                progress.total += 1;
+                if done {
+                    progress.completed += 1;
+                }
+                acc
+            });
+        Ok(out)
+    }
+
+    /// Helper function to fetch the checklist item with a given id.
+    ///
+    /// Returns `Err(RowNotFound)` if the item does not exist.
+    async fn get_item_from_id(db: &DbConn, id: i32) -> Result<checklist_items::Model, AppError> {
+        let res = checklist_items::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to select checklist item")?
+            .ok_or(AppError::RowNotFound)?;
+        Ok(res)
+    }
+
+    /// Helper function to get the number of checklist items of the activity with `activity_id`.
+    async fn get_item_count(db: &DbConn, activity_id: i32) -> Result<i32, AppError> {
+        let res = checklist_items::Entity::find()
+            .filter(checklist_items::Column::ActivityId.eq(activity_id))
+            .count(db)
+            .await
+            .context("failed to determine count of checklist items")?;
+        Ok(res as i32)
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Appends an item to the end of the checklist of the activity given in `data`.
+    ///
+    /// Returns the created item with appropriate id and ordinal, or `Err(RowNotFound)` if the activity does not exist.
+    pub async fn insert_checklist_item(
+        db: &DbConn,
+        data: CreateChecklistItemInput,
+    ) -> Result<checklist_items::Model, AppError> {
+        activities::Entity::find_by_id(data.activity_id)
+            .one(db)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let item_count = Query::get_item_count(db, data.activity_id).await?;
+        let model = checklist_items::ActiveModel {
+            activity_id: Set(data.activity_id),
+            text: Set(data.text),
+            done: Set(false),
+            ordinal: Set(item_count),
+            ..Default::default()
+        };
+        let model = model
+            .insert(db)
+            .await
+            .context("failed to insert checklist item")?;
+        Ok(model)
+    }
+
+    /// Changes the text of the checklist item with id given in `data`.
+    ///
+    /// Returns `Err(RowNotFound)` if the item does not exist.
+    pub async fn update_checklist_item_text(
+        db: &DbConn,
+        data: UpdateChecklistItemTextInput,
+    ) -> Result<(), AppError> {
+        let mut model = Query::get_item_from_id(db, data.id)
+            .await?
+            .into_active_model();
+
+        model.text = Set(data.text);
+        model
+            .update(db)
+            .await
+            .context("failed to update checklist item")?;
+        Ok(())
+    }
+
+    /// Marks the checklist item with id given in `data` as done or not done.
+    ///
+    /// Returns `Err(RowNotFound)` if the item does not exist.
+    pub async fn update_checklist_item_done(
+        db: &DbConn,
+        data: UpdateChecklistItemDoneInput,
+    ) -> Result<(), AppError> {
+        let mut model = Query::get_item_from_id(db, data.id)
+            .await?
+            .into_active_model();
+
+        model.done = Set(data.done);
+        model
+            .update(db)
+            .await
+            .context("failed to update checklist item")?;
+        Ok(())
+    }
+
+    /// Updates the position of the given item in its checklist.
+    ///
+    /// This also shifts ordinals of other items of the checklist to achieve correct ordering.
+    ///
+    /// Returns `Err(RowNotFound)` if the item does not exist.
+    pub async fn update_checklist_item_ordinal(
+        db: &DbConn,
+        data: UpdateChecklistItemOrdinalInput,
+    ) -> Result<(), AppError> {
+        let model = Query::get_item_from_id(db, data.id).await?;
+        let old_ord = model.ordinal;
+        let activity_id = model.activity_id;
+        let mut model = model.into_active_model();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::left_shift_ordinals(&tr, old_ord, activity_id).await?;
+        Self::right_shift_ordinals(&tr, data.new_ord, activity_id).await?;
+
+        model.ordinal = Set(data.new_ord);
+        model
+            .update(&tr)
+            .await
+            .context("failed to update checklist item")?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Deletes checklist item with a given id.
+    ///
+    /// This also shifts ordinals to maintain correct order of the checklist.
+    ///
+    /// Returns `Err(RowNotFound)` if the item does not exist.
+    pub async fn delete_checklist_item_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+        let model = Query::get_item_from_id(db, id).await?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        checklist_items::Entity::delete_by_id(id)
+            .exec(&tr)
+            .await
+            .context("failed to delete checklist item")?;
+        Self::left_shift_ordinals(&tr, model.ordinal, model.activity_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Helper function that decrements ordinals greater than `start_ord` in the checklist of a given activity.
+    async fn left_shift_ordinals(
+        db: &impl ConnectionTrait,
+        start_ord: i32,
+        activity_id: i32,
+    ) -> Result<(), AppError> {
+        checklist_items::Entity::update_many()
+            .filter(checklist_items::Column::Ordinal.gt(start_ord))
+            .filter(checklist_items::Column::ActivityId.eq(activity_id))
+            .col_expr(
+                checklist_items::Column::Ordinal,
+                checklist_items::Column::Ordinal.into_expr().sub(1),
+            )
+            .exec(db)
+            .await
+            .context("failed to left shift ordinals")?;
+        Ok(())
+    }
+
+    /// Helper function that increments ordinals equal to at least `start_ord` in the checklist of a given activity.
+    async fn right_shift_ordinals(
+        db: &impl ConnectionTrait,
+        start_ord: i32,
+        activity_id: i32,
+    ) -> Result<(), AppError> {
+        checklist_items::Entity::update_many()
+            .filter(checklist_items::Column::Ordinal.gte(start_ord))
+            .filter(checklist_items::Column::ActivityId.eq(activity_id))
+            .col_expr(
+                checklist_items::Column::Ordinal,
+                checklist_items::Column::Ordinal.into_expr().add(1),
+            )
+            .exec(db)
+            .await
+            .context("failed to right shift ordinals")?;
+        Ok(())
+    }
+}
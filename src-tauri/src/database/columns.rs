@@ -2,28 +2,39 @@ use std::collections::HashMap;
 
 use crate::{
     commands::{
-        columns::{RenameColumnInput, UpdateColumnOrdinalInput},
+        columns::{
+            RenameColumnInput, SetColumnOrderInput, UpdateColumnDoneInput,
+            UpdateColumnOrdinalInput, UpdateColumnTriageInput, UpdateColumnWipLimitInput,
+        },
         fetch::ColumnOutput,
+        trash::TrashedColumnOutput,
     },
+    database::history,
     errors::AppError,
+    utils::{ordering, timestamp},
 };
 use anyhow::Context;
 use entity::{
     activities,
     columns::{self, Entity as Column},
+    sea_orm_active_enums::{EventAction, EventEntity},
 };
 use sea_orm::{
     sea_query::SimpleExpr, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbConn,
-    DbErr, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    DbErr, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QueryTrait, Set,
     TransactionTrait,
 };
+use time::OffsetDateTime;
 
 pub struct Query;
 
 impl Query {
     /// Helper function to fetch column ordinal based on id.
-    async fn get_ordinal_from_id(db: &DbConn, id: i32) -> Result<i32, AppError> {
+    ///
+    /// Returns `Err(RowNotFound)` if the column does not exist or is in the trash.
+    async fn get_ordinal_from_id(db: &impl ConnectionTrait, id: i32) -> Result<i32, AppError> {
         let res = columns::Entity::find_by_id(id)
+            .filter(columns::Column::DeletedAt.is_null())
             .one(db)
             .await
             .context("failed to get ordinal from id")?
@@ -31,9 +42,21 @@ impl Query {
         Ok(res.ordinal)
     }
 
-    /// Helper function to get a current number of columns.
-    async fn get_column_count(db: &DbConn) -> Result<i32, AppError> {
+    /// Helper function to fetch the id of the board that owns the column.
+    async fn get_board_id_from_id(db: &impl ConnectionTrait, id: i32) -> Result<i32, AppError> {
+        let res = columns::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to get board_id from id")?
+            .ok_or(AppError::RowNotFound)?;
+        Ok(res.board_id)
+    }
+
+    /// Helper function to get a current number of columns on a given board, excluding the trash.
+    async fn get_column_count(db: &impl ConnectionTrait, board_id: i32) -> Result<i32, AppError> {
         let res = columns::Entity::find()
+            .filter(columns::Column::BoardId.eq(board_id))
+            .filter(columns::Column::DeletedAt.is_null())
             .count(db)
             .await
             .context("failed to determine count of columns")?;
@@ -42,10 +65,20 @@ impl Query {
 
     /// Fetches all persisted columns with their contents associated with their ids.
     ///
+    /// Columns in the trash are skipped.
+    /// If `board_id` is `Some`, only the columns of that board are fetched.
+    ///
     /// Used at the startup of the application.
-    pub async fn all_columns(db: &DbConn) -> Result<HashMap<i32, ColumnOutput>, DbErr> {
+    pub async fn all_columns(
+        db: &DbConn,
+        board_id: Option<i32>,
+    ) -> Result<HashMap<i32, ColumnOutput>, DbErr> {
         let res = Column::find()
             .find_with_related(activities::Entity)
+            .filter(columns::Column::DeletedAt.is_null())
+            .apply_if(board_id, |query, board_id| {
+                query.filter(columns::Column::BoardId.eq(board_id))
+            })
             .order_by_asc(columns::Column::Ordinal)
             .all(db)
             .await?;
@@ -58,6 +91,10 @@ impl Query {
                         ColumnOutput {
                             name: column.name,
                             ordinal: column.ordinal,
+                            board_id: column.board_id,
+                            is_done: column.is_done,
+                            wip_limit: column.wip_limit,
+                            requires_triage: column.requires_triage,
                             activities: activities
                                 .into_iter()
                                 .map(|activity| activity.id)
@@ -69,14 +106,44 @@ impl Query {
         Ok(out)
     }
 
+    /// Fetches all columns in the trash associated with their ids.
+    pub async fn all_trashed_columns(
+        db: &DbConn,
+    ) -> Result<HashMap<i32, TrashedColumnOutput>, DbErr> {
+        let res = Column::find()
+            .find_with_related(activities::Entity)
+            .filter(columns::Column::DeletedAt.is_not_null())
+            .all(db)
+            .await?;
+
+        let out = res
+            .into_iter()
+            .fold(HashMap::new(), |mut acc, (column, activities)| {
+                acc.insert(
+                    column.id,
+                    TrashedColumnOutput {
+                        name: column.name,
+                        board_id: column.board_id,
+                        activities: activities.into_iter().map(|activity| activity.id).collect(),
+                        deleted_at: column.deleted_at.unwrap(),
+                    },
+                );
+                acc
+            });
+        Ok(out)
+    }
+
     /// Helper function to get the number of activities in a column given by id.
     ///
-    /// A `None` value in `column_id` means counting activities in the stash.
+    /// A `None` value in `column_id` means counting activities in the stash of the board with `board_id`.
+    ///
+    /// Archived activities are not counted.
     ///
     /// Returns 0 if column with id equal to `column_id` does not exist.
     async fn get_activity_count_in_column(
-        db: &DbConn,
+        db: &impl ConnectionTrait,
         column_id: Option<i32>,
+        board_id: i32,
     ) -> Result<i32, AppError> {
         let res = activities::Entity::find()
             .filter(
@@ -85,9 +152,11 @@ impl Query {
                     .add(
                         activities::Column::ColumnId
                             .is_null()
-                            .and(SimpleExpr::from(column_id == None)),
+                            .and(SimpleExpr::from(column_id.is_none())),
                     ),
             )
+            .filter(activities::Column::BoardId.eq(board_id))
+            .filter(activities::Column::ArchivedAt.is_null())
             .count(db)
             .await
             .context("failed to determine count of columns")?;
@@ -98,17 +167,33 @@ impl Query {
 pub struct Mutation;
 
 impl Mutation {
-    /// Appends column to the end of the column list.
+    /// Appends column to the end of the column list of the board with `board_id`.
     ///
     /// Returns the created column with appropriate id and ordinal.
-    pub async fn insert_column(db: &DbConn, name: String) -> Result<columns::Model, AppError> {
-        let column_count = Query::get_column_count(db).await?;
+    pub async fn insert_column(
+        db: &DbConn,
+        board_id: i32,
+        name: String,
+    ) -> Result<columns::Model, AppError> {
+        let column_count = Query::get_column_count(db, board_id).await?;
         let model = columns::ActiveModel {
             name: Set(name),
             ordinal: Set(column_count),
+            board_id: Set(board_id),
             ..Default::default()
         };
-        let model = model.insert(db).await.context("failed to insert column")?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let model = model.insert(&tr).await.context("failed to insert column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            model.id,
+            EventAction::Create,
+            None,
+            Some(&model),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(model)
     }
 
@@ -116,15 +201,124 @@ impl Mutation {
     ///
     /// Returns `Err(RowNotFound)` if column with the given id does not exist.
     pub async fn update_column_name(db: &DbConn, data: RenameColumnInput) -> Result<(), AppError> {
-        let mut model = columns::Entity::find_by_id(data.id)
+        let before = columns::Entity::find_by_id(data.id)
             .one(db)
             .await
             .context("failed to select column")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
 
         model.name = Set(data.new_name);
-        model.update(db).await.context("failed to update column")?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Marks the column with id given in `data` as a column of finished activities or unmarks it.
+    ///
+    /// Returns `Err(RowNotFound)` if column with the given id does not exist.
+    pub async fn update_column_done(
+        db: &DbConn,
+        data: UpdateColumnDoneInput,
+    ) -> Result<(), AppError> {
+        let before = columns::Entity::find_by_id(data.id)
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
+
+        model.is_done = Set(data.is_done);
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Sets whether activities must hold tags of all required categories to be moved into the column with id given in `data`.
+    ///
+    /// Activities already in the column are kept even if they miss some tags.
+    ///
+    /// Returns `Err(RowNotFound)` if column with the given id does not exist.
+    pub async fn update_column_triage(
+        db: &DbConn,
+        data: UpdateColumnTriageInput,
+    ) -> Result<(), AppError> {
+        let before = columns::Entity::find_by_id(data.id)
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
+
+        model.requires_triage = Set(data.requires_triage);
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Sets the maximum number of activities in the column with id given in `data`, or removes the limit if it is `None`.
+    ///
+    /// Activities already in the column are kept even if they exceed the new limit.
+    ///
+    /// Returns `Err(InvalidWipLimit)` if the limit is negative
+    /// and `Err(RowNotFound)` if column with the given id does not exist.
+    pub async fn update_column_wip_limit(
+        db: &DbConn,
+        data: UpdateColumnWipLimitInput,
+    ) -> Result<(), AppError> {
+        if data.wip_limit.is_some_and(|limit| limit < 0) {
+            return Err(AppError::InvalidWipLimit);
+        }
+        let before = columns::Entity::find_by_id(data.id)
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
+
+        model.wip_limit = Set(data.wip_limit);
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            data.id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
@@ -132,67 +326,315 @@ impl Mutation {
     ///
     /// Returns `Err(RowNotFound)` if column with id given in `data` does not exist.
     pub async fn update_column_ordinal(
-        db: &DbConn,
+        db: &(impl ConnectionTrait + TransactionTrait),
         data: UpdateColumnOrdinalInput,
     ) -> Result<(), AppError> {
         let old_ord = Query::get_ordinal_from_id(db, data.column_id).await?;
-        let mut model = columns::Entity::find_by_id(data.column_id)
+        let board_id = Query::get_board_id_from_id(db, data.column_id).await?;
+        let before = columns::Entity::find_by_id(data.column_id)
             .one(db)
             .await
             .context("failed to select column")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
 
         let tr = db.begin().await.context("failed to begin transaction")?;
-        Self::left_shift_ordinals(&tr, old_ord).await?;
-        Self::right_shift_ordinals(&tr, data.new_ord).await?;
+        Self::left_shift_ordinals(&tr, old_ord, board_id).await?;
+        Self::right_shift_ordinals(&tr, data.new_ord, board_id).await?;
 
         model.ordinal = Set(data.new_ord);
-        model.update(&tr).await.context("failed to update column")?;
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            data.column_id,
+            EventAction::Move,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
         tr.commit().await.context("failed to commit transaction")?;
 
         Ok(())
     }
 
-    /// Deletes column with the id equal to `id`.
+    /// Puts the columns of the board with id given in `data` in the order of the ids given in `data`.
+    ///
+    /// Returns `Err(InvalidOrder)` if the ids are not a permutation of the ids of the columns outside the trash.
+    pub async fn set_column_order(db: &DbConn, data: SetColumnOrderInput) -> Result<(), AppError> {
+        let res = Column::find()
+            .filter(columns::Column::BoardId.eq(data.board_id))
+            .filter(columns::Column::DeletedAt.is_null())
+            .order_by_asc(columns::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select columns")?;
+        let current: Vec<i32> = res.iter().map(|model| model.id).collect();
+        let moves = ordering::moves_between(&current, &data.ids)?;
+        let models: HashMap<i32, columns::Model> =
+            res.into_iter().map(|model| (model.id, model)).collect();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        history::Mutation::record_moves(
+            &tr,
+            EventEntity::Column,
+            &models,
+            moves,
+            |model, ordinal| columns::Model {
+                ordinal,
+                ..model.clone()
+            },
+        )
+        .await?;
+        for (ordinal, id) in data.ids.iter().enumerate() {
+            let model = &models[id];
+            if model.ordinal != ordinal as i32 {
+                let mut model = model.clone().into_active_model();
+                model.ordinal = Set(ordinal as i32);
+                model.update(&tr).await.context("failed to update column")?;
+            }
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Moves column with the id equal to `id` to the trash.
+    ///
+    /// Activities of the column stay in it and are hidden together with the column.
+    /// The column keeps its ordinal, so that it can be restored to the same position.
+    /// Updates ordinals to maintain correct order of the remaining columns.
     ///
-    /// Updates ordinals to maintain correct order of columns.
-    pub async fn delete_column_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+    /// Returns `Err(RowNotFound)` if the column does not exist or is already in the trash.
+    pub async fn delete_column_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
         let deleted_ordinal = Query::get_ordinal_from_id(db, id).await?;
-        let other_activity_count = Query::get_activity_count_in_column(db, None)
+        let before = columns::Entity::find_by_id(id)
+            .one(db)
             .await
-            .context("failed to determine the count of other activities")?;
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        let board_id = before.board_id;
+        let mut model = before.clone().into_active_model();
+        model.deleted_at = Set(Some(timestamp::now()));
+
         let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model
+            .update(&tr)
+            .await
+            .context("failed to move column to the trash")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            id,
+            EventAction::Delete,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+
+        Self::left_shift_ordinals(&tr, deleted_ordinal, board_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Restores column with the id equal to `id` from the trash together with its activities.
+    ///
+    /// The column is put back to the position it had when it was deleted, or to the end of the list
+    /// if the board has fewer columns now.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no column with this id in the trash.
+    pub async fn restore_column_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let model = columns::Entity::find_by_id(id)
+            .filter(columns::Column::DeletedAt.is_not_null())
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        let column_count = Query::get_column_count(db, model.board_id).await?;
+        let new_ord = model.ordinal.min(column_count);
+        let board_id = model.board_id;
+        let before = model.clone();
+        let mut model = model.into_active_model();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord, board_id).await?;
+
+        model.ordinal = Set(new_ord);
+        model.deleted_at = Set(None);
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            id,
+            EventAction::Restore,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Permanently deletes the column with the id equal to `id`, without moving it to the trash first.
+    ///
+    /// Activities of the column are moved to the stash of its board.
+    /// Updates ordinals to maintain correct order of the remaining columns.
+    /// Used to revert the creation of a column.
+    ///
+    /// Returns `Err(RowNotFound)` if the column does not exist or is in the trash.
+    pub async fn remove_column_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let column = columns::Entity::find_by_id(id)
+            .filter(columns::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::remove_column(&tr, &column, EventAction::Delete).await?;
+        Self::left_shift_ordinals(&tr, column.ordinal, column.board_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Inserts a previously removed column with all its fields, including its id.
+    ///
+    /// The column is put back to the position it had, or to the end of the list if the board has fewer columns now.
+    /// Used to reapply the creation of a column.
+    pub async fn reinsert_column(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        column: columns::Model,
+    ) -> Result<(), AppError> {
+        let column_count = Query::get_column_count(db, column.board_id).await?;
+        let new_ord = column.ordinal.min(column_count);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord, column.board_id).await?;
+        let res = columns::Model {
+            ordinal: new_ord,
+            ..column
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(&tr)
+        .await
+        .context("failed to insert column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Column,
+            res.id,
+            EventAction::Restore,
+            None,
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Permanently deletes columns that were moved to the trash before `deleted_before`.
+    ///
+    /// Activities of the purged columns are moved to the stash of their board.
+    ///
+    /// Returns the number of purged columns.
+    pub async fn purge_trashed_columns(
+        db: &impl ConnectionTrait,
+        deleted_before: OffsetDateTime,
+    ) -> Result<u64, AppError> {
+        let purged = columns::Entity::find()
+            .filter(columns::Column::DeletedAt.lt(deleted_before))
+            .all(db)
+            .await
+            .context("failed to select trashed columns")?;
+
+        for column in purged.iter() {
+            Self::remove_column(db, column, EventAction::Purge).await?;
+        }
+
+        Ok(purged.len() as u64)
+    }
+
+    /// Helper function that deletes a column and moves its activities to the stash of its board.
+    ///
+    /// The deletion is recorded with `action`, followed by the moves of the activities. Ordinals of other columns are not updated.
+    async fn remove_column(
+        db: &impl ConnectionTrait,
+        column: &columns::Model,
+        action: EventAction,
+    ) -> Result<(), AppError> {
+        let other_activity_count = Query::get_activity_count_in_column(db, None, column.board_id)
+            .await
+            .context("failed to determine the count of other activities")?;
+        let moved = activities::Entity::find()
+            .filter(activities::Column::ColumnId.eq(column.id))
+            .all(db)
+            .await
+            .context("failed to select activities of column")?;
 
         activities::Entity::update_many()
-            .filter(activities::Column::ColumnId.eq(id))
+            .filter(activities::Column::ColumnId.eq(column.id))
             .col_expr(
                 activities::Column::Ordinal,
                 activities::Column::Ordinal
                     .into_expr()
                     .add(other_activity_count),
             )
-            .exec(&tr)
+            .exec(db)
             .await
             .context("failed to update activity ordinals")?;
 
-        columns::Entity::delete_by_id(id)
-            .exec(&tr)
+        columns::Entity::delete_by_id(column.id)
+            .exec(db)
             .await
             .context("failed to delete column")?;
+        history::Mutation::record(
+            db,
+            EventEntity::Column,
+            column.id,
+            action,
+            Some(column),
+            None,
+        )
+        .await?;
 
-        Self::left_shift_ordinals(&tr, deleted_ordinal).await?;
-        tr.commit().await.context("failed to commit transaction")?;
+        for before in moved {
+            let after = activities::Entity::find_by_id(before.id)
+                .one(db)
+                .await
+                .context("failed to select activity")?;
+            history::Mutation::record(
+                db,
+                EventEntity::Activity,
+                before.id,
+                EventAction::Move,
+                Some(&before),
+                after.as_ref(),
+            )
+            .await?;
+        }
         Ok(())
     }
 
-    /// Helper function that decrements ordinals greater than `start_ord`.
+    /// Helper function that decrements ordinals greater than `start_ord` on a given board.
+    ///
+    /// Columns in the trash keep their ordinals.
     async fn left_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
+        board_id: i32,
     ) -> Result<(), AppError> {
         columns::Entity::update_many()
             .filter(columns::Column::Ordinal.gt(start_ord))
+            .filter(columns::Column::BoardId.eq(board_id))
+            .filter(columns::Column::DeletedAt.is_null())
             .col_expr(
                 columns::Column::Ordinal,
                 columns::Column::Ordinal.into_expr().sub(1),
@@ -203,13 +645,18 @@ impl Mutation {
         Ok(())
     }
 
-    /// Helper function that increments ordinals equal to at least `start_ord`.
+    /// Helper function that increments ordinals equal to at least `start_ord` on a given board.
+    ///
+    /// Columns in the trash keep their ordinals.
     async fn right_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
+        board_id: i32,
     ) -> Result<(), AppError> {
         columns::Entity::update_many()
             .filter(columns::Column::Ordinal.gte(start_ord))
+            .filter(columns::Column::BoardId.eq(board_id))
+            .filter(columns::Column::DeletedAt.is_null())
             .col_expr(
                 columns::Column::Ordinal,
                 columns::Column::Ordinal.into_expr().add(1),
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::{
+    commands::comments::{AddCommentInput, CommentOutput, EditCommentInput},
+    errors::AppError,
+    utils::timestamp,
+};
+use anyhow::Context;
+use entity::{
+    activities,
+    activity_comments::{self, Entity as ActivityComment},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, IntoActiveModel, QueryFilter,
+    QuerySelect, Set,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Fetches all comments of the activity with `activity_id` associated with their ids.
+    pub async fn all_activity_comments(
+        db: &DbConn,
+        activity_id: i32,
+    ) -> Result<HashMap<i32, CommentOutput>, DbErr> {
+        let res = ActivityComment::find()
+            .filter(activity_comments::Column::ActivityId.eq(activity_id))
+            .all(db)
+            .await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, comment| {
+            acc.insert(
+                comment.id,
+                CommentOutput {
+                    author: comment.author,
+                    text: comment.text,
+                    created_at: comment.created_at,
+                    updated_at: comment.updated_at,
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
+    /// Counts comments of every activity that has any.
+    ///
+    /// Returns the counts associated with activity ids.
+    pub async fn comment_count_by_activity(db: &DbConn) -> Result<HashMap<i32, i32>, DbErr> {
+        let res: Vec<(i32, i32)> = ActivityComment::find()
+            .select_only()
+            .column(activity_comments::Column::ActivityId)
+            .column_as(activity_comments::Column::Id.count(), "count")
+            .group_by(activity_comments::Column::ActivityId)
+            .into_tuple()
+            .all(db)
+            .await?;
+        Ok(res.into_iter().collect())
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Adds a comment to the activity given in `data`.
+    ///
+    /// Returns the created comment, or `Err(RowNotFound)` if the activity does not exist.
+    pub async fn insert_comment(
+        db: &DbConn,
+        data: AddCommentInput,
+    ) -> Result<activity_comments::Model, AppError> {
+        activities::Entity::find_by_id(data.activity_id)
+            .one(db)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let now = timestamp::now();
+        let model = activity_comments::ActiveModel {
+            activity_id: Set(data.activity_id),
+            author: Set(data.author),
+            text: Set(data.text),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        let model = model.insert(db).await.context("failed to insert comment")?;
+        Ok(model)
+    }
+
+    /// Changes the text of the comment with id given in `data`.
+    ///
+    /// Returns `Err(RowNotFound)` if the comment does not exist.
+    pub async fn update_comment_text(db: &DbConn, data: EditCommentInput) -> Result<(), AppError> {
+        let mut model = ActivityComment::find_by_id(data.id)
+            .one(db)
+            .await
+            .context("failed to select comment")?
+            .ok_or(AppError::RowNotFound)?
+            .into_active_model();
+
+        model.text = Set(data.text);
+        model.updated_at = Set(timestamp::now());
+        model.update(db).await.context("failed to update comment")?;
+        Ok(())
+    }
+
+    /// Deletes the comment with a given id.
+    ///
+    /// Returns `Err(RowNotFound)` if the comment does not exist.
+    pub async fn delete_comment_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+        let res = ActivityComment::delete_by_id(id)
+            .exec(db)
+            .await
+            .context("failed to delete comment")?;
+        if res.rows_affected == 0 {
+            return Err(AppError::RowNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+
+use crate::{
+    commands::{
+        custom_fields::{
+            AddCustomFieldOptionInput, CreateCustomFieldInput, RenameCustomFieldInput,
+            SetActivityFieldValueInput,
+        },
+        fetch::{CustomFieldOutput, FieldOptionOutput},
+    },
+    errors::AppError,
+};
+use anyhow::Context;
+use entity::{
+    activities, activity_field_values, custom_field_options,
+    custom_fields::{self, Entity as CustomField},
+    sea_orm_active_enums::FieldKind,
+};
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr,
+    EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QueryTrait, Set,
+    TransactionTrait,
+};
+use serde_json::Value;
+use time::Date;
+
+pub struct Query;
+
+impl Query {
+    /// Fetches custom fields with their options.
+    ///
+    /// Returns two maps:
+    /// - the first map associates field ids with their definitions
+    /// - the second map associates option ids with their contents.
+    ///
+    /// If `board_id` is `Some`, only the fields of that board are fetched.
+    pub async fn all_with_options(
+        db: &DbConn,
+        board_id: Option<i32>,
+    ) -> Result<
+        (
+            HashMap<i32, CustomFieldOutput>,
+            HashMap<i32, FieldOptionOutput>,
+        ),
+        DbErr,
+    > {
+        let res = CustomField::find()
+            .find_with_related(custom_field_options::Entity)
+            .apply_if(board_id, |query, board_id| {
+                query.filter(custom_fields::Column::BoardId.eq(board_id))
+            })
+            .order_by_asc(custom_fields::Column::Ordinal)
+            .all(db)
+            .await?;
+
+        let mut fields = HashMap::new();
+        let mut options = HashMap::new();
+        res.into_iter().for_each(|(field, field_options)| {
+            fields.insert(
+                field.id,
+                CustomFieldOutput {
+                    name: field.name,
+                    kind: field.kind,
+                    ordinal: field.ordinal,
+                    board_id: field.board_id,
+                    options: field_options.iter().map(|option| option.id).collect(),
+                },
+            );
+            field_options.into_iter().for_each(|option| {
+                options.insert(
+                    option.id,
+                    FieldOptionOutput {
+                        name: option.name,
+                        ordinal: option.ordinal,
+                        field_id: field.id,
+                    },
+                );
+            })
+        });
+
+        Ok((fields, options))
+    }
+
+    /// Fetches custom field values of all activities.
+    ///
+    /// Returns the values associated with field ids, associated with activity ids.
+    /// A value is a number, a string, a `YYYY-MM-DD` date or an option id, depending on the kind of the field.
+    pub async fn values_by_activity(
+        db: &DbConn,
+    ) -> Result<HashMap<i32, HashMap<i32, Value>>, DbErr> {
+        let res = activity_field_values::Entity::find().all(db).await?;
+
+        let out = res.into_iter().fold(
+            HashMap::new(),
+            |mut acc: HashMap<i32, HashMap<i32, Value>>, value| {
+                let json = if let Some(number) = value.number_value {
+                    Value::from(number)
+                } else if let Some(text) = value.text_value {
+                    Value::from(text)
+                } else if let Some(date) = value.date_value {
+                    Value::from(date.to_string())
+                } else {
+                    Value::from(value.option_id)
+                };
+                acc.entry(value.activity_id)
+                    .or_default()
+                    .insert(value.field_id, json);
+                acc
+            },
+        );
+        Ok(out)
+    }
+
+    /// Helper function that fetches the custom field with a given id.
+    ///
+    /// Returns `Err(RowNotFound)` if the field does not exist.
+    async fn get_field_from_id(db: &DbConn, id: i32) -> Result<custom_fields::Model, AppError> {
+        let res = CustomField::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to select custom field")?
+            .ok_or(AppError::RowNotFound)?;
+        Ok(res)
+    }
+
+    /// Helper function that counts custom fields on a given board.
+    async fn get_field_count(db: &impl ConnectionTrait, board_id: i32) -> Result<i32, AppError> {
+        let res = CustomField::find()
+            .filter(custom_fields::Column::BoardId.eq(board_id))
+            .count(db)
+            .await
+            .context("failed to determine count of custom fields")?;
+        Ok(res as i32)
+    }
+
+    /// Helper function that counts options of a given custom field.
+    async fn get_option_count(db: &impl ConnectionTrait, field_id: i32) -> Result<i32, AppError> {
+        let res = custom_field_options::Entity::find()
+            .filter(custom_field_options::Column::FieldId.eq(field_id))
+            .count(db)
+            .await
+            .context("failed to determine count of custom field options")?;
+        Ok(res as i32)
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Appends a custom field to the end of the field list of the board given in `data`.
+    ///
+    /// Options are only created for single-select fields and ignored for the other kinds.
+    ///
+    /// Returns the created field with appropriate id and ordinal.
+    pub async fn insert_custom_field(
+        db: &DbConn,
+        data: CreateCustomFieldInput,
+    ) -> Result<custom_fields::Model, AppError> {
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let field_count = Query::get_field_count(&tr, data.board_id).await?;
+        let field = custom_fields::ActiveModel {
+            board_id: Set(data.board_id),
+            name: Set(data.name),
+            kind: Set(data.kind),
+            ordinal: Set(field_count),
+            ..Default::default()
+        };
+        let field = field
+            .insert(&tr)
+            .await
+            .context("failed to insert custom field")?;
+
+        if field.kind == FieldKind::SingleSelect {
+            for (ordinal, name) in data.options.into_iter().enumerate() {
+                let option = custom_field_options::ActiveModel {
+                    field_id: Set(field.id),
+                    name: Set(name),
+                    ordinal: Set(ordinal as i32),
+                    ..Default::default()
+                };
+                option
+                    .insert(&tr)
+                    .await
+                    .context("failed to insert custom field option")?;
+            }
+        }
+
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(field)
+    }
+
+    /// Renames the custom field with id given in `data`.
+    ///
+    /// Returns `Err(RowNotFound)` if the field does not exist.
+    pub async fn update_custom_field_name(
+        db: &DbConn,
+        data: RenameCustomFieldInput,
+    ) -> Result<(), AppError> {
+        let mut model = Query::get_field_from_id(db, data.id)
+            .await?
+            .into_active_model();
+
+        model.name = Set(data.new_name);
+        model
+            .update(db)
+            .await
+            .context("failed to update custom field")?;
+        Ok(())
+    }
+
+    /// Deletes the custom field with a given id together with its options and values.
+    ///
+    /// Shifts ordinals to match the correct order of the remaining fields.
+    ///
+    /// Returns `Err(RowNotFound)` if the field does not exist.
+    pub async fn delete_custom_field_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+        let field = Query::get_field_from_id(db, id).await?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        CustomField::delete_by_id(id)
+            .exec(&tr)
+            .await
+            .context("failed to delete custom field")?;
+        CustomField::update_many()
+            .filter(custom_fields::Column::Ordinal.gt(field.ordinal))
+            .filter(custom_fields::Column::BoardId.eq(field.board_id))
+            .col_expr(
+                custom_fields::Column::Ordinal,
+                custom_fields::Column::Ordinal.into_expr().sub(1),
+            )
+            .exec(&tr)
+            .await
+            .context("failed to left shift ordinals")?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Appends an option to the end of the option list of the field given in `data`.
+    ///
+    /// Returns `Err(RowNotFound)` if the field does not exist
+    /// and `Err(InvalidFieldValue)` if it is not a single-select field.
+    pub async fn insert_custom_field_option(
+        db: &DbConn,
+        data: AddCustomFieldOptionInput,
+    ) -> Result<custom_field_options::Model, AppError> {
+        let field = Query::get_field_from_id(db, data.field_id).await?;
+        if field.kind != FieldKind::SingleSelect {
+            return Err(AppError::InvalidFieldValue);
+        }
+        let option_count = Query::get_option_count(db, field.id).await?;
+        let option = custom_field_options::ActiveModel {
+            field_id: Set(field.id),
+            name: Set(data.name),
+            ordinal: Set(option_count),
+            ..Default::default()
+        };
+        let option = option
+            .insert(db)
+            .await
+            .context("failed to insert custom field option")?;
+        Ok(option)
+    }
+
+    /// Deletes the custom field option with a given id. Activities with this option lose their value.
+    ///
+    /// Shifts ordinals to match the correct order of the remaining options.
+    ///
+    /// Returns `Err(RowNotFound)` if the option does not exist.
+    pub async fn delete_custom_field_option_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+        let option = custom_field_options::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to select custom field option")?
+            .ok_or(AppError::RowNotFound)?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        custom_field_options::Entity::delete_by_id(id)
+            .exec(&tr)
+            .await
+            .context("failed to delete custom field option")?;
+        custom_field_options::Entity::update_many()
+            .filter(custom_field_options::Column::Ordinal.gt(option.ordinal))
+            .filter(custom_field_options::Column::FieldId.eq(option.field_id))
+            .col_expr(
+                custom_field_options::Column::Ordinal,
+                custom_field_options::Column::Ordinal.into_expr().sub(1),
+            )
+            .exec(&tr)
+            .await
+            .context("failed to left shift ordinals")?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Sets the value of a custom field on an activity, or clears it if the value is `null`.
+    ///
+    /// Returns `Err(RowNotFound)` if the activity or the field does not exist, or they belong to different boards,
+    /// and `Err(InvalidFieldValue)` if the value does not match the kind of the field.
+    pub async fn set_activity_field_value(
+        db: &DbConn,
+        data: SetActivityFieldValueInput,
+    ) -> Result<(), AppError> {
+        let field = Query::get_field_from_id(db, data.field_id).await?;
+        let activity = activities::Entity::find_by_id(data.activity_id)
+            .one(db)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        if activity.board_id != field.board_id {
+            return Err(AppError::RowNotFound);
+        }
+
+        if data.value.is_null() {
+            activity_field_values::Entity::delete_many()
+                .filter(activity_field_values::Column::ActivityId.eq(activity.id))
+                .filter(activity_field_values::Column::FieldId.eq(field.id))
+                .exec(db)
+                .await
+                .context("failed to delete activity field value")?;
+            return Ok(());
+        }
+
+        let mut model = activity_field_values::ActiveModel {
+            activity_id: Set(activity.id),
+            field_id: Set(field.id),
+            number_value: Set(None),
+            text_value: Set(None),
+            date_value: Set(None),
+            option_id: Set(None),
+        };
+        match field.kind {
+            FieldKind::Number => {
+                let number: f64 = parse_value(data.value)?;
+                model.number_value = Set(Some(number));
+            }
+            FieldKind::Text => {
+                let text: String = parse_value(data.value)?;
+                model.text_value = Set(Some(text));
+            }
+            FieldKind::Date => {
+                let date: Date = parse_value(data.value)?;
+                model.date_value = Set(Some(date));
+            }
+            FieldKind::SingleSelect => {
+                let option_id: i32 = parse_value(data.value)?;
+                custom_field_options::Entity::find_by_id(option_id)
+                    .filter(custom_field_options::Column::FieldId.eq(field.id))
+                    .one(db)
+                    .await
+                    .context("failed to select custom field option")?
+                    .ok_or(AppError::InvalidFieldValue)?;
+                model.option_id = Set(Some(option_id));
+            }
+        }
+
+        activity_field_values::Entity::insert(model)
+            .on_conflict(
+                OnConflict::columns([
+                    activity_field_values::Column::ActivityId,
+                    activity_field_values::Column::FieldId,
+                ])
+                .update_columns([
+                    activity_field_values::Column::NumberValue,
+                    activity_field_values::Column::TextValue,
+                    activity_field_values::Column::DateValue,
+                    activity_field_values::Column::OptionId,
+                ])
+                .to_owned(),
+            )
+            .exec(db)
+            .await
+            .context("failed to insert activity field value")?;
+        Ok(())
+    }
+}
+
+/// Helper function that converts a JSON value to the type stored by a custom field.
+fn parse_value<T: serde::de::DeserializeOwned>(value: Value) -> Result<T, AppError> {
+    serde_json::from_value(value).map_err(|_| AppError::InvalidFieldValue)
+}
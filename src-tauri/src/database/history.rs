@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crate::{
+    commands::history::EventOutput,
+    errors::AppError,
+    utils::{ordering::OrderMove, timestamp},
+};
+use anyhow::Context;
+use entity::{
+    events::{self, Entity as Event},
+    sea_orm_active_enums::{EventAction, EventEntity},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use serde::Serialize;
+
+pub struct Query;
+
+impl Query {
+    /// Fetches the events of the activity with a given id, starting with the oldest one.
+    ///
+    /// Events of deleted activities are kept, so their history can still be fetched.
+    pub async fn activity_history(db: &DbConn, id: i32) -> Result<Vec<EventOutput>, DbErr> {
+        let res = Event::find()
+            .filter(events::Column::Entity.eq(EventEntity::Activity))
+            .filter(events::Column::EntityId.eq(id))
+            .order_by_asc(events::Column::Id)
+            .all(db)
+            .await?;
+
+        let out = res
+            .into_iter()
+            .map(|event| EventOutput {
+                id: event.id,
+                entity: event.entity,
+                entity_id: event.entity_id,
+                action: event.action,
+                before: event
+                    .before
+                    .and_then(|json| serde_json::from_str(&json).ok()),
+                after: event
+                    .after
+                    .and_then(|json| serde_json::from_str(&json).ok()),
+                created_at: event.created_at,
+            })
+            .collect();
+        Ok(out)
+    }
+
+    /// Fetches the id of the latest event, or 0 if there are no events.
+    pub async fn last_event_id(db: &DbConn) -> Result<i32, DbErr> {
+        let res = Event::find()
+            .order_by_desc(events::Column::Id)
+            .one(db)
+            .await?;
+        Ok(res.map_or(0, |event| event.id))
+    }
+
+    /// Fetches all events recorded after the event with a given id, starting with the oldest one.
+    pub async fn events_after(db: &DbConn, id: i32) -> Result<Vec<events::Model>, DbErr> {
+        Event::find()
+            .filter(events::Column::Id.gt(id))
+            .order_by_asc(events::Column::Id)
+            .all(db)
+            .await
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Records a mutation of an entity together with its state before and after the mutation.
+    ///
+    /// Should be called with the transaction of the mutation, so that the event is only kept if the mutation is.
+    pub async fn record<T: Serialize>(
+        db: &impl ConnectionTrait,
+        entity: EventEntity,
+        entity_id: i32,
+        action: EventAction,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<(), AppError> {
+        let before = before
+            .map(serde_json::to_string)
+            .transpose()
+            .context("failed to serialize entity")?;
+        let after = after
+            .map(serde_json::to_string)
+            .transpose()
+            .context("failed to serialize entity")?;
+        let model = events::ActiveModel {
+            entity: Set(entity),
+            entity_id: Set(entity_id),
+            action: Set(action),
+            before: Set(before),
+            after: Set(after),
+            created_at: Set(timestamp::now()),
+            ..Default::default()
+        };
+        model.insert(db).await.context("failed to insert event")?;
+        Ok(())
+    }
+
+    /// Records a new order of a list as a sequence of single moves of the items in `models`,
+    /// so that undoing it restores the old order exactly. `with_ordinal` returns a copy of an item at another position.
+    ///
+    /// Should be called with the transaction that applies the new order.
+    pub async fn record_moves<T: Serialize>(
+        db: &impl ConnectionTrait,
+        entity: EventEntity,
+        models: &HashMap<i32, T>,
+        moves: Vec<OrderMove>,
+        with_ordinal: impl Fn(&T, i32) -> T,
+    ) -> Result<(), AppError> {
+        for order_move in moves {
+            let model = &models[&order_move.id];
+            let before = with_ordinal(model, order_move.from);
+            let after = with_ordinal(model, order_move.to);
+            Self::record(
+                db,
+                entity,
+                order_move.id,
+                EventAction::Move,
+                Some(&before),
+                Some(&after),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
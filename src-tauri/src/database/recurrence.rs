@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    commands::recurrence::{CreateRecurrenceInput, RecurrenceOutput},
+    database::{activity, history},
+    errors::AppError,
+    utils::timestamp,
+};
+use anyhow::Context;
+use entity::{
+    activities, activity_tags, categories, category_tags, columns, recurrence_occurrences,
+    recurrences::{self, Entity as Recurrence},
+    sea_orm_active_enums::{EventAction, EventEntity, RecurrenceKind, SelectionMode},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use time::{Date, Duration, Weekday};
+
+/// Number of days before today that missed occurrences are still generated for.
+const MAX_CATCH_UP_DAYS: i64 = 30;
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+pub struct Query;
+
+impl Query {
+    /// Fetches all recurrence rules associated with their ids.
+    pub async fn all_recurrences(db: &DbConn) -> Result<HashMap<i32, RecurrenceOutput>, DbErr> {
+        let res = Recurrence::find().all(db).await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, recurrence| {
+            acc.insert(
+                recurrence.id,
+                RecurrenceOutput {
+                    template_id: recurrence.template_id,
+                    column_id: recurrence.column_id,
+                    kind: recurrence.kind,
+                    weekdays: weekdays_from_mask(recurrence.weekdays.unwrap_or(0)),
+                    day_of_month: recurrence.day_of_month,
+                    interval_days: recurrence.interval_days,
+                    start_date: recurrence.start_date,
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
+    /// Helper function that fetches the date of the latest occurrence of a given recurrence rule.
+    async fn get_last_occurrence_date(
+        db: &DbConn,
+        recurrence_id: i32,
+    ) -> Result<Option<Date>, AppError> {
+        let res = recurrence_occurrences::Entity::find()
+            .filter(recurrence_occurrences::Column::RecurrenceId.eq(recurrence_id))
+            .order_by_desc(recurrence_occurrences::Column::Date)
+            .one(db)
+            .await
+            .context("failed to select last occurrence")?;
+        Ok(res.map(|occurrence| occurrence.date))
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Creates a recurrence rule and returns it with its newly created id.
+    ///
+    /// Returns `Err(RowNotFound)` if the template activity or the target column does not exist or the column is in the trash,
+    /// `Err(BoardMismatch)` if the column is on another board than the template,
+    /// and `Err(InvalidRecurrence)` if the fields required by the kind of the rule are missing or out of range.
+    pub async fn insert_recurrence(
+        db: &DbConn,
+        data: CreateRecurrenceInput,
+    ) -> Result<recurrences::Model, AppError> {
+        let mut model = recurrences::ActiveModel {
+            template_id: Set(data.template_id),
+            column_id: Set(data.column_id),
+            kind: Set(data.kind),
+            start_date: Set(data.start_date),
+            ..Default::default()
+        };
+        match data.kind {
+            RecurrenceKind::Daily => {}
+            RecurrenceKind::Weekly => {
+                if data.weekdays.is_empty() {
+                    return Err(AppError::InvalidRecurrence);
+                }
+                model.weekdays = Set(Some(weekdays_to_mask(&data.weekdays)));
+            }
+            RecurrenceKind::Monthly => match data.day_of_month {
+                Some(day @ 1..=31) => model.day_of_month = Set(Some(day)),
+                _ => return Err(AppError::InvalidRecurrence),
+            },
+            RecurrenceKind::EveryNDays => match data.interval_days {
+                Some(days @ 1..) => model.interval_days = Set(Some(days)),
+                _ => return Err(AppError::InvalidRecurrence),
+            },
+        }
+
+        let template = activities::Entity::find_by_id(data.template_id)
+            .one(db)
+            .await
+            .context("failed to select template activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let column = columns::Entity::find_by_id(data.column_id)
+            .filter(columns::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select column")?
+            .ok_or(AppError::RowNotFound)?;
+        if column.board_id != template.board_id {
+            return Err(AppError::BoardMismatch);
+        }
+
+        let res = model
+            .insert(db)
+            .await
+            .context("failed to insert recurrence")?;
+        Ok(res)
+    }
+
+    /// Deletes the recurrence rule with a given id. Activities generated from it are kept.
+    ///
+    /// Returns `Err(RowNotFound)` if the rule does not exist.
+    pub async fn delete_recurrence_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
+        let res = Recurrence::delete_by_id(id)
+            .exec(db)
+            .await
+            .context("failed to delete recurrence")?;
+        if res.rows_affected == 0 {
+            return Err(AppError::RowNotFound);
+        }
+        Ok(())
+    }
+
+    /// Generates activities for all occurrences of recurrence rules up to and including `today`.
+    ///
+    /// Every rule continues from the day after its latest occurrence, so occurrences missed while the application
+    /// was closed are generated as well, and no occurrence is generated twice.
+    /// Occurrences more than `MAX_CATCH_UP_DAYS` days before `today` are not generated anymore.
+    /// Each generated activity copies the name, body and tags of the template, is due on the day of its occurrence
+    /// and is put at the top of the target column. Work-in-progress limits are not enforced.
+    /// Rules whose target column is in the trash, or whose template is archived or in a column in the trash,
+    /// are skipped until they are brought back. A rule that fails is logged and skipped as well.
+    ///
+    /// Returns the number of generated activities.
+    pub async fn generate_activities(db: &DbConn, today: Date) -> Result<u64, AppError> {
+        let res = Recurrence::find()
+            .find_also_related(columns::Entity)
+            .all(db)
+            .await
+            .context("failed to select recurrences")?;
+
+        let mut generated = 0;
+        for (recurrence, column) in res {
+            let Some(column) = column.filter(|column| column.deleted_at.is_none()) else {
+                continue;
+            };
+            match Self::generate_rule_activities(db, &recurrence, &column, today).await {
+                Ok(count) => generated += count,
+                Err(error) => warn!(
+                    "Failed to generate activities of recurrence {}: {error}",
+                    recurrence.id
+                ),
+            }
+        }
+        Ok(generated)
+    }
+
+    /// Helper function that generates activities for the occurrences of a single rule up to and including `today`.
+    async fn generate_rule_activities(
+        db: &DbConn,
+        recurrence: &recurrences::Model,
+        column: &columns::Model,
+        today: Date,
+    ) -> Result<u64, AppError> {
+        let template = activities::Entity::find_by_id(recurrence.template_id)
+            .one(db)
+            .await
+            .context("failed to select template activity")?
+            .ok_or(AppError::RowNotFound)?;
+        if template.archived_at.is_some() {
+            return Ok(0);
+        }
+        if let Some(column_id) = template.column_id {
+            let template_column = columns::Entity::find_by_id(column_id)
+                .one(db)
+                .await
+                .context("failed to select template column")?
+                .ok_or(AppError::RowNotFound)?;
+            if template_column.deleted_at.is_some() {
+                return Ok(0);
+            }
+        }
+
+        let catch_up_date = today.saturating_sub(Duration::days(MAX_CATCH_UP_DAYS));
+        let first_date = match Query::get_last_occurrence_date(db, recurrence.id).await? {
+            Some(date) => date.next_day().unwrap_or(date).max(recurrence.start_date),
+            None => recurrence.start_date,
+        };
+        let mut date = first_date.max(catch_up_date);
+        let mut generated = 0;
+        while date <= today {
+            if occurs_on(recurrence, date) {
+                Self::generate_activity(db, recurrence, &template, column, date).await?;
+                generated += 1;
+            }
+            let Some(next_day) = date.next_day() else {
+                break;
+            };
+            date = next_day;
+        }
+        Ok(generated)
+    }
+
+    /// Helper function that creates the activity of a single occurrence together with its occurrence record.
+    async fn generate_activity(
+        db: &DbConn,
+        recurrence: &recurrences::Model,
+        template: &activities::Model,
+        column: &columns::Model,
+        date: Date,
+    ) -> Result<(), AppError> {
+        let tags: Vec<category_tags::Model> = activity_tags::Entity::find()
+            .find_also_related(category_tags::Entity)
+            .filter(activity_tags::Column::ActivityId.eq(template.id))
+            .order_by_asc(category_tags::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select template tags")?
+            .into_iter()
+            .filter_map(|(_, tag)| tag)
+            .filter(|tag| tag.deleted_at.is_none())
+            .collect();
+        let single_category_ids: HashSet<i32> = categories::Entity::find()
+            .filter(categories::Column::Id.is_in(tags.iter().filter_map(|tag| tag.category_id)))
+            .filter(categories::Column::Selection.eq(SelectionMode::Single))
+            .all(db)
+            .await
+            .context("failed to select categories")?
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+        // A template may hold several tags of a category that became single-select later; only the first one is copied.
+        let mut filled_category_ids = HashSet::new();
+        let tag_ids: Vec<i32> = tags
+            .into_iter()
+            .filter(|tag| match tag.category_id {
+                Some(category_id) if single_category_ids.contains(&category_id) => {
+                    filled_category_ids.insert(category_id)
+                }
+                _ => true,
+            })
+            .map(|tag| tag.id)
+            .collect();
+
+        let now = timestamp::now();
+        let model = activities::ActiveModel {
+            name: Set(template.name.clone()),
+            body: Set(template.body.clone()),
+            due_date: Set(Some(date)),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+            column_entered_at: Set(Some(now)),
+            ordinal: Set(0),
+            column_id: Set(Some(column.id)),
+            board_id: Set(column.board_id),
+            ..Default::default()
+        };
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        activity::Mutation::right_shift_ordinals(&tr, 0, Some(column.id), column.board_id).await?;
+        let activity = model
+            .insert(&tr)
+            .await
+            .context("failed to insert activity")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Activity,
+            activity.id,
+            EventAction::Create,
+            None,
+            Some(&activity),
+        )
+        .await?;
+        for tag_id in tag_ids {
+            let model = activity_tags::ActiveModel {
+                activity_id: Set(activity.id),
+                category_tag_id: Set(tag_id),
+            };
+            let link = model
+                .insert(&tr)
+                .await
+                .context("failed to insert activity_tag")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                activity.id,
+                EventAction::AddTag,
+                None,
+                Some(&link),
+            )
+            .await?;
+        }
+        let occurrence = recurrence_occurrences::ActiveModel {
+            recurrence_id: Set(recurrence.id),
+            date: Set(date),
+            activity_id: Set(Some(activity.id)),
+        };
+        occurrence
+            .insert(&tr)
+            .await
+            .context("failed to insert occurrence")?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+}
+
+/// Helper function that checks whether a recurrence rule has an occurrence on a given date.
+fn occurs_on(recurrence: &recurrences::Model, date: Date) -> bool {
+    if date < recurrence.start_date {
+        return false;
+    }
+    match recurrence.kind {
+        RecurrenceKind::Daily => true,
+        RecurrenceKind::Weekly => {
+            let mask = recurrence.weekdays.unwrap_or(0);
+            mask & (1 << date.weekday().number_days_from_monday()) != 0
+        }
+        RecurrenceKind::Monthly => {
+            let day = recurrence.day_of_month.unwrap_or(1);
+            let last_day = i32::from(date.month().length(date.year()));
+            i32::from(date.day()) == day.min(last_day)
+        }
+        RecurrenceKind::EveryNDays => {
+            let interval = i64::from(recurrence.interval_days.unwrap_or(1).max(1));
+            (date - recurrence.start_date).whole_days() % interval == 0
+        }
+    }
+}
+
+/// Helper function that converts weekdays to a bit mask with Monday as the lowest bit.
+fn weekdays_to_mask(weekdays: &[Weekday]) -> i32 {
+    weekdays.iter().fold(0, |mask, weekday| {
+        mask | (1 << weekday.number_days_from_monday())
+    })
+}
+
+/// Helper function that converts a bit mask with Monday as the lowest bit to weekdays.
+fn weekdays_from_mask(mask: i32) -> Vec<Weekday> {
+    WEEKDAYS
+        .into_iter()
+        .filter(|weekday| mask & (1 << weekday.number_days_from_monday()) != 0)
+        .collect()
+}
@@ -0,0 +1,224 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    commands::{
+        fetch::ActivityRelationsOutput,
+        relations::{LinkActivitiesInput, UnlinkActivitiesInput},
+    },
+    errors::AppError,
+};
+use anyhow::Context;
+use entity::{
+    activities,
+    activity_relations::{self, Entity as ActivityRelation},
+    columns,
+    sea_orm_active_enums::RelationKind,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbConn, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, QueryTrait, Set, TransactionTrait,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Fetches the relations of every activity that has any.
+    ///
+    /// Returns the relations associated with activity ids. A `relates to` link is listed on both activities.
+    pub async fn relations_by_activity(
+        db: &DbConn,
+    ) -> Result<HashMap<i32, ActivityRelationsOutput>, DbErr> {
+        let res = ActivityRelation::find().all(db).await?;
+
+        let out = res.into_iter().fold(
+            HashMap::new(),
+            |mut acc: HashMap<i32, ActivityRelationsOutput>, relation| {
+                match relation.kind {
+                    RelationKind::Blocks => {
+                        acc.entry(relation.activity_id)
+                            .or_default()
+                            .blocks
+                            .push(relation.related_activity_id);
+                        acc.entry(relation.related_activity_id)
+                            .or_default()
+                            .blocked_by
+                            .push(relation.activity_id);
+                    }
+                    RelationKind::RelatesTo => {
+                        acc.entry(relation.activity_id)
+                            .or_default()
+                            .related
+                            .push(relation.related_activity_id);
+                        acc.entry(relation.related_activity_id)
+                            .or_default()
+                            .related
+                            .push(relation.activity_id);
+                    }
+                }
+                acc
+            },
+        );
+        Ok(out)
+    }
+
+    /// Checks whether the activity with a given id is blocked by an activity that is not finished yet.
+    ///
+    /// A blocking activity is finished if it is archived or lies in a column marked as done.
+    pub async fn has_unfinished_blockers(db: &DbConn, id: i32) -> Result<bool, AppError> {
+        let res = activities::Entity::find()
+            .filter(
+                activities::Column::Id.in_subquery(
+                    ActivityRelation::find()
+                        .select_only()
+                        .column(activity_relations::Column::ActivityId)
+                        .filter(activity_relations::Column::RelatedActivityId.eq(id))
+                        .filter(activity_relations::Column::Kind.eq(RelationKind::Blocks))
+                        .into_query(),
+                ),
+            )
+            .filter(activities::Column::ArchivedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(activities::Column::ColumnId.is_null())
+                    .add(
+                        activities::Column::ColumnId.not_in_subquery(
+                            columns::Entity::find()
+                                .select_only()
+                                .column(columns::Column::Id)
+                                .filter(columns::Column::IsDone.eq(true))
+                                .into_query(),
+                        ),
+                    ),
+            )
+            .count(db)
+            .await
+            .context("failed to count unfinished blockers")?;
+        Ok(res > 0)
+    }
+
+    /// Helper function that checks whether `to` can be reached from `from` by following `blocks` links.
+    async fn is_blocking_path(
+        db: &impl ConnectionTrait,
+        from: i32,
+        to: i32,
+    ) -> Result<bool, AppError> {
+        let edges: Vec<(i32, i32)> = ActivityRelation::find()
+            .select_only()
+            .column(activity_relations::Column::ActivityId)
+            .column(activity_relations::Column::RelatedActivityId)
+            .filter(activity_relations::Column::Kind.eq(RelationKind::Blocks))
+            .into_tuple()
+            .all(db)
+            .await
+            .context("failed to select blocking relations")?;
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(current) = stack.pop() {
+            if current == to {
+                return Ok(true);
+            }
+            if visited.insert(current) {
+                stack.extend(
+                    edges
+                        .iter()
+                        .filter(|(blocker, _)| *blocker == current)
+                        .map(|(_, blocked)| *blocked),
+                );
+            }
+        }
+        Ok(false)
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Links two activities with a relation of a given kind, replacing their previous link if there was one.
+    ///
+    /// Since a `relates to` link has no direction, it is stored with the smaller activity id first.
+    ///
+    /// Returns `Err(RowNotFound)` if any of the activities does not exist and `Err(RelationCycle)`
+    /// if the activities are the same or the new `blocks` link would close a cycle of blocking activities.
+    pub async fn link_activities(db: &DbConn, data: LinkActivitiesInput) -> Result<(), AppError> {
+        if data.activity_id == data.related_activity_id {
+            return Err(AppError::RelationCycle);
+        }
+        let found = activities::Entity::find()
+            .filter(activities::Column::Id.is_in([data.activity_id, data.related_activity_id]))
+            .count(db)
+            .await
+            .context("failed to select activities")?;
+        if found < 2 {
+            return Err(AppError::RowNotFound);
+        }
+        let (activity_id, related_activity_id) = match data.kind {
+            RelationKind::Blocks => (data.activity_id, data.related_activity_id),
+            RelationKind::RelatesTo => (
+                data.activity_id.min(data.related_activity_id),
+                data.activity_id.max(data.related_activity_id),
+            ),
+        };
+
+        // The previous link is removed first, so that the cycle check sees the links the new one would join.
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::delete_link(&tr, data.activity_id, data.related_activity_id).await?;
+        if data.kind == RelationKind::Blocks
+            && Query::is_blocking_path(&tr, data.related_activity_id, data.activity_id).await?
+        {
+            return Err(AppError::RelationCycle);
+        }
+        let model = activity_relations::ActiveModel {
+            activity_id: Set(activity_id),
+            related_activity_id: Set(related_activity_id),
+            kind: Set(data.kind),
+        };
+        model
+            .insert(&tr)
+            .await
+            .context("failed to insert activity relation")?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Removes the link between two activities, in whichever direction it was made.
+    ///
+    /// Returns `Err(RowNotFound)` if the activities are not linked.
+    pub async fn unlink_activities(
+        db: &DbConn,
+        data: UnlinkActivitiesInput,
+    ) -> Result<(), AppError> {
+        let removed = Self::delete_link(db, data.activity_id, data.related_activity_id).await?;
+        if removed == 0 {
+            return Err(AppError::RowNotFound);
+        }
+        Ok(())
+    }
+
+    /// Helper function that deletes the links between two activities in both directions.
+    ///
+    /// Returns the number of deleted links.
+    async fn delete_link(
+        db: &impl ConnectionTrait,
+        activity_id: i32,
+        related_activity_id: i32,
+    ) -> Result<u64, AppError> {
+        let res =
+            ActivityRelation::delete_many()
+                .filter(
+                    Condition::any()
+                        .add(activity_relations::Column::ActivityId.eq(activity_id).and(
+                            activity_relations::Column::RelatedActivityId.eq(related_activity_id),
+                        ))
+                        .add(
+                            activity_relations::Column::ActivityId
+                                .eq(related_activity_id)
+                                .and(activity_relations::Column::RelatedActivityId.eq(activity_id)),
+                        ),
+                )
+                .exec(db)
+                .await
+                .context("failed to delete activity relation")?;
+        Ok(res.rows_affected)
+    }
+}
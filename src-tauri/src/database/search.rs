@@ -0,0 +1,71 @@
+use crate::{
+    commands::search::{SearchActivitiesInput, SearchHitOutput},
+    utils::search::{to_fts_query, to_highlighted_html, MATCH_END, MATCH_START},
+};
+use sea_orm::{ConnectionTrait, DbConn, DbErr, FromQueryResult, Statement};
+
+/// Number of hits returned by a search unless another limit is given.
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+/// Number of tokens in a snippet of the matched text.
+const SNIPPET_TOKENS: i32 = 16;
+const SNIPPET_ELLIPSIS: &str = "…";
+
+pub struct Query;
+
+impl Query {
+    /// Searches the names, bodies, comments and checklists of activities for the query given in `data`, the best hit first.
+    ///
+    /// Matches in names weigh more than matches in bodies, which weigh more than matches in comments and checklists.
+    /// Archived activities and activities of columns in the trash are skipped.
+    /// If `data.board_id` is `Some`, only the activities of that board are searched.
+    /// Names and snippets are escaped for HTML, with the matched terms wrapped in `<mark>` tags.
+    ///
+    /// Returns no hits if the query has no searchable terms.
+    pub async fn search_activities(
+        db: &DbConn,
+        data: SearchActivitiesInput,
+    ) -> Result<Vec<SearchHitOutput>, DbErr> {
+        let Some(query) = to_fts_query(&data.query) else {
+            return Ok(Vec::new());
+        };
+
+        let statement = Statement::from_sql_and_values(
+            db.get_database_backend(),
+            r#"
+            SELECT
+                activities.id AS activity_id,
+                activities.board_id AS board_id,
+                activities.column_id AS column_id,
+                highlight(activity_search, 0, ?1, ?2) AS name,
+                snippet(activity_search, -1, ?1, ?2, ?3, ?4) AS snippet,
+                bm25(activity_search, 10.0, 5.0, 1.0, 1.0) AS rank
+            FROM activity_search
+            JOIN activities ON activities.id = activity_search.rowid
+            LEFT JOIN columns ON columns.id = activities.column_id
+            WHERE activity_search MATCH ?5
+                AND activities.archived_at IS NULL
+                AND columns.deleted_at IS NULL
+                AND (?6 IS NULL OR activities.board_id = ?6)
+            ORDER BY rank
+            LIMIT ?7
+            "#,
+            [
+                MATCH_START.to_string().into(),
+                MATCH_END.to_string().into(),
+                SNIPPET_ELLIPSIS.into(),
+                SNIPPET_TOKENS.into(),
+                query.into(),
+                data.board_id.into(),
+                data.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).into(),
+            ],
+        );
+        let mut hits = SearchHitOutput::find_by_statement(statement)
+            .all(db)
+            .await?;
+        for hit in hits.iter_mut() {
+            hit.name = to_highlighted_html(&hit.name);
+            hit.snippet = to_highlighted_html(&hit.snippet);
+        }
+        Ok(hits)
+    }
+}
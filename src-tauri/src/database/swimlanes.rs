@@ -0,0 +1,200 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    commands::swimlanes::{MoveActivityToLaneInput, SwimlaneOutput, SwimlanesOutput},
+    database::{activity, history},
+    errors::AppError,
+};
+use anyhow::Context;
+use entity::{
+    activities, activity_tags, categories, category_tags, columns,
+    sea_orm_active_enums::{EventAction, EventEntity},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Fetches the activities of the board that owns the category with a given id,
+    /// grouped into lanes by the tags of that category and into cells by their columns.
+    ///
+    /// Only activities in columns are included; the stash, archived activities and columns in the trash are skipped.
+    /// An activity holding several tags of the category is put into the lane of the first of them in the tag order.
+    /// Every lane contains a cell for every column of the board, even if it is empty.
+    ///
+    /// Returns `Err(RowNotFound)` if the category does not exist or is in the trash.
+    pub async fn swimlanes(db: &DbConn, category_id: i32) -> Result<SwimlanesOutput, AppError> {
+        let category = categories::Entity::find_by_id(category_id)
+            .filter(categories::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select category")?
+            .ok_or(AppError::RowNotFound)?;
+        let tags = category_tags::Entity::find()
+            .filter(category_tags::Column::CategoryId.eq(category.id))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .order_by_asc(category_tags::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select category tags")?;
+        let column_ids: Vec<i32> = columns::Entity::find()
+            .filter(columns::Column::BoardId.eq(category.board_id))
+            .filter(columns::Column::DeletedAt.is_null())
+            .order_by_asc(columns::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select columns")?
+            .into_iter()
+            .map(|column| column.id)
+            .collect();
+        let activities = activities::Entity::find()
+            .filter(activities::Column::ColumnId.is_in(column_ids.clone()))
+            .filter(activities::Column::ArchivedAt.is_null())
+            .order_by_asc(activities::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select activities")?;
+        let tag_ids: Vec<i32> = tags.iter().map(|tag| tag.id).collect();
+        let links: HashSet<(i32, i32)> = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.is_in(tag_ids.clone()))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?
+            .into_iter()
+            .map(|activity_tag| (activity_tag.activity_id, activity_tag.category_tag_id))
+            .collect();
+
+        let empty_cells: HashMap<i32, Vec<i32>> =
+            column_ids.iter().map(|id| (*id, Vec::new())).collect();
+        let mut lanes: Vec<SwimlaneOutput> = tag_ids
+            .iter()
+            .map(|tag_id| Some(*tag_id))
+            .chain([None])
+            .map(|tag_id| SwimlaneOutput {
+                tag_id,
+                cells: empty_cells.clone(),
+            })
+            .collect();
+        for activity in activities {
+            let tag_id = tag_ids
+                .iter()
+                .copied()
+                .find(|tag_id| links.contains(&(activity.id, *tag_id)));
+            let lane = lanes
+                .iter_mut()
+                .find(|lane| lane.tag_id == tag_id)
+                .expect("every tag of the category has a lane");
+            lane.cells
+                .entry(activity.column_id.unwrap())
+                .or_default()
+                .push(activity.id);
+        }
+
+        Ok(SwimlanesOutput {
+            category_id: category.id,
+            board_id: category.board_id,
+            lanes,
+        })
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Moves an activity to the lane of the tag given in `data` by replacing its tag of that category.
+    ///
+    /// A `None` value in `tag_id` moves the activity to the lane without a tag.
+    /// Since the activity is left with at most one tag of the category, single-select categories stay valid.
+    /// Links to tags of the category in the trash are kept, so that restoring such a tag brings them back.
+    ///
+    /// Returns `Err(RowNotFound)` if the activity does not exist, the category is in the trash or on another board,
+    /// or the tag does not belong to the category or is in the trash.
+    /// Returns `Err(MissingRequiredTags)` if the move leaves the activity without a tag of a required category
+    /// while it is in a column requiring triage.
+    pub async fn move_activity_to_lane(
+        db: &DbConn,
+        data: MoveActivityToLaneInput,
+    ) -> Result<(), AppError> {
+        let activity = activities::Entity::find_by_id(data.id)
+            .one(db)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        categories::Entity::find_by_id(data.category_id)
+            .filter(categories::Column::BoardId.eq(activity.board_id))
+            .filter(categories::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select category")?
+            .ok_or(AppError::RowNotFound)?;
+        if let Some(tag_id) = data.tag_id {
+            category_tags::Entity::find_by_id(tag_id)
+                .filter(category_tags::Column::CategoryId.eq(data.category_id))
+                .filter(category_tags::Column::DeletedAt.is_null())
+                .one(db)
+                .await
+                .context("failed to select category tag")?
+                .ok_or(AppError::RowNotFound)?;
+        }
+        let category_tag_ids: Vec<i32> = category_tags::Entity::find()
+            .select_only()
+            .column(category_tags::Column::Id)
+            .filter(category_tags::Column::CategoryId.eq(data.category_id))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(db)
+            .await
+            .context("failed to select category tags")?;
+
+        let removed = activity_tags::Entity::find()
+            .filter(activity_tags::Column::ActivityId.eq(data.id))
+            .filter(activity_tags::Column::CategoryTagId.is_in(category_tag_ids))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for activity_tag in removed.iter() {
+            activity_tags::Entity::delete_many()
+                .filter(activity_tags::Column::ActivityId.eq(data.id))
+                .filter(activity_tags::Column::CategoryTagId.eq(activity_tag.category_tag_id))
+                .exec(&tr)
+                .await
+                .context("failed to delete activity tag")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                data.id,
+                EventAction::RemoveTag,
+                Some(activity_tag),
+                None,
+            )
+            .await?;
+        }
+        if let Some(tag_id) = data.tag_id {
+            let model = activity_tags::ActiveModel {
+                activity_id: Set(data.id),
+                category_tag_id: Set(tag_id),
+            };
+            let res = model
+                .insert(&tr)
+                .await
+                .context("failed to insert activity tag")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                data.id,
+                EventAction::AddTag,
+                None,
+                Some(&res),
+            )
+            .await?;
+        }
+        activity::Mutation::check_triage_complete(&tr, data.id, activity.column_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+}
@@ -1,19 +1,31 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::commands::fetch::OtherTagOutput;
-use crate::commands::tags::{UpdateTagColorInput, UpdateTagOrdinalInput};
-use crate::utils::coloring::{rgb_int_to_string, rgb_string_to_int, string_to_color};
+use crate::commands::tags::{
+    AttachTagToCategoryInput, MergeTagsInput, SetTagOrderInput, UpdateTagColorInput,
+    UpdateTagOrdinalInput,
+};
+use crate::commands::trash::TrashedTagOutput;
+use crate::database::{activity, category, history};
+use crate::utils::coloring::{
+    candidate_colors, furthest_color, rgb_int_to_string, rgb_string_to_int, text_color,
+};
+use crate::utils::{ordering, timestamp};
 use crate::{
     commands::tags::{CreateTagInput, UpdateTagNameInput},
     errors::AppError,
 };
 use anyhow::Context;
 use entity::category_tags::{self, Entity as CategoryTag};
+use entity::sea_orm_active_enums::{EventAction, EventEntity, SelectionMode, TagPalette};
+use entity::{activities, activity_tags, categories};
 use sea_orm::{
     sea_query::SimpleExpr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait,
-    IntoActiveModel, PaginatorTrait, QueryFilter, Set, TransactionTrait, Value,
+    IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set,
+    TransactionTrait, Value,
 };
 use sea_orm::{Condition, DbErr};
+use time::OffsetDateTime;
 
 pub struct Query;
 
@@ -24,6 +36,7 @@ impl Query {
     pub async fn all_other_tags(db: &DbConn) -> Result<HashMap<i32, OtherTagOutput>, DbErr> {
         let res = CategoryTag::find()
             .filter(Condition::any().add(category_tags::Column::CategoryId.is_null()))
+            .filter(category_tags::Column::DeletedAt.is_null())
             .all(db)
             .await?;
 
@@ -34,6 +47,7 @@ impl Query {
                     OtherTagOutput {
                         name: tag.tag_name,
                         color: rgb_int_to_string(tag.color),
+                        text_color: text_color(tag.color).into(),
                         ordinal: tag.ordinal,
                     },
                 );
@@ -42,11 +56,61 @@ impl Query {
         Ok(out)
     }
 
+    /// Fetches all tags in the trash associated with their ids.
+    pub async fn all_trashed_tags(db: &DbConn) -> Result<HashMap<i32, TrashedTagOutput>, DbErr> {
+        let res = CategoryTag::find()
+            .filter(category_tags::Column::DeletedAt.is_not_null())
+            .all(db)
+            .await?;
+
+        let out = res.into_iter().fold(HashMap::new(), |mut acc, tag| {
+            acc.insert(
+                tag.id,
+                TrashedTagOutput {
+                    name: tag.tag_name,
+                    category_id: tag.category_id,
+                    color: rgb_int_to_string(tag.color),
+                    text_color: text_color(tag.color).into(),
+                    deleted_at: tag.deleted_at.unwrap(),
+                },
+            );
+            acc
+        });
+        Ok(out)
+    }
+
+    /// Fetches ids of the tags that should be hidden from activities.
+    ///
+    /// These are the tags in the trash and the tags of categories in the trash.
+    pub async fn trashed_tag_ids(db: &DbConn) -> Result<HashSet<i32>, DbErr> {
+        let res = CategoryTag::find()
+            .filter(
+                Condition::any()
+                    .add(category_tags::Column::DeletedAt.is_not_null())
+                    .add(
+                        category_tags::Column::CategoryId.in_subquery(
+                            categories::Entity::find()
+                                .select_only()
+                                .column(categories::Column::Id)
+                                .filter(categories::Column::DeletedAt.is_not_null())
+                                .into_query(),
+                        ),
+                    ),
+            )
+            .all(db)
+            .await?;
+        Ok(res.into_iter().map(|tag| tag.id).collect())
+    }
+
     /// Helper function to get ordinal of the tag with a given id.
     ///
-    /// Returns `Err(RowNotFound)` if the tag does not exist.
-    async fn get_ordinal_from_id(db: &DbConn, category_tag_id: i32) -> Result<i32, AppError> {
+    /// Returns `Err(RowNotFound)` if the tag does not exist or is in the trash.
+    async fn get_ordinal_from_id(
+        db: &impl ConnectionTrait,
+        category_tag_id: i32,
+    ) -> Result<i32, AppError> {
         let res = category_tags::Entity::find_by_id(category_tag_id)
+            .filter(category_tags::Column::DeletedAt.is_null())
             .one(db)
             .await
             .context("failed to get ordinal from id")?
@@ -57,10 +121,11 @@ impl Query {
     /// Helper function to get the tag amount in a given category.
     ///
     /// If `category_id` is `None`, then returns the non-category tag amount.
+    /// Tags in the trash are not counted.
     ///
     /// Returns 0 if category with a given id does not exist.
     async fn get_tag_count_from_category(
-        db: &DbConn,
+        db: &impl ConnectionTrait,
         category_id: Option<i32>,
     ) -> Result<i32, AppError> {
         let res = category_tags::Entity::find()
@@ -73,16 +138,60 @@ impl Query {
                             .and(SimpleExpr::from(category_id.is_none())),
                     ),
             )
+            .filter(category_tags::Column::DeletedAt.is_null())
             .count(db)
             .await
             .context("failed to determine count of columns")?;
         Ok(res as i32)
     }
 
+    /// Helper function to pick a color for a new tag named `name` in a given category.
+    ///
+    /// The color is chosen from the palette of the category to be perceptually furthest from the colors of the other tags in it.
+    /// Non-category tags use the `Hash` palette.
+    ///
+    /// Returns `Err(RowNotFound)` if category with a given id does not exist.
+    async fn pick_color(
+        db: &DbConn,
+        category_id: Option<i32>,
+        name: &str,
+    ) -> Result<i32, AppError> {
+        let palette = match category_id {
+            Some(category_id) => {
+                categories::Entity::find_by_id(category_id)
+                    .one(db)
+                    .await
+                    .context("failed to select category")?
+                    .ok_or(AppError::RowNotFound)?
+                    .palette
+            }
+            None => TagPalette::Hash,
+        };
+        let taken: Vec<i32> = category_tags::Entity::find()
+            .filter(match category_id {
+                Some(category_id) => category_tags::Column::CategoryId.eq(category_id),
+                None => category_tags::Column::CategoryId.is_null(),
+            })
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .context("failed to select category tags")?
+            .into_iter()
+            .map(|tag| tag.color)
+            .collect();
+
+        let candidates = candidate_colors(palette, name);
+        let color = furthest_color(&candidates, &taken).context("palette has no colors")?;
+        Ok(color)
+    }
+
     /// Helper function used to fetch category id from tag id.
     ///
     /// Returns `Ok(None)` if tag with the given `id` is a non-category tag.
-    async fn get_category_id_from_record_id(db: &DbConn, id: i32) -> Result<Option<i32>, AppError> {
+    async fn get_category_id_from_record_id(
+        db: &impl ConnectionTrait,
+        id: i32,
+    ) -> Result<Option<i32>, AppError> {
         let res = category_tags::Entity::find_by_id(id)
             .one(db)
             .await
@@ -98,40 +207,72 @@ pub struct Mutation;
 impl Mutation {
     /// Creates a new tag and appends it to the end of the list.
     ///
-    /// Returns a tag with the given name, generated color, id and ordinal.
+    /// The color of the tag is generated with the palette of its category, keeping it distinct from the colors of the other tags.
+    ///
+    /// Returns a tag with the given name, generated color, id and ordinal,
+    /// or `Err(TagNameConflict)` if the category already has a tag with the same name, even in the trash.
     pub async fn create_tag(
         db: &DbConn,
         data: CreateTagInput,
     ) -> Result<category_tags::Model, AppError> {
+        Self::check_name_free(db, data.category_id, &data.tag_name, None).await?;
         let tag_count = Query::get_tag_count_from_category(db, data.category_id).await?;
+        let color = Query::pick_color(db, data.category_id, &data.tag_name).await?;
         let tag_model = category_tags::ActiveModel {
-            color: Set(string_to_color(&data.tag_name)),
+            color: Set(color),
             tag_name: Set(data.tag_name),
             category_id: Set(data.category_id),
             ordinal: Set(tag_count),
             ..Default::default()
         };
 
-        let res = tag_model.insert(db).await.context("failed to insert tag")?;
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = tag_model
+            .insert(&tr)
+            .await
+            .context("failed to insert tag")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            res.id,
+            EventAction::Create,
+            None,
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(res)
     }
 
     /// Renames the tag with id given in `data`.
     ///
-    /// Returns `Err(RowNotFound)` if the tag with a given id does not exist.
+    /// Returns `Err(RowNotFound)` if the tag with a given id does not exist
+    /// and `Err(TagNameConflict)` if another tag of its category has the same name, even in the trash.
     pub async fn update_tag_name(db: &DbConn, data: UpdateTagNameInput) -> Result<(), AppError> {
-        let mut tag_model = category_tags::Entity::find_by_id(data.category_tag_id)
+        let before = category_tags::Entity::find_by_id(data.category_tag_id)
             .one(db)
             .await
             .context("failed to get category_tags model")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        Self::check_name_free(db, before.category_id, &data.tag_name, Some(before.id)).await?;
+        let mut tag_model = before.clone().into_active_model();
 
         tag_model.tag_name = Set(data.tag_name);
-        category_tags::Entity::update(tag_model)
-            .exec(db)
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = category_tags::Entity::update(tag_model)
+            .exec(&tr)
             .await
             .context("failed to update category_tags row")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            data.category_tag_id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
@@ -141,24 +282,151 @@ impl Mutation {
     ///
     /// Returns `Err(RowNotFound)` if the tag with a given id does not exist.
     pub async fn update_tag_ordinal(
-        db: &DbConn,
+        db: &(impl ConnectionTrait + TransactionTrait),
         data: UpdateTagOrdinalInput,
     ) -> Result<(), AppError> {
         let category_id = Query::get_category_id_from_record_id(db, data.category_tag_id).await?;
         let old_ord = Query::get_ordinal_from_id(db, data.category_tag_id).await?;
-        let mut model = category_tags::Entity::find_by_id(data.category_tag_id)
+        let before = category_tags::Entity::find_by_id(data.category_tag_id)
             .one(db)
             .await
             .context("failed to select column")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        let mut model = before.clone().into_active_model();
 
         let tr = db.begin().await.context("failed to begin transaction")?;
         Self::left_shift_ordinals(&tr, old_ord, category_id).await?;
         Self::right_shift_ordinals(&tr, data.new_ord, category_id).await?;
 
         model.ordinal = Set(data.new_ord);
-        model.update(&tr).await.context("failed to update column")?;
+        let res = model.update(&tr).await.context("failed to update column")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            data.category_tag_id,
+            EventAction::Move,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Moves the tag with id given in `data` to the end of the tag list of another category,
+    /// or of the non-category tags if `category_id` is `None`.
+    ///
+    /// This also shifts ordinals to close the gap in the list the tag leaves.
+    /// Moving the tag to the category it already belongs to changes nothing.
+    ///
+    /// Returns `Err(RowNotFound)` if the tag or the category does not exist or is in the trash,
+    /// `Err(BoardMismatch)` if the tag belongs to a category of another board or is held by an activity of another board,
+    /// `Err(TagNameConflict)` if the category already has a tag with the same name, even in the trash,
+    /// and `Err(SelectionConflict)` with ids of the activities that would hold more than one tag
+    /// of a single-select category.
+    pub async fn update_tag_category(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        data: AttachTagToCategoryInput,
+    ) -> Result<(), AppError> {
+        let before = CategoryTag::find_by_id(data.category_tag_id)
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to get category_tags model")?
+            .ok_or(AppError::RowNotFound)?;
+        if before.category_id == data.category_id {
+            return Ok(());
+        }
+        let category = match data.category_id {
+            Some(category_id) => Some(
+                categories::Entity::find_by_id(category_id)
+                    .filter(categories::Column::DeletedAt.is_null())
+                    .one(db)
+                    .await
+                    .context("failed to select category")?
+                    .ok_or(AppError::RowNotFound)?,
+            ),
+            None => None,
+        };
+        if let Some(category) = &category {
+            Self::check_same_board(db, &[before.id], category.board_id).await?;
+        }
+        Self::check_name_free(db, data.category_id, &before.tag_name, None).await?;
+        let new_ord = Query::get_tag_count_from_category(db, data.category_id).await?;
+        let mut model = before.clone().into_active_model();
+        model.category_id = Set(data.category_id);
+        model.ordinal = Set(new_ord);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model
+            .update(&tr)
+            .await
+            .context("failed to update category_tags row")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            data.category_tag_id,
+            EventAction::Move,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        Self::left_shift_ordinals(&tr, before.ordinal, before.category_id).await?;
+        if let Some(category) =
+            category.filter(|category| category.selection == SelectionMode::Single)
+        {
+            let conflicts = category::Query::selection_conflicts(&tr, category.id).await?;
+            if !conflicts.is_empty() {
+                return Err(AppError::SelectionConflict {
+                    activity_ids: conflicts
+                        .into_iter()
+                        .map(|(activity_id, _)| activity_id)
+                        .collect(),
+                });
+            }
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Puts the tags of the category with id given in `data`, or the non-category tags if it is `None`, in the order of the ids given in `data`.
+    ///
+    /// Returns `Err(InvalidOrder)` if the ids are not a permutation of the ids of the tags outside the trash.
+    pub async fn set_tag_order(db: &DbConn, data: SetTagOrderInput) -> Result<(), AppError> {
+        let res = CategoryTag::find()
+            .filter(match data.category_id {
+                Some(category_id) => category_tags::Column::CategoryId.eq(category_id),
+                None => category_tags::Column::CategoryId.is_null(),
+            })
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .order_by_asc(category_tags::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select category tags")?;
+        let current: Vec<i32> = res.iter().map(|model| model.id).collect();
+        let moves = ordering::moves_between(&current, &data.ids)?;
+        let models: HashMap<i32, category_tags::Model> =
+            res.into_iter().map(|model| (model.id, model)).collect();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        history::Mutation::record_moves(&tr, EventEntity::Tag, &models, moves, |model, ordinal| {
+            category_tags::Model {
+                ordinal,
+                ..model.clone()
+            }
+        })
+        .await?;
+        for (ordinal, id) in data.ids.iter().enumerate() {
+            let model = &models[id];
+            if model.ordinal != ordinal as i32 {
+                let mut model = model.clone().into_active_model();
+                model.ordinal = Set(ordinal as i32);
+                model
+                    .update(&tr)
+                    .await
+                    .context("failed to update category_tags row")?;
+            }
+        }
         tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
@@ -167,38 +435,405 @@ impl Mutation {
     ///
     /// Returns `Err(RowNotFound)` if tag with id given in `data` does not exist.
     pub async fn update_tag_color(db: &DbConn, data: UpdateTagColorInput) -> Result<(), AppError> {
-        let mut tag_model = category_tags::Entity::find_by_id(data.category_tag_id)
+        let before = category_tags::Entity::find_by_id(data.category_tag_id)
             .one(db)
             .await
             .context("failed to get category_tags model")?
-            .ok_or(AppError::RowNotFound)?
-            .into_active_model();
+            .ok_or(AppError::RowNotFound)?;
+        let mut tag_model = before.clone().into_active_model();
 
         tag_model.color = Set(rgb_string_to_int(&data.color)?);
-        category_tags::Entity::update(tag_model)
-            .exec(db)
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = category_tags::Entity::update(tag_model)
+            .exec(&tr)
             .await
             .context("failed to update category_tags row")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            data.category_tag_id,
+            EventAction::Update,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
-    /// Deletes tag with a given id.
+    /// Moves tag with a given id to the trash.
     ///
+    /// Links between the tag and activities are kept, so that restoring the tag brings them back.
     /// This also shifts ordinals to maintain correct tag order.
-    pub async fn delete_tag_by_id(db: &DbConn, id: i32) -> Result<(), AppError> {
-        let category_id = Query::get_category_id_from_record_id(db, id).await?;
+    ///
+    /// Returns `Err(RowNotFound)` if the tag does not exist or is already in the trash.
+    pub async fn delete_tag_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
         let deleted_ord = Query::get_ordinal_from_id(db, id).await?;
+        let before = category_tags::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .context("failed to get category_tags model")?
+            .ok_or(AppError::RowNotFound)?;
+        let category_id = before.category_id;
+        let mut model = before.clone().into_active_model();
+        model.deleted_at = Set(Some(timestamp::now()));
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let res = model
+            .update(&tr)
+            .await
+            .context("failed to move category_tag to the trash")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            id,
+            EventAction::Delete,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        Self::left_shift_ordinals(&tr, deleted_ord, category_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Restores tag with a given id from the trash.
+    ///
+    /// The tag is put back to the position it had when it was deleted, or to the end of the list
+    /// if its category has fewer tags now.
+    ///
+    /// Returns `Err(RowNotFound)` if there is no tag with this id in the trash.
+    pub async fn restore_tag_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let model = category_tags::Entity::find_by_id(id)
+            .filter(category_tags::Column::DeletedAt.is_not_null())
+            .one(db)
+            .await
+            .context("failed to get category_tags model")?
+            .ok_or(AppError::RowNotFound)?;
+        let tag_count = Query::get_tag_count_from_category(db, model.category_id).await?;
+        let new_ord = model.ordinal.min(tag_count);
+        let category_id = model.category_id;
+        let before = model.clone();
+        let mut model = model.into_active_model();
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord, category_id).await?;
+
+        model.ordinal = Set(new_ord);
+        model.deleted_at = Set(None);
+        let res = model
+            .update(&tr)
+            .await
+            .context("failed to update category_tags row")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            id,
+            EventAction::Restore,
+            Some(&before),
+            Some(&res),
+        )
+        .await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Permanently deletes the tag with a given id, without moving it to the trash first.
+    ///
+    /// Removal of its links to activities is recorded in the history before the deletion itself.
+    /// This also shifts ordinals to maintain correct tag order.
+    /// Used to revert the creation of a tag and to delete tags merged into another one.
+    ///
+    /// Returns `Err(RowNotFound)` if the tag does not exist or is in the trash.
+    pub async fn remove_tag_by_id(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        id: i32,
+    ) -> Result<(), AppError> {
+        let tag = category_tags::Entity::find_by_id(id)
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to get category_tags model")?
+            .ok_or(AppError::RowNotFound)?;
+        let links = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.eq(id))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+
         let tr = db.begin().await.context("failed to begin transaction")?;
+        for link in links.iter() {
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                link.activity_id,
+                EventAction::RemoveTag,
+                Some(link),
+                None,
+            )
+            .await?;
+        }
         category_tags::Entity::delete_by_id(id)
             .exec(&tr)
             .await
             .context("failed to delete category_tag")?;
-        Self::left_shift_ordinals(&tr, deleted_ord, category_id).await?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            id,
+            EventAction::Delete,
+            Some(&tag),
+            None,
+        )
+        .await?;
+        Self::left_shift_ordinals(&tr, tag.ordinal, tag.category_id).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Inserts a previously removed tag with all its fields, including its id.
+    ///
+    /// The tag is put back to the position it had, or to the end of the list if its category has fewer tags now.
+    /// Used to reapply the creation of a tag; its links to activities have to be restored separately.
+    pub async fn reinsert_tag(
+        db: &(impl ConnectionTrait + TransactionTrait),
+        tag: category_tags::Model,
+    ) -> Result<(), AppError> {
+        let tag_count = Query::get_tag_count_from_category(db, tag.category_id).await?;
+        let new_ord = tag.ordinal.min(tag_count);
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        Self::right_shift_ordinals(&tr, new_ord, tag.category_id).await?;
+        let res = category_tags::Model {
+            ordinal: new_ord,
+            ..tag
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(&tr)
+        .await
+        .context("failed to insert tag")?;
+        history::Mutation::record(
+            &tr,
+            EventEntity::Tag,
+            res.id,
+            EventAction::Restore,
+            None,
+            Some(&res),
+        )
+        .await?;
         tr.commit().await.context("failed to commit transaction")?;
         Ok(())
     }
 
+    /// Merges the tags with ids given in `source_ids` into the tag with id `target_id`.
+    ///
+    /// Every activity linked to a source tag is linked to the target tag instead; activities that already have
+    /// the target tag just lose the source tag. If the target tag belongs to a single-select category,
+    /// newly linked activities lose their other tags of that category. The source tags are then deleted
+    /// and the ordinals of the remaining tags in each affected category are shifted.
+    /// The target tag may belong to another category than the source tags. Repeated ids
+    /// and the id of the target tag in `source_ids` are ignored.
+    ///
+    /// Returns the number of activities that lost a source tag, `Err(RowNotFound)`
+    /// if any of the tags does not exist or is in the trash, and `Err(BoardMismatch)` if the target tag
+    /// belongs to a category and a source tag belongs to a category of another board or is held by an activity of another board.
+    pub async fn merge_tags(db: &DbConn, data: MergeTagsInput) -> Result<u64, AppError> {
+        let (_, target_category) = CategoryTag::find_by_id(data.target_id)
+            .find_also_related(categories::Entity)
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .context("failed to select target tag")?
+            .ok_or(AppError::RowNotFound)?;
+        let source_ids: HashSet<i32> = data
+            .source_ids
+            .into_iter()
+            .filter(|id| *id != data.target_id)
+            .collect();
+        let sources = CategoryTag::find()
+            .filter(category_tags::Column::Id.is_in(source_ids.clone()))
+            .filter(category_tags::Column::DeletedAt.is_null())
+            .order_by_asc(category_tags::Column::CategoryId)
+            .order_by_asc(category_tags::Column::Ordinal)
+            .all(db)
+            .await
+            .context("failed to select source tags")?;
+        if sources.len() != source_ids.len() {
+            return Err(AppError::RowNotFound);
+        }
+        let links = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.is_in(source_ids))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?;
+        let mut target_activity_ids: HashSet<i32> = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.eq(data.target_id))
+            .all(db)
+            .await
+            .context("failed to select activity tags")?
+            .into_iter()
+            .map(|link| link.activity_id)
+            .collect();
+        let changed: HashSet<i32> = links.iter().map(|link| link.activity_id).collect();
+        if let Some(category) = target_category {
+            let ids: Vec<i32> = sources.iter().map(|source| source.id).collect();
+            Self::check_same_board(db, &ids, category.board_id).await?;
+        }
+
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        for link in links.iter() {
+            activity_tags::Entity::delete_many()
+                .filter(activity_tags::Column::ActivityId.eq(link.activity_id))
+                .filter(activity_tags::Column::CategoryTagId.eq(link.category_tag_id))
+                .exec(&tr)
+                .await
+                .context("failed to delete activity_tag")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                link.activity_id,
+                EventAction::RemoveTag,
+                Some(link),
+                None,
+            )
+            .await?;
+        }
+        // The target tag is linked once the source links are gone, so that they are not unlinked twice.
+        for link in links.iter() {
+            if !target_activity_ids.insert(link.activity_id) {
+                continue;
+            }
+            activity::Mutation::unlink_exclusive_tags(&tr, link.activity_id, data.target_id)
+                .await?;
+            let model = activity_tags::ActiveModel {
+                activity_id: Set(link.activity_id),
+                category_tag_id: Set(data.target_id),
+            };
+            let res = model
+                .insert(&tr)
+                .await
+                .context("failed to insert activity_tag")?;
+            history::Mutation::record(
+                &tr,
+                EventEntity::Activity,
+                link.activity_id,
+                EventAction::AddTag,
+                None,
+                Some(&res),
+            )
+            .await?;
+        }
+        for source in sources {
+            Self::remove_tag_by_id(&tr, source.id).await?;
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(changed.len() as u64)
+    }
+
+    /// Permanently deletes tags that were moved to the trash before `deleted_before`.
+    ///
+    /// This also deletes links between the purged tags and activities.
+    ///
+    /// Returns the number of purged tags.
+    pub async fn purge_trashed_tags(
+        db: &impl ConnectionTrait,
+        deleted_before: OffsetDateTime,
+    ) -> Result<u64, AppError> {
+        let purged = category_tags::Entity::find()
+            .filter(category_tags::Column::DeletedAt.lt(deleted_before))
+            .all(db)
+            .await
+            .context("failed to select trashed category_tags")?;
+        for tag in purged.iter() {
+            category_tags::Entity::delete_by_id(tag.id)
+                .exec(db)
+                .await
+                .context("failed to purge category_tag")?;
+            history::Mutation::record(
+                db,
+                EventEntity::Tag,
+                tag.id,
+                EventAction::Purge,
+                Some(tag),
+                None,
+            )
+            .await?;
+        }
+        Ok(purged.len() as u64)
+    }
+
+    /// Helper function that refuses to give a tag of the category with id `category_id` the name `tag_name`
+    /// if another tag of the category already has it, including the tags in the trash,
+    /// which keep their names reserved until they are purged.
+    ///
+    /// The tag with id `tag_id` is not counted, so that it can be renamed to its current name.
+    async fn check_name_free(
+        db: &impl ConnectionTrait,
+        category_id: Option<i32>,
+        tag_name: &str,
+        tag_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let conflict = CategoryTag::find()
+            .filter(category_tags::Column::TagName.eq(tag_name))
+            .filter(
+                Condition::any()
+                    .add(category_tags::Column::CategoryId.eq(category_id))
+                    .add(
+                        category_tags::Column::CategoryId
+                            .is_null()
+                            .and(SimpleExpr::from(category_id.is_none())),
+                    ),
+            )
+            .apply_if(tag_id, |query, id| {
+                query.filter(category_tags::Column::Id.ne(id))
+            })
+            .one(db)
+            .await
+            .context("failed to select category tags")?;
+        if conflict.is_some() {
+            return Err(AppError::TagNameConflict);
+        }
+        Ok(())
+    }
+
+    /// Helper function that refuses to put the tags with ids `ids` into a category of the board with id `board_id`
+    /// if any of them belongs to a category of another board or is held by an activity of another board.
+    async fn check_same_board(
+        db: &impl ConnectionTrait,
+        ids: &[i32],
+        board_id: i32,
+    ) -> Result<(), AppError> {
+        let foreign_categories = CategoryTag::find()
+            .inner_join(categories::Entity)
+            .filter(category_tags::Column::Id.is_in(ids.iter().copied()))
+            .filter(categories::Column::BoardId.ne(board_id))
+            .count(db)
+            .await
+            .context("failed to determine count of categories")?;
+        let foreign_activities = activity_tags::Entity::find()
+            .inner_join(activities::Entity)
+            .filter(activity_tags::Column::CategoryTagId.is_in(ids.iter().copied()))
+            .filter(activities::Column::BoardId.ne(board_id))
+            .count(db)
+            .await
+            .context("failed to determine count of activities")?;
+        if foreign_categories > 0 || foreign_activities > 0 {
+            return Err(AppError::BoardMismatch);
+        }
+        Ok(())
+    }
+
     /// Helper function that decrements ordinals greater than `start_ord`.
+    ///
+    /// Tags in the trash keep their ordinals.
     async fn left_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
@@ -215,6 +850,7 @@ impl Mutation {
                             .and(SimpleExpr::from(category_id.is_none())),
                     ),
             )
+            .filter(category_tags::Column::DeletedAt.is_null())
             .col_expr(
                 category_tags::Column::Ordinal,
                 SimpleExpr::from(category_tags::Column::Ordinal.into_expr())
@@ -227,6 +863,8 @@ impl Mutation {
     }
 
     /// Helper function that increments ordinals equal to at least `start_ord`.
+    ///
+    /// Tags in the trash keep their ordinals.
     async fn right_shift_ordinals(
         db: &impl ConnectionTrait,
         start_ord: i32,
@@ -243,6 +881,7 @@ impl Mutation {
                             .and(SimpleExpr::from(category_id.is_none())),
                     ),
             )
+            .filter(category_tags::Column::DeletedAt.is_null())
             .col_expr(
                 category_tags::Column::Ordinal,
                 SimpleExpr::from(category_tags::Column::Ordinal.into_expr())
@@ -0,0 +1,37 @@
+use anyhow::Context;
+use sea_orm::{DbConn, TransactionTrait};
+use time::Duration;
+
+use crate::{
+    commands::trash::PurgeTrashOutput,
+    database::{category, columns, tags},
+    errors::AppError,
+    utils::timestamp,
+};
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Permanently deletes columns, categories and tags that were moved to the trash
+    /// more than `older_than_days` days ago.
+    ///
+    /// Categories are purged before tags, so tags of a purged category are not counted separately.
+    /// Activities of purged columns are moved to the stash of their board.
+    pub async fn purge_trash(
+        db: &DbConn,
+        older_than_days: i64,
+    ) -> Result<PurgeTrashOutput, AppError> {
+        let deleted_before = timestamp::now() - Duration::days(older_than_days);
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        let categories = category::Mutation::purge_trashed_categories(&tr, deleted_before).await?;
+        let tags = tags::Mutation::purge_trashed_tags(&tr, deleted_before).await?;
+        let columns = columns::Mutation::purge_trashed_columns(&tr, deleted_before).await?;
+        tr.commit().await.context("failed to commit transaction")?;
+
+        Ok(PurgeTrashOutput {
+            columns,
+            categories,
+            tags,
+        })
+    }
+}
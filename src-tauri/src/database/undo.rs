@@ -0,0 +1,603 @@
+use std::{collections::HashSet, future::Future};
+
+use crate::{
+    commands::{
+        activity::{UnarchiveActivityInput, UpdateActivityContentInput},
+        boards::{RenameBoardInput, UpdateBoardOrdinalInput},
+        category::UpdateCategoryOrdinalInput,
+        columns::UpdateColumnOrdinalInput,
+        tags::{AttachTagToCategoryInput, UpdateTagOrdinalInput},
+        undo::UndoOutput,
+    },
+    database::{self, activity::ActivitySnapshot, history},
+    errors::AppError,
+};
+use anyhow::Context;
+use entity::{
+    activities, activity_tags, boards, categories, category_tags, columns, events,
+    sea_orm_active_enums::{EventAction, EventEntity},
+};
+use sea_orm::{
+    ActiveModelTrait, DatabaseTransaction, DbConn, EntityTrait, IntoActiveModel, Set,
+    TransactionTrait,
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+/// Maximum number of entries kept in the undo stack; the oldest entries are dropped first.
+const UNDO_LIMIT: usize = 100;
+
+/// Undo and redo stacks of board operations.
+///
+/// Every entry holds the events recorded by a single command, in the order they were recorded.
+/// The events keep the state of the entities before and after the command, which is enough to revert
+/// or reapply it, including ordinals and tag links. Deletions of activities also keep the rows deleted
+/// along with them, see `ActivitySnapshot`, and so do creations of activities once they are reverted.
+#[derive(Default)]
+pub struct UndoStack {
+    entries: Mutex<Entries>,
+}
+
+#[derive(Default)]
+struct Entries {
+    undo: Vec<Vec<events::Model>>,
+    redo: Vec<Vec<events::Model>>,
+}
+
+impl Entries {
+    /// Helper function that pushes an entry to the undo stack and drops the oldest entries over the limit.
+    fn push_undo(&mut self, entry: Vec<events::Model>) {
+        self.undo.push(entry);
+        if self.undo.len() > UNDO_LIMIT {
+            let excess = self.undo.len() - UNDO_LIMIT;
+            self.undo.drain(..excess);
+        }
+    }
+}
+
+impl UndoStack {
+    /// Runs a mutation and pushes the events it recorded to the undo stack as a single entry.
+    ///
+    /// The redo stack is cleared if the mutation recorded any events. Events are pushed even if the mutation
+    /// fails after some of its transactions were committed, so that those can be undone as well.
+    pub async fn record<T>(
+        &self,
+        db: &DbConn,
+        mutation: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<T, AppError> {
+        let mut entries = self.entries.lock().await;
+        let last_id = history::Query::last_event_id(db)
+            .await
+            .context("failed to select last event")?;
+        let res = mutation.await;
+        let events = history::Query::events_after(db, last_id)
+            .await
+            .context("failed to select recorded events")?;
+        if !events.is_empty() {
+            entries.push_undo(events);
+            entries.redo.clear();
+        }
+        res
+    }
+
+    /// Runs a mutation without recording it, e.g. one that is not triggered by the user.
+    ///
+    /// No other mutation is recorded while it runs, so its events never end up in an entry.
+    pub async fn skip<T>(
+        &self,
+        mutation: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<T, AppError> {
+        let _entries = self.entries.lock().await;
+        mutation.await
+    }
+
+    /// Collects the content hashes of the attachments that reverting or reapplying an entry of either stack inserts again.
+    ///
+    /// Their files have to be kept in the attachment store even though no attachment refers to them.
+    pub async fn attachment_hashes(&self) -> HashSet<String> {
+        let entries = self.entries.lock().await;
+        entries
+            .undo
+            .iter()
+            .chain(entries.redo.iter())
+            .flatten()
+            .filter(|event| event.entity == EventEntity::Activity)
+            .filter_map(|event| match event.action {
+                EventAction::Delete => parse::<ActivitySnapshot>(event.before.as_deref()).ok()?,
+                EventAction::Create => parse::<ActivitySnapshot>(event.after.as_deref()).ok()?,
+                _ => None,
+            })
+            .flat_map(|snapshot| snapshot.attachments)
+            .map(|attachment| attachment.content_hash)
+            .collect()
+    }
+
+    /// Runs a mutation that cannot be undone and clears both stacks,
+    /// since their entries may refer to entities that the mutation removes permanently.
+    pub async fn discard<T>(
+        &self,
+        mutation: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<T, AppError> {
+        let mut entries = self.entries.lock().await;
+        let res = mutation.await;
+        entries.undo.clear();
+        entries.redo.clear();
+        res
+    }
+}
+
+pub struct Query;
+
+impl Query {
+    /// Fetches the current state of all entities referred to by the events of an entry.
+    async fn affected_entities(
+        db: &DbConn,
+        entry: &[events::Model],
+    ) -> Result<UndoOutput, AppError> {
+        let ids = |entity: EventEntity| -> HashSet<i32> {
+            entry
+                .iter()
+                .filter(|event| event.entity == entity)
+                .map(|event| event.entity_id)
+                .collect()
+        };
+
+        let mut out = UndoOutput::default();
+        for id in ids(EventEntity::Activity) {
+            let model = activities::Entity::find_by_id(id)
+                .one(db)
+                .await
+                .context("failed to select activity")?;
+            out.activities.insert(id, model);
+        }
+        for id in ids(EventEntity::Column) {
+            let model = columns::Entity::find_by_id(id)
+                .one(db)
+                .await
+                .context("failed to select column")?;
+            out.columns.insert(id, model);
+        }
+        for id in ids(EventEntity::Category) {
+            let model = categories::Entity::find_by_id(id)
+                .one(db)
+                .await
+                .context("failed to select category")?;
+            out.categories.insert(id, model);
+        }
+        for id in ids(EventEntity::Tag) {
+            let model = category_tags::Entity::find_by_id(id)
+                .one(db)
+                .await
+                .context("failed to select category tag")?;
+            out.tags.insert(id, model);
+        }
+        for id in ids(EventEntity::Board) {
+            let model = boards::Entity::find_by_id(id)
+                .one(db)
+                .await
+                .context("failed to select board")?;
+            out.boards.insert(id, model);
+        }
+        Ok(out)
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Reverts the latest entry of the undo stack and moves it to the redo stack.
+    ///
+    /// Returns `Ok(None)` if there is nothing to undo, otherwise the new state of the affected entities.
+    /// If the entry cannot be reverted, nothing is changed, the entry is kept on the stack and the error is returned.
+    pub async fn undo(db: &DbConn, stack: &UndoStack) -> Result<Option<UndoOutput>, AppError> {
+        let mut entries = stack.entries.lock().await;
+        let Some(mut entry) = entries.undo.pop() else {
+            return Ok(None);
+        };
+        if let Err(err) = Self::apply_entry(db, &mut entry, true).await {
+            entries.undo.push(entry);
+            return Err(err);
+        }
+        let out = Query::affected_entities(db, &entry).await?;
+        entries.redo.push(entry);
+        Ok(Some(out))
+    }
+
+    /// Reapplies the latest entry of the redo stack and moves it back to the undo stack.
+    ///
+    /// Returns `Ok(None)` if there is nothing to redo, otherwise the new state of the affected entities.
+    /// If the entry cannot be reapplied, nothing is changed, the entry is kept on the stack and the error is returned.
+    pub async fn redo(db: &DbConn, stack: &UndoStack) -> Result<Option<UndoOutput>, AppError> {
+        let mut entries = stack.entries.lock().await;
+        let Some(mut entry) = entries.redo.pop() else {
+            return Ok(None);
+        };
+        if let Err(err) = Self::apply_entry(db, &mut entry, false).await {
+            entries.redo.push(entry);
+            return Err(err);
+        }
+        let out = Query::affected_entities(db, &entry).await?;
+        entries.push_undo(entry);
+        Ok(Some(out))
+    }
+
+    /// Helper function that applies all events of an entry in a single transaction,
+    /// bringing the entities to the state before the events if `revert` is set, and to the state after them otherwise.
+    ///
+    /// Entities are brought back to states they already had, so the checks of the target columns,
+    /// like work-in-progress limits, blockers and triage, are skipped.
+    ///
+    /// Reverting the creation of an activity deletes everything added to it since, so the state after the creation
+    /// is replaced with a snapshot of the activity taken right before it is deleted, see `ActivitySnapshot`.
+    async fn apply_entry(
+        db: &DbConn,
+        entry: &mut [events::Model],
+        revert: bool,
+    ) -> Result<(), AppError> {
+        let tr = db.begin().await.context("failed to begin transaction")?;
+        if revert {
+            for event in entry.iter_mut().rev() {
+                if event.entity == EventEntity::Activity && event.action == EventAction::Create {
+                    event.after = Some(Self::activity_snapshot(&tr, event.entity_id).await?);
+                }
+                Self::apply(&tr, event, event.before.as_deref(), event.after.as_deref()).await?;
+            }
+        } else {
+            for event in entry.iter() {
+                Self::apply(&tr, event, event.after.as_deref(), event.before.as_deref()).await?;
+            }
+        }
+        tr.commit().await.context("failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Helper function that serializes the current state of the activity with id `id`, along with the rows
+    /// that would be deleted with it.
+    ///
+    /// Returns `Err(RowNotFound)` if the activity does not exist.
+    async fn activity_snapshot(tr: &DatabaseTransaction, id: i32) -> Result<String, AppError> {
+        let activity = database::activity::Query::find_activity_by_id(tr, id)
+            .await
+            .context("failed to select activity")?
+            .ok_or(AppError::RowNotFound)?;
+        let snapshot = database::activity::Mutation::snapshot(tr, activity).await?;
+        let res = serde_json::to_string(&snapshot).context("failed to serialize activity")?;
+        Ok(res)
+    }
+
+    /// Helper function that brings the entity of an event to the `target` state,
+    /// which is the state either before or after the event; `other` is the opposite one.
+    async fn apply(
+        tr: &DatabaseTransaction,
+        event: &events::Model,
+        target: Option<&str>,
+        other: Option<&str>,
+    ) -> Result<(), AppError> {
+        let id = event.entity_id;
+        match event.entity {
+            EventEntity::Activity => {
+                Self::apply_activity(tr, id, event.action, target, other).await
+            }
+            EventEntity::Column => Self::apply_column(tr, id, event.action, target).await,
+            EventEntity::Category => Self::apply_category(tr, id, event.action, target).await,
+            EventEntity::Tag => Self::apply_tag(tr, id, event.action, target).await,
+            EventEntity::Board => Self::apply_board(tr, id, event.action, target).await,
+        }
+    }
+
+    /// Helper function that applies an event of the activity with id `id`.
+    async fn apply_activity(
+        tr: &DatabaseTransaction,
+        id: i32,
+        action: EventAction,
+        target: Option<&str>,
+        other: Option<&str>,
+    ) -> Result<(), AppError> {
+        use database::activity::Mutation;
+
+        match action {
+            EventAction::Create | EventAction::Delete | EventAction::Restore => {
+                match parse::<ActivitySnapshot>(target)? {
+                    Some(snapshot) => Mutation::reinsert_activity(tr, snapshot).await,
+                    None => Mutation::delete_activity_by_id(tr, id).await,
+                }
+            }
+            EventAction::Update => {
+                let model: activities::Model = snapshot(target)?;
+                let data = UpdateActivityContentInput {
+                    id,
+                    name: model.name,
+                    body: model.body,
+                    start_date: model.start_date,
+                    due_date: model.due_date,
+                };
+                Mutation::update_activity_content_by_id(tr, data).await
+            }
+            EventAction::Move => {
+                let model: activities::Model = snapshot(target)?;
+                Mutation::move_activity_unchecked(tr, id, model.column_id, model.ordinal).await
+            }
+            EventAction::Archive | EventAction::Unarchive => {
+                let model: activities::Model = snapshot(target)?;
+                if model.archived_at.is_some() {
+                    return Mutation::archive_activity_by_id(tr, id).await;
+                }
+                let data = UnarchiveActivityInput {
+                    id,
+                    column_id: model.column_id,
+                    new_ord: model.ordinal,
+                };
+                Mutation::unarchive_activity_unchecked(tr, data).await
+            }
+            EventAction::AddTag | EventAction::RemoveTag => {
+                match parse::<activity_tags::Model>(target)? {
+                    Some(link) => Mutation::link_tag(tr, id, link.category_tag_id).await,
+                    None => {
+                        let link: activity_tags::Model = snapshot(other)?;
+                        Mutation::unlink_tag(tr, id, link.category_tag_id).await
+                    }
+                }
+            }
+            EventAction::Purge => Err(AppError::NotUndoable),
+        }
+    }
+
+    /// Helper function that applies an event of the column with id `id`.
+    ///
+    /// A column that did not exist in the `target` state is deleted permanently, and one that no longer exists is inserted again.
+    async fn apply_column(
+        tr: &DatabaseTransaction,
+        id: i32,
+        action: EventAction,
+        target: Option<&str>,
+    ) -> Result<(), AppError> {
+        use database::columns::Mutation;
+
+        match action {
+            EventAction::Create | EventAction::Delete | EventAction::Restore => {
+                let Some(target) = parse::<columns::Model>(target)? else {
+                    return Mutation::remove_column_by_id(tr, id).await;
+                };
+                let current = columns::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select column")?;
+                match current {
+                    None => Mutation::reinsert_column(tr, target).await,
+                    Some(_) if target.deleted_at.is_some() => {
+                        Mutation::delete_column_by_id(tr, id).await
+                    }
+                    Some(_) => Mutation::restore_column_by_id(tr, id).await,
+                }
+            }
+            EventAction::Update => {
+                let target: columns::Model = snapshot(target)?;
+                let before = columns::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select column")?
+                    .ok_or(AppError::RowNotFound)?;
+                let mut model = before.clone().into_active_model();
+                model.name = Set(target.name);
+                model.is_done = Set(target.is_done);
+                model.wip_limit = Set(target.wip_limit);
+                model.requires_triage = Set(target.requires_triage);
+
+                let res = model.update(tr).await.context("failed to update column")?;
+                history::Mutation::record(
+                    tr,
+                    EventEntity::Column,
+                    id,
+                    EventAction::Update,
+                    Some(&before),
+                    Some(&res),
+                )
+                .await
+            }
+            EventAction::Move => {
+                let target: columns::Model = snapshot(target)?;
+                let data = UpdateColumnOrdinalInput {
+                    column_id: id,
+                    new_ord: target.ordinal,
+                };
+                Mutation::update_column_ordinal(tr, data).await
+            }
+            _ => Err(AppError::NotUndoable),
+        }
+    }
+
+    /// Helper function that applies an event of the category with id `id`.
+    ///
+    /// A category that did not exist in the `target` state is deleted permanently, and one that no longer exists is inserted again.
+    async fn apply_category(
+        tr: &DatabaseTransaction,
+        id: i32,
+        action: EventAction,
+        target: Option<&str>,
+    ) -> Result<(), AppError> {
+        use database::category::Mutation;
+
+        match action {
+            EventAction::Create | EventAction::Delete | EventAction::Restore => {
+                let Some(target) = parse::<categories::Model>(target)? else {
+                    return Mutation::remove_category_by_id(tr, id).await;
+                };
+                let current = categories::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select category")?;
+                match current {
+                    None => Mutation::reinsert_category(tr, target).await,
+                    Some(_) if target.deleted_at.is_some() => {
+                        Mutation::delete_category_by_id(tr, id).await
+                    }
+                    Some(_) => Mutation::restore_category_by_id(tr, id).await,
+                }
+            }
+            EventAction::Update => {
+                let target: categories::Model = snapshot(target)?;
+                let before = categories::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select category")?
+                    .ok_or(AppError::RowNotFound)?;
+                let mut model = before.clone().into_active_model();
+                model.name = Set(target.name);
+                model.palette = Set(target.palette);
+                model.selection = Set(target.selection);
+                model.is_required = Set(target.is_required);
+
+                let res = model
+                    .update(tr)
+                    .await
+                    .context("failed to update category")?;
+                history::Mutation::record(
+                    tr,
+                    EventEntity::Category,
+                    id,
+                    EventAction::Update,
+                    Some(&before),
+                    Some(&res),
+                )
+                .await
+            }
+            EventAction::Move => {
+                let target: categories::Model = snapshot(target)?;
+                let data = UpdateCategoryOrdinalInput {
+                    category_id: id,
+                    new_ord: target.ordinal,
+                };
+                Mutation::update_category_ordinal(tr, data).await
+            }
+            _ => Err(AppError::NotUndoable),
+        }
+    }
+
+    /// Helper function that applies an event of the tag with id `id`.
+    ///
+    /// A tag that did not exist in the `target` state is deleted permanently, and one that no longer exists is inserted again.
+    async fn apply_tag(
+        tr: &DatabaseTransaction,
+        id: i32,
+        action: EventAction,
+        target: Option<&str>,
+    ) -> Result<(), AppError> {
+        use database::tags::Mutation;
+
+        match action {
+            EventAction::Create | EventAction::Delete | EventAction::Restore => {
+                let Some(target) = parse::<category_tags::Model>(target)? else {
+                    return Mutation::remove_tag_by_id(tr, id).await;
+                };
+                let current = category_tags::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select category tag")?;
+                match current {
+                    None => Mutation::reinsert_tag(tr, target).await,
+                    Some(_) if target.deleted_at.is_some() => {
+                        Mutation::delete_tag_by_id(tr, id).await
+                    }
+                    Some(_) => Mutation::restore_tag_by_id(tr, id).await,
+                }
+            }
+            EventAction::Update => {
+                let target: category_tags::Model = snapshot(target)?;
+                let before = category_tags::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select category tag")?
+                    .ok_or(AppError::RowNotFound)?;
+                let mut model = before.clone().into_active_model();
+                model.tag_name = Set(target.tag_name);
+                model.color = Set(target.color);
+
+                let res = model
+                    .update(tr)
+                    .await
+                    .context("failed to update category tag")?;
+                history::Mutation::record(
+                    tr,
+                    EventEntity::Tag,
+                    id,
+                    EventAction::Update,
+                    Some(&before),
+                    Some(&res),
+                )
+                .await
+            }
+            EventAction::Move => {
+                let target: category_tags::Model = snapshot(target)?;
+                let current = category_tags::Entity::find_by_id(id)
+                    .one(tr)
+                    .await
+                    .context("failed to select category tag")?
+                    .ok_or(AppError::RowNotFound)?;
+                if current.category_id != target.category_id {
+                    let data = AttachTagToCategoryInput {
+                        category_tag_id: id,
+                        category_id: target.category_id,
+                    };
+                    Mutation::update_tag_category(tr, data).await?;
+                }
+                let data = UpdateTagOrdinalInput {
+                    category_tag_id: id,
+                    new_ord: target.ordinal,
+                };
+                Mutation::update_tag_ordinal(tr, data).await
+            }
+            _ => Err(AppError::NotUndoable),
+        }
+    }
+
+    /// Helper function that applies an event of the board with id `id`.
+    async fn apply_board(
+        tr: &DatabaseTransaction,
+        id: i32,
+        action: EventAction,
+        target: Option<&str>,
+    ) -> Result<(), AppError> {
+        use database::boards::Mutation;
+
+        match action {
+            EventAction::Create | EventAction::Delete | EventAction::Restore => {
+                match parse::<boards::Model>(target)? {
+                    Some(model) => Mutation::reinsert_board(tr, model).await,
+                    None => Mutation::delete_board_by_id(tr, id).await,
+                }
+            }
+            EventAction::Update => {
+                let target: boards::Model = snapshot(target)?;
+                let data = RenameBoardInput {
+                    id,
+                    new_name: target.name,
+                };
+                Mutation::update_board_name(tr, data).await
+            }
+            EventAction::Move => {
+                let target: boards::Model = snapshot(target)?;
+                let data = UpdateBoardOrdinalInput {
+                    board_id: id,
+                    new_ord: target.ordinal,
+                };
+                Mutation::update_board_ordinal(tr, data).await
+            }
+            _ => Err(AppError::NotUndoable),
+        }
+    }
+}
+
+/// Helper function that deserializes the state of an entity stored in an event.
+fn parse<T: DeserializeOwned>(json: Option<&str>) -> Result<Option<T>, AppError> {
+    let res = json
+        .map(serde_json::from_str)
+        .transpose()
+        .context("failed to deserialize entity")?;
+    Ok(res)
+}
+
+/// Helper function that deserializes the state of an entity stored in an event, which must be present.
+fn snapshot<T: DeserializeOwned>(json: Option<&str>) -> Result<T, AppError> {
+    let res = parse(json)?.context("event is missing the state of the entity")?;
+    Ok(res)
+}
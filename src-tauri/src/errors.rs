@@ -3,10 +3,38 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum AppError {
-    #[error("The rgb string is invalid")]
+    #[error("The color string is invalid")]
     InvalidColor,
     #[error("The item with a specified id is not found")]
     RowNotFound,
+    #[error("The activity is archived")]
+    ActivityArchived,
+    #[error("The start date is later than the due date")]
+    InvalidDateRange,
+    #[error("The link would create a cycle of blocking activities")]
+    RelationCycle,
+    #[error("The activity is blocked by unfinished activities")]
+    ActivityBlocked,
+    #[error("The value does not match the kind of the custom field")]
+    InvalidFieldValue,
+    #[error("The column {column_id} already holds its limit of {limit} activities")]
+    WipLimitExceeded { column_id: i32, limit: i32 },
+    #[error("The work-in-progress limit must not be negative")]
+    InvalidWipLimit,
+    #[error("The recurrence rule is missing a field required by its kind")]
+    InvalidRecurrence,
+    #[error("The ids do not match the existing ids")]
+    InvalidOrder,
+    #[error("The tags or categories belong to different boards")]
+    BoardMismatch,
+    #[error("The category already has a tag with this name, possibly in the trash")]
+    TagNameConflict,
+    #[error("The activities {activity_ids:?} hold more than one tag of the category")]
+    SelectionConflict { activity_ids: Vec<i32> },
+    #[error("The activity is missing tags of the required categories {category_ids:?}")]
+    MissingRequiredTags { category_ids: Vec<i32> },
+    #[error("The operation cannot be undone")]
+    NotUndoable,
     #[error(transparent)]
     Unexpected(anyhow::Error),
 }
@@ -4,7 +4,11 @@
 #[macro_use]
 extern crate tracing;
 
-use crate::commands::{activity::*, category::*, columns::*, fetch::*, splashscreen::*, tags::*};
+use crate::commands::{
+    activity::*, attachments::*, boards::*, bulk::*, category::*, checklist::*, columns::*,
+    comments::*, custom_fields::*, fetch::*, history::*, recurrence::*, relations::*, search::*,
+    splashscreen::*, swimlanes::*, tags::*, trash::*, undo::*,
+};
 use tauri::Manager;
 
 pub mod commands;
@@ -20,31 +24,101 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
-            app.manage(setup::get_database_pool(&app));
+            app.manage(setup::get_database_pool(app));
+            app.manage(setup::get_attachment_store(app));
+            app.manage(database::undo::UndoStack::default());
+            setup::spawn_recurrence_generator(app);
             info!("Spinning up banban");
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             create_activity,
+            duplicate_activity,
             delete_activity,
+            archive_activity,
+            unarchive_activity,
+            list_archived_activities,
+            list_overdue_activities,
+            list_activities_due_today,
+            list_activities_due_within,
             update_activity_content,
             update_activity_column,
             add_tag_to_activity,
             remove_tag_from_activity,
+            bulk_move_activities,
+            bulk_add_tag,
+            bulk_remove_tag,
+            bulk_archive_activities,
+            bulk_delete_activities,
+            get_activity_history,
+            search_activities,
+            fetch_swimlanes,
+            move_activity_to_lane,
+            create_checklist_item,
+            fetch_checklist,
+            update_checklist_item_text,
+            update_checklist_item_done,
+            update_checklist_item_ordinal,
+            delete_checklist_item,
+            link_activities,
+            unlink_activities,
+            create_recurrence,
+            delete_recurrence,
+            list_recurrences,
+            add_comment,
+            edit_comment,
+            delete_comment,
+            list_comments,
+            add_attachment,
+            delete_attachment,
+            list_attachments,
+            create_custom_field,
+            rename_custom_field,
+            delete_custom_field,
+            add_custom_field_option,
+            delete_custom_field_option,
+            set_activity_field_value,
             create_category,
             delete_category,
+            restore_category,
             create_column,
             rename_column,
+            update_column_done,
+            update_column_triage,
+            update_column_wip_limit,
             delete_column,
+            restore_column,
             update_column_ordinal,
+            set_column_order,
             update_category_name,
+            update_category_ordinal,
+            update_category_palette,
+            update_category_selection,
+            update_category_required,
+            list_activities_missing_required_tags,
+            recolor_category,
+            set_category_order,
             create_tag,
             update_tag_name,
             update_tag_ordinal,
+            attach_tag_to_category,
+            set_tag_order,
             update_tag_color,
+            merge_tags,
             delete_tag,
+            restore_tag,
+            list_trash,
+            purge_trash,
+            undo,
+            redo,
+            create_board,
+            fetch_boards,
+            rename_board,
+            update_board_ordinal,
+            delete_board,
             close_splashscreen,
-            fetch_all
+            fetch_all,
+            fetch_board
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
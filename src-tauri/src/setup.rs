@@ -1,15 +1,19 @@
+use crate::database::{recurrence, undo::UndoStack};
+use crate::utils::{file_store::FileStore, timestamp};
 use sea_orm::{DatabaseConnection, SqlxSqliteConnector};
 use sqlx::migrate::Migrator;
 use sqlx::SqlitePool;
+use std::time::Duration;
 #[cfg(not(dev))]
 use tauri::path::PathResolver;
-use tauri::App;
-#[cfg(not(dev))]
-use tauri::Manager;
+use tauri::{App, Manager};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 
 const DATABASE_FILE_NAME: &str = "database.sqlite3";
+const ATTACHMENTS_DIR_NAME: &str = "attachments";
+const RECURRENCE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 pub fn tracing() {
     let fmt_layer = fmt::layer()
         .without_time()
@@ -27,6 +31,29 @@ pub fn tracing() {
         .init();
 }
 
+/// Generates recurring activities now and then every hour while the application is running.
+///
+/// Must be called after the database connection and the undo stack are managed by the app.
+/// Generated activities are not recorded in the undo stack.
+/// Occurrences are generated up to the current UTC date.
+pub fn spawn_recurrence_generator(app: &App) {
+    let app = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        let db = app.state::<DatabaseConnection>();
+        let stack = app.state::<UndoStack>();
+        loop {
+            let today = timestamp::now().date();
+            let generated = recurrence::Mutation::generate_activities(db.inner(), today);
+            match stack.skip(generated).await {
+                Ok(0) => {}
+                Ok(count) => info!("Generated {count} recurring activities"),
+                Err(error) => warn!("Failed to generate recurring activities: {error}"),
+            }
+            tokio::time::sleep(RECURRENCE_INTERVAL).await;
+        }
+    });
+}
+
 static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
 
 #[cfg(dev)]
@@ -46,20 +73,33 @@ pub fn get_database_pool(_app: &App) -> DatabaseConnection {
     })
 }
 
+#[cfg(dev)]
+pub fn get_attachment_store(_app: &App) -> FileStore {
+    FileStore::new(format!("../{ATTACHMENTS_DIR_NAME}"))
+}
+
 #[cfg(not(dev))]
-pub fn get_database_pool(app: &App) -> DatabaseConnection {
-    use tauri::Manager;
+fn get_app_data_dir(app: &App) -> std::path::PathBuf {
+    let app_data_dir = app
+        .path()
+        .resolve(".", tauri::path::BaseDirectory::AppData)
+        .unwrap();
 
-    tauri::async_runtime::block_on(async {
-        let app_data_dir = app
-            .path()
-            .resolve(".", tauri::path::BaseDirectory::AppData)
-            .unwrap();
+    // Tauri path resolver returns the UNC path on Windows, starting with "\\?\".
+    // This path format is generally not supported.
+    // See issue https://github.com/tauri-apps/tauri/issues/5850.
+    dunce::simplified(&app_data_dir).to_path_buf()
+}
 
-        // Tauri path resolver returns the UNC path on Windows, starting with "\\?\".
-        // This path format is generally not supported.
-        // See issue https://github.com/tauri-apps/tauri/issues/5850.
-        let app_data_dir = dunce::simplified(&app_data_dir);
+#[cfg(not(dev))]
+pub fn get_attachment_store(app: &App) -> FileStore {
+    FileStore::new(get_app_data_dir(app).join(ATTACHMENTS_DIR_NAME))
+}
+
+#[cfg(not(dev))]
+pub fn get_database_pool(app: &App) -> DatabaseConnection {
+    tauri::async_runtime::block_on(async {
+        let app_data_dir = get_app_data_dir(app);
         let file_path = app_data_dir.join(DATABASE_FILE_NAME);
         trace!("App data dir: {app_data_dir:?}, database file path: {file_path:?}");
 
@@ -1 +1,5 @@
 pub mod coloring;
+pub mod file_store;
+pub mod ordering;
+pub mod search;
+pub mod timestamp;
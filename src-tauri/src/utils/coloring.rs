@@ -1,8 +1,26 @@
 use crate::errors::AppError;
+use entity::sea_orm_active_enums::TagPalette;
 
 const PRIME_MUL: i64 = 2147462143;
 const PRIME_MOD: i64 = 998244353;
 
+/// Text color used on backgrounds on which it has a higher contrast than white.
+pub const DARK_TEXT_COLOR: &str = "#000000";
+/// Text color used on backgrounds on which it has a higher contrast than black.
+pub const LIGHT_TEXT_COLOR: &str = "#FFFFFF";
+
+/// Colors of the `Fixed` palette, chosen to be easy to tell apart.
+pub const FIXED_PALETTE: [i32; 12] = [
+    0xE6194B, 0x3CB44B, 0xFFE119, 0x4363D8, 0xF58231, 0x911EB4, 0x42D4F4, 0xF032E6, 0xBFEF45,
+    0xFABED4, 0x469990, 0x9A6324,
+];
+/// Number of hues the `Spaced` palette chooses from when a single tag is created.
+const SPACED_HUE_COUNT: usize = 36;
+/// Number of variations of the tag name hashed by the `Hash` palette.
+const HASH_VARIANT_COUNT: usize = 16;
+const SPACED_SATURATION: f64 = 0.65;
+const SPACED_LIGHTNESS: f64 = 0.5;
+
 pub fn string_to_color(input: &str) -> i32 {
     let hash = input.chars().fold(0i64, |hash, char| {
         (char as i64 * PRIME_MUL + ((hash << 8) - hash)) % PRIME_MOD
@@ -12,18 +30,214 @@ pub fn string_to_color(input: &str) -> i32 {
 }
 
 pub fn int_to_rgb(input: i32) -> (i32, i32, i32) {
-    (input & 0xff0000, input & 0xff00, input & 0xff)
+    ((input >> 16) & 0xff, (input >> 8) & 0xff, input & 0xff)
+}
+
+pub fn rgb_to_int(r: i32, g: i32, b: i32) -> i32 {
+    (r << 16) | (g << 8) | b
 }
 
+/// Parses a CSS color into its integer representation.
+///
+/// Accepts `#rgb`, `#rrggbb`, bare `rrggbb`, `rgb(r, g, b)` and `hsl(h, s%, l%)` notations.
+/// Functional notations may separate their arguments with commas or spaces.
+///
+/// Returns `Err(InvalidColor)` if the string is not one of these notations or a value is out of range.
 pub fn rgb_string_to_int(input: &str) -> Result<i32, AppError> {
-    i32::from_str_radix(input, 16).map_err(|_| AppError::InvalidColor)
+    let input = input.trim().to_ascii_lowercase();
+
+    if let Some(args) = function_args(&input, "rgb") {
+        let [r, g, b] = args;
+        return Ok(rgb_to_int(
+            parse_channel(r)?,
+            parse_channel(g)?,
+            parse_channel(b)?,
+        ));
+    }
+    if let Some(args) = function_args(&input, "hsl") {
+        let [h, s, l] = args;
+        let h = h.strip_suffix("deg").unwrap_or(h);
+        let h: f64 = h.parse().map_err(|_| AppError::InvalidColor)?;
+        let s = parse_percentage(s)?;
+        let l = parse_percentage(l)?;
+        return Ok(hsl_to_int(h, s, l));
+    }
+
+    let hex = input.strip_prefix('#').unwrap_or(&input);
+    if !hex.chars().all(|char| char.is_ascii_hexdigit()) {
+        return Err(AppError::InvalidColor);
+    }
+    match hex.len() {
+        3 => {
+            let doubled: String = hex.chars().flat_map(|char| [char, char]).collect();
+            i32::from_str_radix(&doubled, 16).map_err(|_| AppError::InvalidColor)
+        }
+        6 => i32::from_str_radix(hex, 16).map_err(|_| AppError::InvalidColor),
+        _ => Err(AppError::InvalidColor),
+    }
 }
 
+/// Formats a color as a `#RRGGBB` string, which `rgb_string_to_int` parses back to the same integer.
 pub fn rgb_int_to_string(input: i32) -> String {
-    (0..3).fold(String::from("#"), |mut color, i| {
-        let value = (input >> ((2 - i) * 8)) & 0xff;
-        let hex = format!("{value:X}");
-        color.push_str(&format!("{hex:0<2}"));
-        color
-    })
+    format!("#{:06X}", input & 0xffffff)
+}
+
+/// Chooses the text color with the higher WCAG contrast ratio against the background color `input`.
+pub fn text_color(input: i32) -> &'static str {
+    let luminance = relative_luminance(input);
+    let dark_contrast = (luminance + 0.05) / 0.05;
+    let light_contrast = 1.05 / (luminance + 0.05);
+
+    if dark_contrast >= light_contrast {
+        DARK_TEXT_COLOR
+    } else {
+        LIGHT_TEXT_COLOR
+    }
+}
+
+/// Computes the relative luminance of a color as defined by WCAG 2.
+pub fn relative_luminance(input: i32) -> f64 {
+    let (r, g, b) = int_to_rgb(input);
+
+    0.2126 * linearize(r) + 0.7152 * linearize(g) + 0.0722 * linearize(b)
+}
+
+/// Computes the perceptual distance between two colors as the CIE76 difference of their CIELAB coordinates.
+pub fn color_distance(a: i32, b: i32) -> f64 {
+    let (l1, a1, b1) = int_to_lab(a);
+    let (l2, a2, b2) = int_to_lab(b);
+
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+/// Picks the candidate whose distance to the closest of the `taken` colors is the largest.
+///
+/// Ties are resolved in favor of the earlier candidate, so the first candidate is picked if nothing is taken.
+///
+/// Returns `None` if there are no candidates.
+pub fn furthest_color(candidates: &[i32], taken: &[i32]) -> Option<i32> {
+    let min_distance = |candidate: i32| {
+        taken
+            .iter()
+            .map(|color| color_distance(candidate, *color))
+            .fold(f64::INFINITY, f64::min)
+    };
+
+    candidates
+        .iter()
+        .copied()
+        .fold(None, |best: Option<(i32, f64)>, candidate| {
+            let distance = min_distance(candidate);
+            match best {
+                Some((_, best_distance)) if best_distance >= distance => best,
+                _ => Some((candidate, distance)),
+            }
+        })
+        .map(|(color, _)| color)
+}
+
+/// Lists the colors the `palette` can give to a new tag named `name`, starting with the preferred one.
+pub fn candidate_colors(palette: TagPalette, name: &str) -> Vec<i32> {
+    match palette {
+        TagPalette::Hash => std::iter::once(string_to_color(name))
+            .chain((1..HASH_VARIANT_COUNT).map(|i| string_to_color(&format!("{name}#{i}"))))
+            .collect(),
+        TagPalette::Fixed => FIXED_PALETTE.to_vec(),
+        TagPalette::Spaced => spaced_colors(SPACED_HUE_COUNT),
+    }
+}
+
+/// Generates `count` colors with evenly spaced hues, starting with red.
+pub fn spaced_colors(count: usize) -> Vec<i32> {
+    (0..count)
+        .map(|i| {
+            let hue = 360.0 * i as f64 / count as f64;
+            hsl_to_int(hue, SPACED_SATURATION, SPACED_LIGHTNESS)
+        })
+        .collect()
+}
+
+/// Converts an sRGB channel to its linear value between 0 and 1.
+fn linearize(channel: i32) -> f64 {
+    let value = channel as f64 / 255.0;
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a color to CIELAB coordinates using the D65 white point.
+fn int_to_lab(input: i32) -> (f64, f64, f64) {
+    let (r, g, b) = int_to_rgb(input);
+    let (r, g, b) = (linearize(r), linearize(g), linearize(b));
+
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Splits the arguments of the CSS function `name`, e.g. `rgb(1, 2, 3)`, if the input is a call to it.
+fn function_args<'a>(input: &'a str, name: &str) -> Option<[&'a str; 3]> {
+    let args = input
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    let args: Vec<&str> = args
+        .split(|char: char| char == ',' || char.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect();
+
+    args.try_into().ok()
+}
+
+fn parse_channel(input: &str) -> Result<i32, AppError> {
+    match input.parse::<i32>() {
+        Ok(value) if (0..=255).contains(&value) => Ok(value),
+        _ => Err(AppError::InvalidColor),
+    }
+}
+
+/// Parses a percentage like `50%` into a fraction between 0 and 1.
+fn parse_percentage(input: &str) -> Result<f64, AppError> {
+    let value: f64 = input
+        .strip_suffix('%')
+        .and_then(|value| value.parse().ok())
+        .ok_or(AppError::InvalidColor)?;
+    if !(0.0..=100.0).contains(&value) {
+        return Err(AppError::InvalidColor);
+    }
+
+    Ok(value / 100.0)
+}
+
+fn hsl_to_int(h: f64, s: f64, l: f64) -> i32 {
+    let h = h.rem_euclid(360.0);
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = chroma * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = l - chroma / 2.0;
+
+    let (r, g, b) = match h {
+        h if h < 60.0 => (chroma, x, 0.0),
+        h if h < 120.0 => (x, chroma, 0.0),
+        h if h < 180.0 => (0.0, chroma, x),
+        h if h < 240.0 => (0.0, x, chroma),
+        h if h < 300.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let to_channel = |value: f64| ((value + m) * 255.0).round() as i32;
+
+    rgb_to_int(to_channel(r), to_channel(g), to_channel(b))
 }
@@ -0,0 +1,84 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex, MutexGuard};
+
+/// Extension of files that are still being written.
+const TMP_EXTENSION: &str = "tmp";
+
+/// Directory of files addressed by the SHA-256 hash of their contents.
+///
+/// Storing the same contents twice keeps a single file.
+pub struct FileStore {
+    dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Waits until nobody else holds the store and holds it until the returned guard is dropped.
+    ///
+    /// Storing a file and recording its content hash, as well as collecting the garbage, should happen
+    /// while holding the store, so that a file is never removed between being stored and being recorded.
+    pub async fn lock(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    /// Returns the path of the file with a given content hash.
+    pub fn path(&self, content_hash: &str) -> PathBuf {
+        self.dir.join(content_hash)
+    }
+
+    /// Writes `contents` to the store unless a file with the same contents is already there.
+    ///
+    /// Returns the content hash that identifies the file.
+    pub fn store(&self, contents: &[u8]) -> io::Result<String> {
+        let content_hash = format!("{:x}", Sha256::digest(contents));
+        let path = self.path(&content_hash);
+        if !path.exists() {
+            fs::create_dir_all(&self.dir)?;
+            // Writing to a temporary file first makes sure that a file named after a hash is always complete.
+            let tmp_path = self.dir.join(format!("{content_hash}.{TMP_EXTENSION}"));
+            fs::write(&tmp_path, contents)?;
+            fs::rename(&tmp_path, &path)?;
+        }
+        Ok(content_hash)
+    }
+
+    /// Removes every file whose content hash is not in `keep`.
+    ///
+    /// Temporary files are skipped, since they may still be being written.
+    pub fn retain(&self, keep: &HashSet<String>) -> io::Result<()> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            let is_tmp = path
+                .extension()
+                .is_some_and(|extension| extension == TMP_EXTENSION);
+            if !is_tmp && !is_kept(&path, keep) {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_kept(path: &Path, keep: &HashSet<String>) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| keep.contains(name))
+}
@@ -0,0 +1,43 @@
+use crate::errors::AppError;
+
+/// Move of a single item from one position to another in an ordered list.
+pub struct OrderMove {
+    pub id: i32,
+    pub from: i32,
+    pub to: i32,
+}
+
+/// Computes moves of single items that turn the order `current` into the order `target`,
+/// as if every item was taken out of the list and put back at its new position one after another.
+///
+/// Applying the moves in reverse order with `from` and `to` swapped restores the order `current`.
+///
+/// Returns `Err(InvalidOrder)` if `target` is not a permutation of `current`.
+pub fn moves_between(current: &[i32], target: &[i32]) -> Result<Vec<OrderMove>, AppError> {
+    let mut sorted_current = current.to_vec();
+    sorted_current.sort_unstable();
+    let mut sorted_target = target.to_vec();
+    sorted_target.sort_unstable();
+    if sorted_current != sorted_target {
+        return Err(AppError::InvalidOrder);
+    }
+
+    let mut order = current.to_vec();
+    let mut moves = Vec::new();
+    for (to, id) in target.iter().enumerate() {
+        let from = order
+            .iter()
+            .position(|item| item == id)
+            .ok_or(AppError::InvalidOrder)?;
+        if from != to {
+            order.remove(from);
+            order.insert(to, *id);
+            moves.push(OrderMove {
+                id: *id,
+                from: from as i32,
+                to: to as i32,
+            });
+        }
+    }
+    Ok(moves)
+}
@@ -0,0 +1,65 @@
+/// Private-use characters put around the matched terms by FTS5, replaced by `<mark>` tags once the text is escaped.
+pub const MATCH_START: char = '\u{E000}';
+pub const MATCH_END: char = '\u{E001}';
+
+/// Turns a search query typed by the user into an FTS5 query.
+///
+/// Words and `"quoted phrases"` are matched as they are, and all of them must match.
+/// A word or a phrase followed by `*` matches any text starting with it.
+/// Other FTS5 syntax is not interpreted, so any input makes a valid query.
+///
+/// Returns `None` if the query has no searchable terms.
+pub fn to_fts_query(input: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char.is_whitespace() {
+            continue;
+        }
+        let text: String = if char == '"' {
+            chars.by_ref().take_while(|char| *char != '"').collect()
+        } else {
+            let mut word = String::from(char);
+            while let Some(char) = chars.next_if(|char| !char.is_whitespace() && *char != '"') {
+                word.push(char);
+            }
+            word
+        };
+
+        let mut text = text.as_str();
+        let mut is_prefix = false;
+        if let Some(stripped) = text.strip_suffix('*') {
+            text = stripped.trim_end_matches('*');
+            is_prefix = true;
+        }
+        if chars.next_if_eq(&'*').is_some() {
+            is_prefix = true;
+        }
+
+        if text.chars().any(char::is_alphanumeric) {
+            let term = format!("\"{}\"", text.trim());
+            terms.push(if is_prefix { term + "*" } else { term });
+        }
+    }
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Escapes text highlighted by FTS5 for HTML and wraps the terms between `MATCH_START` and `MATCH_END` in `<mark>` tags.
+pub fn to_highlighted_html(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            MATCH_START => res.push_str("<mark>"),
+            MATCH_END => res.push_str("</mark>"),
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            char => res.push(char),
+        }
+    }
+    res
+}
@@ -0,0 +1,11 @@
+use time::OffsetDateTime;
+
+/// Returns the current UTC time truncated to whole seconds.
+///
+/// Timestamps are stored as RFC 3339 text, so keeping the same precision for every row
+/// makes them compare correctly as strings in SQL queries.
+pub fn now() -> OffsetDateTime {
+    OffsetDateTime::now_utc()
+        .replace_nanosecond(0)
+        .expect("zero is a valid nanosecond")
+}
@@ -1,8 +1,25 @@
 use app_lib::{
-    commands::activity::{CreateActivityInput, UpdateActivityColumnInput},
-    database::activity::{Mutation, Query},
+    commands::{
+        activity::{
+            CreateActivityInput, DuplicateActivityInput, UnarchiveActivityInput,
+            UpdateActivityColumnInput, UpdateActivityContentInput, WipLimitBreachOutput,
+        },
+        columns::UpdateColumnWipLimitInput,
+    },
+    database::{
+        activity::{Mutation, Query},
+        checklist, columns,
+    },
+    errors::AppError,
 };
+use entity::activity_tags;
+use sea_orm::{ColumnTrait, DbConn, EntityTrait, QueryFilter};
 use sqlx::SqlitePool;
+use time::{Date, Month};
+
+fn date(day: u8) -> Date {
+    Date::from_calendar_date(2026, Month::October, day).unwrap()
+}
 
 #[sqlx::test(migrations = "../migrations", fixtures("add_activity.sql"))]
 async fn add_activity(db: SqlitePool) {
@@ -10,9 +27,15 @@ async fn add_activity(db: SqlitePool) {
         name: "abc".into(),
         body: Some("def".into()),
         column_id: 1,
+        start_date: None,
+        due_date: None,
+        allow_over_limit: false,
     };
 
-    let res = Mutation::create_activity(&db.into(), input).await.unwrap();
+    let res = Mutation::create_activity(&db.into(), input)
+        .await
+        .unwrap()
+        .activity;
 
     assert_eq!(res.id, 1);
     assert_eq!(res.name, "abc");
@@ -28,9 +51,15 @@ async fn add_activity_changes_ordinals(db: SqlitePool) {
         name: "abc".into(),
         body: Some("def".into()),
         column_id: 1,
+        start_date: None,
+        due_date: None,
+        allow_over_limit: false,
     };
 
-    let res = Mutation::create_activity(&db, input).await.unwrap();
+    let res = Mutation::create_activity(&db, input)
+        .await
+        .unwrap()
+        .activity;
 
     assert_eq!(res.id, 2);
     assert_eq!(res.name, "abc");
@@ -38,7 +67,7 @@ async fn add_activity_changes_ordinals(db: SqlitePool) {
     assert_eq!(res.column_id, Some(1));
     assert_eq!(res.ordinal, 0);
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].name, "Profit");
     assert_eq!(all_activities[&1].ordinal, 1);
 }
@@ -47,7 +76,7 @@ async fn add_activity_changes_ordinals(db: SqlitePool) {
 async fn update_activity_position_position_unchanged(db: SqlitePool) {
     let db = db.into();
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].ordinal, 0);
     assert_eq!(all_activities[&2].ordinal, 1);
     assert_eq!(all_activities[&3].ordinal, 2);
@@ -58,12 +87,13 @@ async fn update_activity_position_position_unchanged(db: SqlitePool) {
             id: 2,
             column_id: Some(1),
             new_ord: 1,
+            allow_over_limit: false,
         },
     )
     .await
     .unwrap();
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].ordinal, 0);
     assert_eq!(all_activities[&2].ordinal, 1);
     assert_eq!(all_activities[&3].ordinal, 2);
@@ -73,7 +103,7 @@ async fn update_activity_position_position_unchanged(db: SqlitePool) {
 async fn update_activity_position_same_column(db: SqlitePool) {
     let db = db.into();
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].ordinal, 0);
     assert_eq!(all_activities[&2].ordinal, 1);
     assert_eq!(all_activities[&3].ordinal, 2);
@@ -84,12 +114,13 @@ async fn update_activity_position_same_column(db: SqlitePool) {
             id: 3,
             column_id: Some(1),
             new_ord: 1,
+            allow_over_limit: false,
         },
     )
     .await
     .unwrap();
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].ordinal, 0);
     assert_eq!(all_activities[&2].ordinal, 2);
     assert_eq!(all_activities[&3].ordinal, 1);
@@ -99,7 +130,7 @@ async fn update_activity_position_same_column(db: SqlitePool) {
 async fn update_activity_position_different_column(db: SqlitePool) {
     let db = db.into();
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].ordinal, 0);
     assert_eq!(all_activities[&2].ordinal, 1);
     assert_eq!(all_activities[&3].ordinal, 2);
@@ -113,12 +144,13 @@ async fn update_activity_position_different_column(db: SqlitePool) {
             id: 2,
             column_id: Some(2),
             new_ord: 0,
+            allow_over_limit: false,
         },
     )
     .await
     .unwrap();
 
-    let all_activities = Query::all_column_activities(&db).await.unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
     assert_eq!(all_activities[&1].ordinal, 0);
     assert_eq!(all_activities[&2].ordinal, 0);
     assert_eq!(all_activities[&3].ordinal, 1);
@@ -126,3 +158,369 @@ async fn update_activity_position_different_column(db: SqlitePool) {
     assert_eq!(all_activities[&5].ordinal, 2);
     assert_eq!(all_activities[&6].ordinal, 3);
 }
+
+#[sqlx::test(migrations = "../migrations", fixtures("update_activity_position.sql"))]
+async fn archive_activity_closes_gap(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::archive_activity_by_id(&db, 2).await.unwrap();
+
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert!(!all_activities.contains_key(&2));
+    assert_eq!(all_activities[&1].ordinal, 0);
+    assert_eq!(all_activities[&3].ordinal, 1);
+
+    let other_activities = Query::all_other_activities(&db, None).await.unwrap();
+    assert!(other_activities.is_empty());
+
+    let archived_activities = Query::all_archived_activities(&db, None).await.unwrap();
+    assert_eq!(archived_activities.len(), 1);
+    assert_eq!(archived_activities[&2].name, "2");
+
+    let res = Mutation::archive_activity_by_id(&db, 2).await;
+    assert!(matches!(res, Err(AppError::ActivityArchived)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("update_activity_position.sql"))]
+async fn unarchive_activity_to_chosen_position(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::archive_activity_by_id(&db, 2).await.unwrap();
+    Mutation::unarchive_activity_by_id(
+        &db,
+        UnarchiveActivityInput {
+            id: 2,
+            column_id: Some(2),
+            new_ord: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&2].column_id, 2);
+    assert_eq!(all_activities[&2].ordinal, 1);
+    assert_eq!(all_activities[&4].ordinal, 0);
+    assert_eq!(all_activities[&5].ordinal, 2);
+    assert_eq!(all_activities[&6].ordinal, 3);
+
+    let archived_activities = Query::all_archived_activities(&db, None).await.unwrap();
+    assert!(archived_activities.is_empty());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("due_dates.sql"))]
+async fn fetch_activities_by_due_date(db: SqlitePool) {
+    let db = db.into();
+    let today = date(18);
+
+    let overdue = Query::overdue_activities(&db, None, today).await.unwrap();
+    let ids: Vec<i32> = overdue.iter().map(|activity| activity.id).collect();
+    assert_eq!(ids, vec![3, 4]);
+
+    let due_today = Query::activities_due_between(&db, None, today, today)
+        .await
+        .unwrap();
+    let ids: Vec<i32> = due_today.iter().map(|activity| activity.id).collect();
+    assert_eq!(ids, vec![2]);
+
+    let due_soon = Query::activities_due_between(&db, None, today, date(25))
+        .await
+        .unwrap();
+    let ids: Vec<i32> = due_soon.iter().map(|activity| activity.id).collect();
+    assert_eq!(ids, vec![2, 1]);
+    assert_eq!(due_soon[1].start_date, Some(date(1)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("add_activity.sql"))]
+async fn start_date_after_due_date_is_rejected(db: SqlitePool) {
+    let input = CreateActivityInput {
+        name: "abc".into(),
+        body: None,
+        column_id: 1,
+        start_date: Some(date(20)),
+        due_date: Some(date(19)),
+        allow_over_limit: false,
+    };
+
+    let res = Mutation::create_activity(&db.into(), input).await;
+
+    assert!(matches!(res, Err(AppError::InvalidDateRange)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("add_activity.sql"))]
+async fn add_activity_records_timestamps(db: SqlitePool) {
+    let input = CreateActivityInput {
+        name: "abc".into(),
+        body: None,
+        column_id: 1,
+        start_date: None,
+        due_date: None,
+        allow_over_limit: false,
+    };
+
+    let res = Mutation::create_activity(&db.into(), input)
+        .await
+        .unwrap()
+        .activity;
+
+    assert!(res.created_at.is_some());
+    assert_eq!(res.updated_at, res.created_at);
+    assert_eq!(res.column_entered_at, res.created_at);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("activity_timestamps.sql"))]
+async fn column_entered_at_changes_only_with_column(db: SqlitePool) {
+    let db = db.into();
+    let initial = Query::find_activity_by_id(&db, 1)
+        .await
+        .unwrap()
+        .unwrap()
+        .column_entered_at;
+
+    Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 1,
+            column_id: Some(1),
+            new_ord: 1,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+    let reordered = Query::find_activity_by_id(&db, 1).await.unwrap().unwrap();
+    assert_eq!(reordered.column_entered_at, initial);
+    assert!(reordered.updated_at > initial);
+
+    Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 1,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+    let moved = Query::find_activity_by_id(&db, 1).await.unwrap().unwrap();
+    assert!(moved.column_entered_at > initial);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("activity_timestamps.sql"))]
+async fn update_activity_content_touches_updated_at(db: SqlitePool) {
+    let db: DbConn = db.into();
+
+    Mutation::update_activity_content_by_id(
+        &db,
+        UpdateActivityContentInput {
+            id: 2,
+            name: "new".into(),
+            body: None,
+            start_date: None,
+            due_date: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = Query::find_activity_by_id(&db, 2).await.unwrap().unwrap();
+    assert!(res.updated_at > res.created_at);
+    assert_eq!(res.column_entered_at, res.created_at);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("wip_limits.sql"))]
+async fn full_column_rejects_new_activities(db: SqlitePool) {
+    let db = db.into();
+    let input = CreateActivityInput {
+        name: "abc".into(),
+        body: None,
+        column_id: 2,
+        start_date: None,
+        due_date: None,
+        allow_over_limit: false,
+    };
+
+    let res = Mutation::create_activity(&db, input).await;
+    assert!(matches!(
+        res,
+        Err(AppError::WipLimitExceeded {
+            column_id: 2,
+            limit: 2
+        })
+    ));
+
+    let res = Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 1,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::WipLimitExceeded { .. })));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("wip_limits.sql"))]
+async fn full_column_allows_reordering_and_leaving(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 3,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+    Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 2,
+            column_id: Some(1),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+    Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 1,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("duplicate_activity.sql"))]
+async fn duplicate_activity_after_original(db: SqlitePool) {
+    let db = db.into();
+
+    let res = Mutation::duplicate_activity(
+        &db,
+        DuplicateActivityInput {
+            id: 2,
+            column_id: None,
+            name_suffix: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res.id, 5);
+    assert_eq!(res.name, "2 (copy)");
+    assert_eq!(res.body, Some("body".into()));
+    assert_eq!(res.column_id, Some(1));
+    assert_eq!(res.ordinal, 2);
+
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&2].ordinal, 1);
+    assert_eq!(all_activities[&3].ordinal, 3);
+
+    let mut tag_ids: Vec<i32> = activity_tags::Entity::find()
+        .filter(activity_tags::Column::ActivityId.eq(5))
+        .all(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|link| link.category_tag_id)
+        .collect();
+    tag_ids.sort();
+    assert_eq!(tag_ids, vec![1, 2]);
+
+    let items = checklist::Query::all_checklist_items(&db, 5).await.unwrap();
+    let mut items: Vec<_> = items.into_values().collect();
+    items.sort_by_key(|item| item.ordinal);
+    let texts: Vec<&str> = items.iter().map(|item| item.text.as_str()).collect();
+    assert_eq!(texts, vec!["first", "second"]);
+    assert!(items[0].done);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("duplicate_activity.sql"))]
+async fn duplicate_activity_to_other_column(db: SqlitePool) {
+    let db = db.into();
+
+    let res = Mutation::duplicate_activity(
+        &db,
+        DuplicateActivityInput {
+            id: 2,
+            column_id: Some(2),
+            name_suffix: Some(" v2".into()),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res.name, "2 v2");
+    assert_eq!(res.column_id, Some(2));
+    assert_eq!(res.ordinal, 0);
+
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&3].ordinal, 2);
+    assert_eq!(all_activities[&4].ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("wip_limits.sql"))]
+async fn full_column_accepts_activities_over_limit_when_allowed(db: SqlitePool) {
+    let db = db.into();
+    let breach = Some(WipLimitBreachOutput {
+        column_id: 2,
+        limit: 2,
+    });
+
+    let res = Mutation::create_activity(
+        &db,
+        CreateActivityInput {
+            name: "abc".into(),
+            body: None,
+            column_id: 2,
+            start_date: None,
+            due_date: None,
+            allow_over_limit: true,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(res.activity.column_id, Some(2));
+    assert_eq!(res.wip_limit_breach, breach);
+
+    let res = Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 1,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: true,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(res, breach);
+    let res = Query::find_activity_by_id(&db, 1).await.unwrap().unwrap();
+    assert_eq!(res.column_id, Some(2));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("wip_limits.sql"))]
+async fn negative_wip_limit_is_rejected(db: SqlitePool) {
+    let db = db.into();
+
+    let res = columns::Mutation::update_column_wip_limit(
+        &db,
+        UpdateColumnWipLimitInput {
+            id: 1,
+            wip_limit: Some(-1),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::InvalidWipLimit)));
+}
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use app_lib::{
+    commands::attachments::AddAttachmentInput,
+    database::{activity, attachments},
+    utils::file_store::FileStore,
+};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("attachments.sql"))]
+async fn duplicate_contents_are_stored_once(db: SqlitePool) {
+    let db = db.into();
+    let source = tempfile::tempdir().unwrap();
+    let store_dir = tempfile::tempdir().unwrap();
+    let store = FileStore::new(store_dir.path());
+    let path = source.path().join("notes.txt");
+    std::fs::write(&path, "hello").unwrap();
+    let tmp_path = store_dir.path().join("partial.tmp");
+
+    let first = attachments::Mutation::insert_attachment(
+        &db,
+        &store,
+        AddAttachmentInput {
+            activity_id: 1,
+            path: path.clone(),
+        },
+    )
+    .await
+    .unwrap();
+    let second = attachments::Mutation::insert_attachment(
+        &db,
+        &store,
+        AddAttachmentInput {
+            activity_id: 2,
+            path,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(first.name, "notes.txt");
+    assert_eq!(first.size, 5);
+    assert_eq!(first.mime_type, "application/octet-stream");
+    assert_eq!(first.content_hash, second.content_hash);
+    assert_eq!(std::fs::read_dir(store_dir.path()).unwrap().count(), 1);
+    std::fs::write(&tmp_path, "hel").unwrap();
+
+    attachments::Mutation::delete_attachment_by_id(&db, &store, &HashSet::new(), first.id)
+        .await
+        .unwrap();
+    assert!(store.path(&second.content_hash).exists());
+
+    activity::Mutation::delete_activity_by_id(&db, 2)
+        .await
+        .unwrap();
+    attachments::Mutation::collect_garbage(&db, &store, &HashSet::new())
+        .await
+        .unwrap();
+    assert!(!store.path(&second.content_hash).exists());
+    assert!(tmp_path.exists());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("attachments.sql"))]
+async fn list_attachments_points_to_store(db: SqlitePool) {
+    let db = db.into();
+    let source = tempfile::tempdir().unwrap();
+    let store_dir = tempfile::tempdir().unwrap();
+    let store = FileStore::new(store_dir.path());
+    let path = source.path().join("image.png");
+    std::fs::write(&path, b"\x89PNG\r\n\x1a\n").unwrap();
+
+    let res = attachments::Mutation::insert_attachment(
+        &db,
+        &store,
+        AddAttachmentInput {
+            activity_id: 1,
+            path,
+        },
+    )
+    .await
+    .unwrap();
+
+    let all = attachments::Query::all_activity_attachments(&db, &store, 1)
+        .await
+        .unwrap();
+    assert_eq!(all[&res.id].mime_type, "image/png");
+    assert_eq!(all[&res.id].path, store.path(&res.content_hash));
+}
@@ -0,0 +1,107 @@
+use app_lib::{
+    commands::activity::UpdateActivityColumnInput,
+    database::{activity, boards, columns, trash},
+};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("boards.sql"))]
+async fn insert_column_appends_to_end_of_board(db: SqlitePool) {
+    let db = db.into();
+
+    let res = columns::Mutation::insert_column(&db, 2, "test".into())
+        .await
+        .unwrap();
+    assert_eq!(res.board_id, 2);
+    assert_eq!(res.ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("boards.sql"))]
+async fn fetch_activities_of_one_board(db: SqlitePool) {
+    let db = db.into();
+
+    let column_activities = activity::Query::all_column_activities(&db, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(column_activities.len(), 1);
+    assert!(column_activities.contains_key(&3));
+
+    let other_activities = activity::Query::all_other_activities(&db, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(other_activities.len(), 1);
+    assert!(other_activities.contains_key(&5));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("boards.sql"))]
+async fn move_activity_to_another_board(db: SqlitePool) {
+    let db = db.into();
+
+    activity::Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: 1,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let all_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(all_activities[&1].column_id, 2);
+    assert_eq!(all_activities[&1].ordinal, 0);
+    assert_eq!(all_activities[&2].ordinal, 0);
+    assert_eq!(all_activities[&3].ordinal, 1);
+
+    let found = activity::Query::find_activity_by_id(&db, 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.board_id, 2);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("boards.sql"))]
+async fn purged_column_moves_activities_to_board_stash(db: SqlitePool) {
+    let db = db.into();
+
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    trash::Mutation::purge_trash(&db, -1).await.unwrap();
+
+    let other_activities = activity::Query::all_other_activities(&db, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(other_activities[&5].ordinal, 0);
+    assert_eq!(other_activities[&3].ordinal, 1);
+
+    let default_board_stash = activity::Query::all_other_activities(&db, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(default_board_stash.len(), 1);
+    assert_eq!(default_board_stash[&4].ordinal, 0);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("boards.sql"))]
+async fn delete_board_removes_its_contents(db: SqlitePool) {
+    let db = db.into();
+
+    boards::Mutation::delete_board_by_id(&db, 1).await.unwrap();
+
+    let all_boards = boards::Query::all_boards(&db).await.unwrap();
+    assert_eq!(all_boards.len(), 1);
+    assert_eq!(all_boards[&2].ordinal, 0);
+
+    let all_columns = columns::Query::all_columns(&db, None).await.unwrap();
+    assert_eq!(all_columns.len(), 1);
+    assert!(all_columns.contains_key(&2));
+
+    let other_activities = activity::Query::all_other_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(other_activities.len(), 1);
+    assert!(other_activities.contains_key(&5));
+}
@@ -0,0 +1,251 @@
+use app_lib::{
+    commands::{
+        attachments::AddAttachmentInput,
+        bulk::{BulkItemOutput, BulkMoveActivitiesInput, BulkTagInput},
+        checklist::CreateChecklistItemInput,
+    },
+    database::{
+        activity::Query,
+        attachments,
+        bulk::Mutation,
+        checklist, columns,
+        undo::{self, UndoStack},
+    },
+    errors::AppError,
+    utils::file_store::FileStore,
+};
+use entity::{activity_tags, checklist_items};
+use sea_orm::{ColumnTrait, DbConn, EntityTrait, QueryFilter};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("bulk.sql"))]
+async fn bulk_move_keeps_relative_order(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let out = stack
+        .record(
+            &db,
+            Mutation::move_activities(
+                &db,
+                BulkMoveActivitiesInput {
+                    ids: vec![4, 2, 99, 7],
+                    column_id: 2,
+                    new_ord: 0,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(out[&2], BulkItemOutput::Done));
+    assert!(matches!(out[&4], BulkItemOutput::Done));
+    assert!(matches!(
+        out[&99],
+        BulkItemOutput::Skipped(AppError::RowNotFound)
+    ));
+    assert!(matches!(
+        out[&7],
+        BulkItemOutput::Skipped(AppError::ActivityArchived)
+    ));
+
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&2].column_id, 2);
+    assert_eq!(all_activities[&2].ordinal, 0);
+    assert_eq!(all_activities[&4].column_id, 2);
+    assert_eq!(all_activities[&4].ordinal, 1);
+    assert_eq!(all_activities[&5].ordinal, 2);
+    assert_eq!(all_activities[&1].ordinal, 0);
+    assert_eq!(all_activities[&3].ordinal, 1);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    for id in 1..=4 {
+        assert_eq!(all_activities[&id].column_id, 1);
+        assert_eq!(all_activities[&id].ordinal, id - 1);
+    }
+    assert_eq!(all_activities[&5].ordinal, 0);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("bulk.sql"))]
+async fn bulk_move_respects_wip_limit(db: SqlitePool) {
+    let db = db.into();
+
+    let out = Mutation::move_activities(
+        &db,
+        BulkMoveActivitiesInput {
+            ids: vec![1, 2, 3],
+            column_id: 3,
+            new_ord: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(matches!(out[&1], BulkItemOutput::Done));
+    assert!(matches!(
+        out[&2],
+        BulkItemOutput::Skipped(AppError::WipLimitExceeded {
+            column_id: 3,
+            limit: 2
+        })
+    ));
+    assert!(matches!(
+        out[&3],
+        BulkItemOutput::Skipped(AppError::WipLimitExceeded { .. })
+    ));
+
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&6].ordinal, 0);
+    assert_eq!(all_activities[&1].ordinal, 1);
+    assert_eq!(all_activities[&2].ordinal, 0);
+    assert_eq!(all_activities[&3].ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("bulk.sql"))]
+async fn bulk_move_rejects_trashed_column(db: SqlitePool) {
+    let db: DbConn = db.into();
+
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    let res = Mutation::move_activities(
+        &db,
+        BulkMoveActivitiesInput {
+            ids: vec![1, 2],
+            column_id: 2,
+            new_ord: 0,
+        },
+    )
+    .await;
+
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&1].column_id, 1);
+    assert_eq!(all_activities[&2].column_id, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("bulk.sql"))]
+async fn bulk_tag_archive_and_delete(db: SqlitePool) {
+    let db = db.into();
+    let tagged = |db| async move {
+        let mut ids: Vec<i32> = activity_tags::Entity::find()
+            .filter(activity_tags::Column::CategoryTagId.eq(1))
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|link| link.activity_id)
+            .collect();
+        ids.sort();
+        ids
+    };
+
+    let out = Mutation::add_tag(
+        &db,
+        BulkTagInput {
+            ids: vec![1, 2, 99],
+            tag_id: 1,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(matches!(out[&1], BulkItemOutput::Done));
+    assert!(matches!(
+        out[&99],
+        BulkItemOutput::Skipped(AppError::RowNotFound)
+    ));
+    assert_eq!(tagged(&db).await, vec![1, 2]);
+
+    Mutation::remove_tag(
+        &db,
+        BulkTagInput {
+            ids: vec![1, 2],
+            tag_id: 1,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(tagged(&db).await.is_empty());
+    let res = Mutation::remove_tag(
+        &db,
+        BulkTagInput {
+            ids: vec![1, 2],
+            tag_id: 99,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+
+    let out = Mutation::archive_activities(&db, vec![1, 3, 7])
+        .await
+        .unwrap();
+    assert!(matches!(
+        out[&7],
+        BulkItemOutput::Skipped(AppError::ActivityArchived)
+    ));
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&2].ordinal, 0);
+    assert_eq!(all_activities[&4].ordinal, 1);
+
+    let out = Mutation::delete_activities(&db, vec![2, 7]).await.unwrap();
+    assert!(matches!(out[&7], BulkItemOutput::Done));
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert!(!all_activities.contains_key(&2));
+    assert_eq!(all_activities[&4].ordinal, 0);
+    assert!(Query::find_activity_by_id(&db, 7).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("bulk.sql"))]
+async fn undo_bulk_delete_restores_children(db: SqlitePool) {
+    let db: DbConn = db.into();
+    let stack = UndoStack::default();
+    let source = tempfile::tempdir().unwrap();
+    let store_dir = tempfile::tempdir().unwrap();
+    let store = FileStore::new(store_dir.path());
+    let path = source.path().join("notes.txt");
+    std::fs::write(&path, "hello").unwrap();
+
+    let item = checklist::Mutation::insert_checklist_item(
+        &db,
+        CreateChecklistItemInput {
+            activity_id: 2,
+            text: "Review".into(),
+        },
+    )
+    .await
+    .unwrap();
+    let attachment = attachments::Mutation::insert_attachment(
+        &db,
+        &store,
+        AddAttachmentInput {
+            activity_id: 2,
+            path,
+        },
+    )
+    .await
+    .unwrap();
+
+    stack
+        .record(&db, Mutation::delete_activities(&db, vec![1, 2]))
+        .await
+        .unwrap();
+    attachments::Mutation::collect_garbage(&db, &store, &stack.attachment_hashes().await)
+        .await
+        .unwrap();
+    assert!(store.path(&attachment.content_hash).exists());
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let all_activities = Query::all_column_activities(&db, None).await.unwrap();
+    assert_eq!(all_activities[&1].ordinal, 0);
+    assert_eq!(all_activities[&2].ordinal, 1);
+    assert!(checklist_items::Entity::find_by_id(item.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_some());
+    let all_attachments = attachments::Query::all_activity_attachments(&db, &store, 2)
+        .await
+        .unwrap();
+    assert!(all_attachments[&attachment.id].path.exists());
+}
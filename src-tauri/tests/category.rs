@@ -1,12 +1,85 @@
-use app_lib::database::category::Mutation;
+use app_lib::{
+    commands::category::{SetCategoryOrderInput, UpdateCategoryOrdinalInput},
+    database::{
+        category::Mutation,
+        undo::{self, UndoStack},
+    },
+    errors::AppError,
+};
+use entity::categories;
+use sea_orm::{DbConn, EntityTrait, QueryOrder};
 use sqlx::SqlitePool;
 
+async fn order(db: &DbConn) -> Vec<i32> {
+    categories::Entity::find()
+        .order_by_asc(categories::Column::Ordinal)
+        .all(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|category| category.id)
+        .collect()
+}
+
 #[sqlx::test(migrations = "../migrations", fixtures("insert_category.sql"))]
 async fn insert_category_appends_to_end(db: SqlitePool) {
     let db = db.into();
 
-    let res = Mutation::insert_category(&db, "test".into()).await.unwrap();
+    let res = Mutation::insert_category(&db, 1, "test".into())
+        .await
+        .unwrap();
     assert_eq!(res.id, 2);
     assert_eq!(res.name, "test");
     assert_eq!(res.ordinal, 1);
 }
+
+#[sqlx::test(migrations = "../migrations", fixtures("category_order.sql"))]
+async fn update_category_ordinal_shifts_other_categories(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::update_category_ordinal(
+        &db,
+        UpdateCategoryOrdinalInput {
+            category_id: 3,
+            new_ord: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(order(&db).await, vec![3, 1, 2]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("category_order.sql"))]
+async fn set_category_order_can_be_undone(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let data = SetCategoryOrderInput {
+        board_id: 1,
+        ids: vec![2, 3, 1],
+    };
+    stack
+        .record(&db, Mutation::set_category_order(&db, data))
+        .await
+        .unwrap();
+    assert_eq!(order(&db).await, vec![2, 3, 1]);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(order(&db).await, vec![1, 2, 3]);
+
+    undo::Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(order(&db).await, vec![2, 3, 1]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("category_order.sql"))]
+async fn set_category_order_rejects_other_ids(db: SqlitePool) {
+    let db = db.into();
+
+    for ids in [vec![1, 2], vec![1, 2, 3, 4], vec![1, 1, 2], vec![1, 2, 4]] {
+        let data = SetCategoryOrderInput { board_id: 1, ids };
+        let res = Mutation::set_category_order(&db, data).await;
+        assert!(matches!(res, Err(AppError::InvalidOrder)));
+    }
+    assert_eq!(order(&db).await, vec![1, 2, 3]);
+}
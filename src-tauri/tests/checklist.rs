@@ -0,0 +1,67 @@
+use app_lib::{
+    commands::checklist::{CreateChecklistItemInput, UpdateChecklistItemOrdinalInput},
+    database::{activity, checklist},
+};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("checklist.sql"))]
+async fn insert_checklist_item_appends_to_end(db: SqlitePool) {
+    let db = db.into();
+
+    let res = checklist::Mutation::insert_checklist_item(
+        &db,
+        CreateChecklistItemInput {
+            activity_id: 2,
+            text: "e".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res.ordinal, 1);
+    assert!(!res.done);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("checklist.sql"))]
+async fn reorder_and_delete_keep_ordinals_dense(db: SqlitePool) {
+    let db = db.into();
+
+    checklist::Mutation::update_checklist_item_ordinal(
+        &db,
+        UpdateChecklistItemOrdinalInput { id: 3, new_ord: 0 },
+    )
+    .await
+    .unwrap();
+    let items = checklist::Query::all_checklist_items(&db, 1).await.unwrap();
+    assert_eq!(items[&3].ordinal, 0);
+    assert_eq!(items[&1].ordinal, 1);
+    assert_eq!(items[&2].ordinal, 2);
+
+    checklist::Mutation::delete_checklist_item_by_id(&db, 1)
+        .await
+        .unwrap();
+    let items = checklist::Query::all_checklist_items(&db, 1).await.unwrap();
+    assert_eq!(items[&3].ordinal, 0);
+    assert_eq!(items[&2].ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("checklist.sql"))]
+async fn fetch_reports_checklist_progress(db: SqlitePool) {
+    let db = db.into();
+
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(activities[&1].checklist.completed, 1);
+    assert_eq!(activities[&1].checklist.total, 3);
+    assert_eq!(activities[&2].checklist.completed, 0);
+    assert_eq!(activities[&2].checklist.total, 1);
+
+    activity::Mutation::delete_activity_by_id(&db, 1)
+        .await
+        .unwrap();
+    assert!(checklist::Query::all_checklist_items(&db, 1)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,65 @@
+use app_lib::{
+    errors::AppError,
+    utils::coloring::{
+        int_to_rgb, rgb_int_to_string, rgb_string_to_int, text_color, DARK_TEXT_COLOR,
+        LIGHT_TEXT_COLOR,
+    },
+};
+
+#[test]
+fn rgb_string_to_int_parses_css_notations() {
+    let cases = [
+        ("#1a2B3c", 0x1a2b3c),
+        ("1A2B3C", 0x1a2b3c),
+        ("#fa0", 0xffaa00),
+        ("rgb(26, 43, 60)", 0x1a2b3c),
+        ("RGB(26 43 60)", 0x1a2b3c),
+        ("hsl(0, 100%, 50%)", 0xff0000),
+        ("hsl(120deg 100% 25%)", 0x008000),
+        ("hsl(240, 100%, 50%)", 0x0000ff),
+        ("hsl(0, 0%, 100%)", 0xffffff),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(rgb_string_to_int(input).unwrap(), expected, "{input}");
+    }
+}
+
+#[test]
+fn rgb_string_to_int_rejects_invalid_colors() {
+    let cases = [
+        "",
+        "#",
+        "#12345",
+        "#12345g",
+        "rgb(256, 0, 0)",
+        "rgb(1, 2)",
+        "hsl(0, 100, 50%)",
+        "hsl(0, 100%, 101%)",
+    ];
+    for input in cases {
+        assert!(
+            matches!(rgb_string_to_int(input), Err(AppError::InvalidColor)),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn rgb_int_to_string_round_trips() {
+    for color in [0x000000, 0x050505, 0x0a0b0c, 0x1a2b3c, 0xffffff] {
+        let string = rgb_int_to_string(color);
+        assert_eq!(string.len(), 7);
+        assert_eq!(rgb_string_to_int(&string).unwrap(), color);
+    }
+    assert_eq!(rgb_int_to_string(0x050505), "#050505");
+    assert_eq!(int_to_rgb(0x1a2b3c), (0x1a, 0x2b, 0x3c));
+}
+
+#[test]
+fn text_color_maximizes_contrast() {
+    assert_eq!(text_color(0xffffff), DARK_TEXT_COLOR);
+    assert_eq!(text_color(0xffff00), DARK_TEXT_COLOR);
+    assert_eq!(text_color(0x000000), LIGHT_TEXT_COLOR);
+    assert_eq!(text_color(0x0000ff), LIGHT_TEXT_COLOR);
+    assert_eq!(text_color(0x777777), DARK_TEXT_COLOR);
+}
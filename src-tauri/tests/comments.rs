@@ -0,0 +1,59 @@
+use app_lib::{
+    commands::comments::{AddCommentInput, EditCommentInput},
+    database::{activity, comments},
+};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("comments.sql"))]
+async fn add_and_edit_comment(db: SqlitePool) {
+    let db = db.into();
+
+    let res = comments::Mutation::insert_comment(
+        &db,
+        AddCommentInput {
+            activity_id: 2,
+            author: "Alex".into(),
+            text: "hello".into(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(res.created_at, res.updated_at);
+
+    comments::Mutation::update_comment_text(
+        &db,
+        EditCommentInput {
+            id: 1,
+            text: "edited".into(),
+        },
+    )
+    .await
+    .unwrap();
+    let all = comments::Query::all_activity_comments(&db, 1)
+        .await
+        .unwrap();
+    assert_eq!(all[&1].text, "edited");
+    assert!(all[&1].updated_at > all[&1].created_at);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("comments.sql"))]
+async fn comment_count_follows_activity(db: SqlitePool) {
+    let db = db.into();
+
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(activities[&1].comment_count, 2);
+    assert_eq!(activities[&2].comment_count, 0);
+
+    comments::Mutation::delete_comment_by_id(&db, 2)
+        .await
+        .unwrap();
+    activity::Mutation::delete_activity_by_id(&db, 1)
+        .await
+        .unwrap();
+    assert!(comments::Query::all_activity_comments(&db, 1)
+        .await
+        .unwrap()
+        .is_empty());
+}
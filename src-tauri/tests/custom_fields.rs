@@ -0,0 +1,105 @@
+use app_lib::{
+    commands::custom_fields::{CreateCustomFieldInput, SetActivityFieldValueInput},
+    database::{activity, custom_fields},
+    errors::AppError,
+};
+use entity::sea_orm_active_enums::FieldKind;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+fn value(activity_id: i32, field_id: i32, value: Value) -> SetActivityFieldValueInput {
+    SetActivityFieldValueInput {
+        activity_id,
+        field_id,
+        value,
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("custom_fields.sql"))]
+async fn create_field_with_options(db: SqlitePool) {
+    let db = db.into();
+
+    let res = custom_fields::Mutation::insert_custom_field(
+        &db,
+        CreateCustomFieldInput {
+            board_id: 1,
+            name: "Stage".into(),
+            kind: FieldKind::SingleSelect,
+            options: vec!["Draft".into(), "Final".into()],
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(res.ordinal, 3);
+
+    let (fields, options) = custom_fields::Query::all_with_options(&db, Some(1))
+        .await
+        .unwrap();
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[&res.id].options.len(), 2);
+    let draft = fields[&res.id].options[0];
+    assert_eq!(options[&draft].name, "Draft");
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("custom_fields.sql"))]
+async fn set_values_appear_in_fetch_output(db: SqlitePool) {
+    let db = db.into();
+
+    for input in [
+        value(1, 1, json!(2.5)),
+        value(1, 2, json!(2)),
+        value(1, 3, json!("2026-10-18")),
+    ] {
+        custom_fields::Mutation::set_activity_field_value(&db, input)
+            .await
+            .unwrap();
+    }
+
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    let values = &activities[&1].field_values;
+    assert_eq!(values[&1], json!(2.5));
+    assert_eq!(values[&2], json!(2));
+    assert_eq!(values[&3], json!("2026-10-18"));
+    assert!(activities[&2].field_values.is_empty());
+
+    custom_fields::Mutation::set_activity_field_value(&db, value(1, 1, Value::Null))
+        .await
+        .unwrap();
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert!(!activities[&1].field_values.contains_key(&1));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("custom_fields.sql"))]
+async fn reject_values_of_wrong_kind(db: SqlitePool) {
+    let db = db.into();
+
+    let invalid = [
+        value(1, 1, json!("five")),
+        value(1, 3, json!("tomorrow")),
+        value(1, 2, json!(3)),
+    ];
+    for input in invalid {
+        let res = custom_fields::Mutation::set_activity_field_value(&db, input).await;
+        assert!(matches!(res, Err(AppError::InvalidFieldValue)));
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("custom_fields.sql"))]
+async fn delete_field_shifts_ordinals(db: SqlitePool) {
+    let db = db.into();
+
+    custom_fields::Mutation::delete_custom_field_by_id(&db, 1)
+        .await
+        .unwrap();
+
+    let (fields, options) = custom_fields::Query::all_with_options(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(fields[&2].ordinal, 0);
+    assert_eq!(fields[&3].ordinal, 1);
+    assert_eq!(options.len(), 2);
+}
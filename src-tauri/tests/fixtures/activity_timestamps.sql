@@ -0,0 +1,5 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1), (2, 'Col2', 1, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id", "created_at", "updated_at", "column_entered_at")
+VALUES
+    (1, '1', '', 1, 0, 1, '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z'),
+    (2, '2', '', 1, 1, 1, '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');
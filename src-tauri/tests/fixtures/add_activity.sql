@@ -1 +1 @@
-INSERT INTO "columns" ("id", "name", "ordinal") VALUES (1, 'New', 0);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'New', 0, 1);
@@ -1,2 +1,2 @@
-INSERT INTO "columns" ("id", "name", "ordinal") VALUES (1, 'New', 0);
-INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal") VALUES (1, 'Profit', 'The $$$ will start rolling in', 1, 0);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'New', 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id") VALUES (1, 'Profit', 'The $$$ will start rolling in', 1, 0, 1);
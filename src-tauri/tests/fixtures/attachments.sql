@@ -0,0 +1,5 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1);
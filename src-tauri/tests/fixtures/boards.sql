@@ -0,0 +1,10 @@
+INSERT INTO "boards" ("id", "name", "ordinal") VALUES (2, 'Side project', 1);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1), (2, 'Col2', 0, 2);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 2, 0, 2),
+    (4, '4', '', NULL, 0, 1),
+    (5, '5', '', NULL, 0, 2);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id") VALUES (1, 'Size', 0, 1), (2, 'Team', 0, 2);
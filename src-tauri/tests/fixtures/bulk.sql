@@ -0,0 +1,17 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "wip_limit")
+VALUES
+    (1, 'Todo', 0, 1, NULL),
+    (2, 'Doing', 1, 1, NULL),
+    (3, 'Review', 2, 1, 2);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id", "archived_at")
+VALUES
+    (1, '1', '', 1, 0, 1, NULL),
+    (2, '2', '', 1, 1, 1, NULL),
+    (3, '3', '', 1, 2, 1, NULL),
+    (4, '4', '', 1, 3, 1, NULL),
+    (5, '5', '', 2, 0, 1, NULL),
+    (6, '6', '', 3, 0, 1, NULL),
+    (7, '7', '', NULL, 0, 1, '2026-10-01T00:00:00Z');
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES (1, 'Work', NULL, 0, 0);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id") VALUES (1, 1);
@@ -0,0 +1,5 @@
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id")
+VALUES
+    (1, 'Size', 0, 1),
+    (2, 'Area', 1, 1),
+    (3, 'Team', 2, 1);
@@ -0,0 +1,11 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1);
+INSERT INTO "checklist_items" ("id", "activity_id", "text", "done", "ordinal")
+VALUES
+    (1, 1, 'a', TRUE, 0),
+    (2, 1, 'b', FALSE, 1),
+    (3, 1, 'c', FALSE, 2),
+    (4, 2, 'd', FALSE, 0);
@@ -0,0 +1,9 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1);
+INSERT INTO "activity_comments" ("id", "activity_id", "author", "text", "created_at", "updated_at")
+VALUES
+    (1, 1, 'Alex', 'first', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z'),
+    (2, 1, 'Sam', 'second', '2026-01-02T00:00:00Z', '2026-01-02T00:00:00Z');
@@ -0,0 +1,14 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1);
+INSERT INTO "custom_fields" ("id", "board_id", "name", "kind", "ordinal")
+VALUES
+    (1, 1, 'Estimate', 'number', 0),
+    (2, 1, 'Owner', 'single_select', 1),
+    (3, 1, 'Released', 'date', 2);
+INSERT INTO "custom_field_options" ("id", "field_id", "name", "ordinal")
+VALUES
+    (1, 2, 'Alex', 0),
+    (2, 2, 'Sam', 1);
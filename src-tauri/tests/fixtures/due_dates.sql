@@ -0,0 +1,10 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1), (2, 'Col2', 1, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id", "due_date", "archived_at", "start_date")
+VALUES
+    (1, '1', '', 1, 0, 1, '2026-10-20', NULL, '2026-10-01'),
+    (2, '2', '', 1, 1, 1, '2026-10-18', NULL, NULL),
+    (3, '3', '', 2, 0, 1, '2026-10-10', NULL, NULL),
+    (4, '4', '', NULL, 0, 1, '2026-10-15', NULL, NULL),
+    (5, '5', '', NULL, 0, 1, '2026-10-01', '2026-10-02T10:00:00Z', NULL),
+    (6, '6', '', 2, 1, 1, '2026-10-30', NULL, NULL),
+    (7, '7', '', 2, 2, 1, NULL, NULL, NULL);
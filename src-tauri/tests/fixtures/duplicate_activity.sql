@@ -0,0 +1,16 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1), (2, 'Col2', 1, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', 'body', 1, 1, 1),
+    (3, '3', '', 1, 2, 1),
+    (4, '4', '', 2, 0, 1);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'Work', NULL, 0, 0),
+    (2, 'Home', NULL, 0, 1);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id") VALUES (2, 1), (2, 2);
+INSERT INTO "checklist_items" ("id", "activity_id", "text", "done", "ordinal")
+VALUES
+    (1, 2, 'first', 1, 0),
+    (2, 2, 'second', 0, 1);
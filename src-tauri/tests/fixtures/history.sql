@@ -0,0 +1,8 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id")
+VALUES
+    (1, 'Todo', 0, 1),
+    (2, 'Doing', 1, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES (1, 'Existing', '', 2, 0, 1);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES (1, 'Work', NULL, 0, 0);
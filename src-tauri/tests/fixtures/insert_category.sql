@@ -1 +1 @@
-INSERT INTO "categories" ("id", "name", "ordinal") VALUES (1, 'Size', 0);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id") VALUES (1, 'Size', 0, 1);
@@ -0,0 +1,12 @@
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id", "palette")
+VALUES
+    (1, 'Area', 0, 1, 'hash'),
+    (2, 'Team', 1, 1, 'fixed'),
+    (3, 'Size', 2, 1, 'spaced');
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'frontend', 1, 0, 0),
+    (2, 'red', 2, 15079755, 0),
+    (3, 's', 3, 0, 0),
+    (4, 'm', 3, 0, 1),
+    (5, 'l', 3, 0, 2);
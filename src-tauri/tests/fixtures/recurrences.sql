@@ -0,0 +1,18 @@
+INSERT INTO "boards" ("id", "name", "ordinal") VALUES (2, 'Home', 1);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Todo', 0, 1), (2, 'Later', 1, 1), (3, 'Todo', 0, 2);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, 'Dependency audit', 'Run cargo audit', NULL, 0, 1),
+    (2, 'Existing', '', 1, 0, 1),
+    (3, 'Backup', '', 2, 0, 1);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id", "selection")
+VALUES
+    (1, 'Team', 0, 1, 'multi'),
+    (2, 'Size', 1, 1, 'single');
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'Backend', 1, 0, 0),
+    (2, 'Small', 2, 0, 0),
+    (3, 'Large', 2, 0, 1);
+-- The template got both sizes before the category became single-select.
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id") VALUES (1, 1), (1, 2), (1, 3);
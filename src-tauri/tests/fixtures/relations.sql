@@ -0,0 +1,10 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "is_done") VALUES (1, 'Todo', 0, 1, FALSE), (2, 'Done', 1, 1, TRUE);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 1, 2, 1);
+INSERT INTO "activity_relations" ("activity_id", "related_activity_id", "kind")
+VALUES
+    (1, 2, 'blocks'),
+    (2, 3, 'blocks');
@@ -0,0 +1,30 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "requires_triage")
+VALUES
+    (1, 'Todo', 0, 1, FALSE),
+    (2, 'Doing', 1, 1, TRUE);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id", "is_required", "deleted_at")
+VALUES
+    (1, 'Type', 0, 1, TRUE, NULL),
+    (2, 'Area', 1, 1, TRUE, NULL),
+    (3, 'Misc', 2, 1, FALSE, NULL),
+    (4, 'Old', 3, 1, TRUE, '2026-10-01T00:00:00Z');
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal", "deleted_at")
+VALUES
+    (1, 'bug', 1, 0, 0, NULL),
+    (2, 'feature', 1, 0, 1, '2026-10-01T00:00:00Z'),
+    (3, 'ui', 2, 0, 0, NULL),
+    (4, 'x', 3, 0, 0, NULL),
+    (5, 'y', 4, 0, 0, NULL);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id", "archived_at")
+VALUES
+    (1, '1', '', 1, 0, 1, NULL),
+    (2, '2', '', 1, 1, 1, NULL),
+    (3, '3', '', 1, 2, 1, NULL),
+    (4, '4', '', NULL, 0, 1, '2026-10-01T00:00:00Z');
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (1, 1),
+    (1, 3),
+    (2, 2),
+    (2, 3),
+    (3, 4);
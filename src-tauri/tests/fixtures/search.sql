@@ -0,0 +1,14 @@
+INSERT INTO "boards" ("id", "name", "ordinal") VALUES (2, 'Other', 1);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Todo', 0, 1), (2, 'Todo', 0, 2);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id", "archived_at")
+VALUES
+    (1, 'Deployment pipeline', 'Write the release notes', 1, 0, 1, NULL),
+    (2, 'Landing page', 'Mention the deployment in the notes of the release', 1, 1, 1, NULL),
+    (3, 'Deployment docs', '', 2, 0, 2, NULL),
+    (4, 'Old deployment', '', NULL, 0, 1, '2026-10-01T00:00:00Z'),
+    (5, 'Onboarding', '', 1, 2, 1, NULL),
+    (6, 'Escape <b> & "quotes"', '', 1, 3, 1, NULL);
+INSERT INTO "activity_comments" ("id", "activity_id", "author", "text", "created_at", "updated_at")
+VALUES (1, 5, 'ann', 'Waiting for the welcome email copy', '2026-10-01T00:00:00Z', '2026-10-01T00:00:00Z');
+INSERT INTO "checklist_items" ("id", "activity_id", "text", "done", "ordinal")
+VALUES (1, 5, 'Order laptops', FALSE, 0);
@@ -0,0 +1,22 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Todo', 0, 1);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id", "selection")
+VALUES
+    (1, 'Priority', 0, 1, 'multi'),
+    (2, 'Area', 1, 1, 'single');
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'high', 1, 0, 0),
+    (2, 'low', 1, 0, 1),
+    (3, 'frontend', 2, 0, 0),
+    (4, 'backend', 2, 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 1, 2, 1);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (1, 2),
+    (1, 1),
+    (2, 2),
+    (3, 3);
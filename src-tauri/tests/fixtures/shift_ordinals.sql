@@ -1,6 +1,6 @@
-INSERT INTO "columns" ("id", "name", "ordinal") VALUES (1, 'Col1', 0);
-INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal")
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
 VALUES
-    (1, '1', '', 1, 0),
-    (2, '2', '', 1, 1),
-    (3, '3', '', 1, 2);
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 1, 2, 1);
@@ -0,0 +1,20 @@
+INSERT INTO "boards" ("id", "name", "ordinal") VALUES (2, 'Home', 1);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "requires_triage")
+VALUES (1, 'Todo', 0, 1, TRUE);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES (1, '1', '', 1, 0, 1);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id", "is_required")
+VALUES
+    (1, 'Team', 0, 1, TRUE),
+    (2, 'Room', 0, 2, FALSE);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal", "deleted_at")
+VALUES
+    (1, 'Backend', 1, 0, 1, NULL),
+    (2, 'Frontend', 1, 0, 0, NULL),
+    (3, 'Legacy', 1, 0, 2, '2026-10-01T00:00:00Z'),
+    (4, 'Kitchen', 2, 0, 0, NULL);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (1, 1),
+    (1, 2),
+    (1, 3);
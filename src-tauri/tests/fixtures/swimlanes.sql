@@ -0,0 +1,27 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id")
+VALUES
+    (1, 'Todo', 0, 1),
+    (2, 'Done', 1, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 1, 2, 1),
+    (4, '4', '', 2, 0, 1),
+    (5, '5', '', NULL, 0, 1);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id")
+VALUES
+    (1, 'Team', 0, 1),
+    (2, 'Size', 1, 1);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'Backend', 1, 0, 1),
+    (2, 'Frontend', 1, 0, 0),
+    (3, 'Small', 2, 0, 0);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (1, 1),
+    (3, 1),
+    (3, 3),
+    (4, 2),
+    (5, 1);
@@ -0,0 +1,21 @@
+INSERT INTO "boards" ("id", "name", "ordinal") VALUES (2, 'Home', 1);
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Todo', 0, 1), (2, 'Todo', 0, 2);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id", "deleted_at")
+VALUES
+    (1, 'Area', 0, 1, NULL),
+    (2, 'Room', 0, 2, NULL),
+    (3, 'Old', 1, 1, '2026-10-01T00:00:00Z');
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'frontend', 1, 0, 0),
+    (2, 'kitchen', 2, 0, 0),
+    (3, 'chore', NULL, 0, 0),
+    (4, 'errand', NULL, 0, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 2, 0, 2);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (1, 3),
+    (2, 4);
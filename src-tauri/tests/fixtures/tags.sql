@@ -0,0 +1,26 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Todo', 0, 1);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id")
+VALUES
+    (1, 'Area', 0, 1),
+    (2, 'Team', 1, 1);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'frontend', 1, 0, 0),
+    (2, 'backend', 1, 0, 1),
+    (3, 'frontend', 2, 0, 0),
+    (4, 'design', 2, 0, 1),
+    (5, 'bug', NULL, 0, 0),
+    (6, 'Bug', NULL, 0, 1),
+    (7, 'chore', NULL, 0, 2);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 1, 2, 1);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (1, 1),
+    (1, 3),
+    (2, 3),
+    (3, 5),
+    (3, 6);
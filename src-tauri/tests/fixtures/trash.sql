@@ -0,0 +1,15 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id") VALUES (1, 'Col1', 0, 1), (2, 'Col2', 1, 1), (3, 'Col3', 2, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 2, 0, 1),
+    (3, '3', '', 2, 1, 1),
+    (4, '4', '', NULL, 0, 1);
+INSERT INTO "categories" ("id", "name", "ordinal", "board_id") VALUES (1, 'Size', 0, 1), (2, 'Priority', 1, 1);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'Small', 1, 0, 0),
+    (2, 'Large', 1, 0, 1),
+    (3, 'High', 2, 0, 0),
+    (4, 'Work', NULL, 0, 0);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id") VALUES (1, 1), (1, 3), (2, 4);
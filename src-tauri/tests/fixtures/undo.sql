@@ -0,0 +1,18 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id")
+VALUES
+    (1, 'Todo', 0, 1),
+    (2, 'Doing', 1, 1);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 1, 2, 1),
+    (4, '4', '', 2, 0, 1);
+INSERT INTO "category_tags" ("id", "tag_name", "category_id", "color", "ordinal")
+VALUES
+    (1, 'Work', NULL, 0, 0),
+    (2, 'Home', NULL, 0, 1);
+INSERT INTO "activity_tags" ("activity_id", "category_tag_id")
+VALUES
+    (2, 1),
+    (2, 2);
@@ -0,0 +1,21 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "wip_limit")
+VALUES
+    (1, 'Todo', 0, 1, NULL),
+    (2, 'Doing', 1, 1, 1),
+    (3, 'Review', 2, 1, NULL);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 1, 1, 1),
+    (3, '3', '', 2, 0, 1),
+    (4, '4', '', 3, 0, 1);
+INSERT INTO "checklist_items" ("id", "activity_id", "text", "done", "ordinal")
+VALUES
+    (1, 1, 'a', TRUE, 0),
+    (2, 1, 'b', FALSE, 1);
+INSERT INTO "activity_comments" ("id", "activity_id", "author", "text", "created_at", "updated_at")
+VALUES (1, 1, 'Alex', 'first', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');
+INSERT INTO "activity_relations" ("activity_id", "related_activity_id", "kind")
+VALUES (1, 2, 'blocks');
+INSERT INTO "attachments" ("id", "activity_id", "name", "size", "mime_type", "content_hash", "created_at")
+VALUES (1, 1, 'notes.txt', 5, 'text/plain', 'abc123', '2026-01-01T00:00:00Z');
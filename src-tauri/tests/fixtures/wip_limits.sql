@@ -0,0 +1,9 @@
+INSERT INTO "columns" ("id", "name", "ordinal", "board_id", "wip_limit")
+VALUES
+    (1, 'Todo', 0, 1, NULL),
+    (2, 'Doing', 1, 1, 2);
+INSERT INTO "activities" ("id", "name", "body", "column_id", "ordinal", "board_id")
+VALUES
+    (1, '1', '', 1, 0, 1),
+    (2, '2', '', 2, 0, 1),
+    (3, '3', '', 2, 1, 1);
@@ -0,0 +1,146 @@
+use app_lib::{
+    commands::{
+        activity::{
+            AddTagToActivityInput, CreateActivityInput, RemoveTagFromActivityInput,
+            UpdateActivityColumnInput, UpdateActivityContentInput,
+        },
+        columns::RenameColumnInput,
+    },
+    database::{activity, columns, history, trash},
+};
+use entity::{
+    events,
+    sea_orm_active_enums::{EventAction, EventEntity},
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("history.sql"))]
+async fn activity_timeline_records_every_mutation(db: SqlitePool) {
+    let db = db.into();
+
+    let created = activity::Mutation::create_activity(
+        &db,
+        CreateActivityInput {
+            name: "Audit".into(),
+            body: None,
+            column_id: 1,
+            start_date: None,
+            due_date: None,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap()
+    .activity;
+    activity::Mutation::update_activity_content_by_id(
+        &db,
+        UpdateActivityContentInput {
+            id: created.id,
+            name: "Dependency audit".into(),
+            body: None,
+            start_date: None,
+            due_date: None,
+        },
+    )
+    .await
+    .unwrap();
+    activity::Mutation::update_activity_column_by_id(
+        &db,
+        UpdateActivityColumnInput {
+            id: created.id,
+            column_id: Some(2),
+            new_ord: 0,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+    activity::Mutation::add_tag_to_activity(
+        &db,
+        AddTagToActivityInput {
+            id: created.id,
+            category_id: None,
+            tag_name: "Work".into(),
+        },
+    )
+    .await
+    .unwrap();
+    activity::Mutation::remove_tag_from_activity(
+        &db,
+        RemoveTagFromActivityInput {
+            id: created.id,
+            category_id: None,
+            tag_name: "Work".into(),
+        },
+    )
+    .await
+    .unwrap();
+    activity::Mutation::delete_activity_by_id(&db, created.id)
+        .await
+        .unwrap();
+
+    let history = history::Query::activity_history(&db, created.id)
+        .await
+        .unwrap();
+    let actions: Vec<EventAction> = history.iter().map(|event| event.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            EventAction::Create,
+            EventAction::Update,
+            EventAction::Move,
+            EventAction::AddTag,
+            EventAction::RemoveTag,
+            EventAction::Delete,
+        ]
+    );
+
+    let moved = &history[2];
+    assert_eq!(moved.before.as_ref().unwrap()["columnId"], 1);
+    assert_eq!(moved.after.as_ref().unwrap()["columnId"], 2);
+    assert_eq!(history[1].before.as_ref().unwrap()["name"], "Audit");
+    assert_eq!(
+        history[1].after.as_ref().unwrap()["name"],
+        "Dependency audit"
+    );
+    assert!(history[5].after.is_none());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("history.sql"))]
+async fn purged_column_records_moves_of_its_activities(db: SqlitePool) {
+    let db = db.into();
+
+    columns::Mutation::update_column_name(
+        &db,
+        RenameColumnInput {
+            id: 2,
+            new_name: "Later".into(),
+        },
+    )
+    .await
+    .unwrap();
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    trash::Mutation::purge_trash(&db, -1).await.unwrap();
+
+    let column_actions: Vec<EventAction> = events::Entity::find()
+        .filter(events::Column::Entity.eq(EventEntity::Column))
+        .filter(events::Column::EntityId.eq(2))
+        .all(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|event| event.action)
+        .collect();
+    assert_eq!(
+        column_actions,
+        vec![EventAction::Update, EventAction::Delete, EventAction::Purge]
+    );
+
+    let history = history::Query::activity_history(&db, 1).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].action, EventAction::Move);
+    assert!(history[0].after.as_ref().unwrap()["columnId"].is_null());
+}
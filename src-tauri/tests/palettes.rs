@@ -0,0 +1,89 @@
+use app_lib::{
+    commands::{
+        category::UpdateCategoryPaletteInput,
+        tags::{CreateTagInput, UpdateTagColorInput},
+    },
+    database::{
+        category, tags,
+        undo::{self, UndoStack},
+    },
+    utils::coloring::{rgb_int_to_string, spaced_colors, string_to_color, FIXED_PALETTE},
+};
+use entity::{category_tags, sea_orm_active_enums::TagPalette};
+use sea_orm::{DbConn, EntityTrait};
+use sqlx::SqlitePool;
+
+async fn color(db: &DbConn, id: i32) -> i32 {
+    category_tags::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .color
+}
+
+fn tag(name: &str, category_id: i32) -> CreateTagInput {
+    CreateTagInput {
+        tag_name: name.into(),
+        category_id: Some(category_id),
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("palettes.sql"))]
+async fn create_tag_avoids_colors_of_siblings(db: SqlitePool) {
+    let db = db.into();
+
+    let data = UpdateTagColorInput {
+        category_tag_id: 1,
+        color: rgb_int_to_string(string_to_color("backend")),
+    };
+    tags::Mutation::update_tag_color(&db, data).await.unwrap();
+    let res = tags::Mutation::create_tag(&db, tag("backend", 1))
+        .await
+        .unwrap();
+    assert_ne!(res.color, string_to_color("backend"));
+
+    let res = tags::Mutation::create_tag(&db, tag("blue", 2))
+        .await
+        .unwrap();
+    assert!(FIXED_PALETTE.contains(&res.color));
+    assert_ne!(res.color, FIXED_PALETTE[0]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("palettes.sql"))]
+async fn recolor_category_can_be_undone(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(&db, category::Mutation::recolor_category(&db, 3))
+        .await
+        .unwrap();
+    let colors = spaced_colors(3);
+    assert_eq!(color(&db, 3).await, colors[0]);
+    assert_eq!(color(&db, 4).await, colors[1]);
+    assert_eq!(color(&db, 5).await, colors[2]);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    for id in 3..=5 {
+        assert_eq!(color(&db, id).await, 0);
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("palettes.sql"))]
+async fn update_category_palette_changes_colors_of_new_tags(db: SqlitePool) {
+    let db = db.into();
+
+    let data = UpdateCategoryPaletteInput {
+        category_id: 1,
+        palette: TagPalette::Fixed,
+    };
+    category::Mutation::update_category_palette(&db, data)
+        .await
+        .unwrap();
+
+    let res = tags::Mutation::create_tag(&db, tag("design", 1))
+        .await
+        .unwrap();
+    assert!(FIXED_PALETTE.contains(&res.color));
+}
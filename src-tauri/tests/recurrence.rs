@@ -0,0 +1,236 @@
+use app_lib::{
+    commands::recurrence::CreateRecurrenceInput,
+    database::{activity, columns, history, recurrence},
+    errors::AppError,
+};
+use entity::sea_orm_active_enums::{EventAction, RecurrenceKind};
+use sqlx::SqlitePool;
+use time::{Date, Month, Weekday};
+
+fn date(day: u8) -> Date {
+    Date::from_calendar_date(2026, Month::October, day).unwrap()
+}
+
+fn rule(kind: RecurrenceKind) -> CreateRecurrenceInput {
+    CreateRecurrenceInput {
+        template_id: 1,
+        column_id: 1,
+        kind,
+        weekdays: Vec::new(),
+        day_of_month: None,
+        interval_days: None,
+        start_date: date(1),
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn weekly_rule_generates_activities_once(db: SqlitePool) {
+    let db = db.into();
+    let input = CreateRecurrenceInput {
+        weekdays: vec![Weekday::Monday, Weekday::Wednesday],
+        ..rule(RecurrenceKind::Weekly)
+    };
+    recurrence::Mutation::insert_recurrence(&db, input)
+        .await
+        .unwrap();
+
+    let generated = recurrence::Mutation::generate_activities(&db, date(12))
+        .await
+        .unwrap();
+    assert_eq!(generated, 3);
+    let generated = recurrence::Mutation::generate_activities(&db, date(12))
+        .await
+        .unwrap();
+    assert_eq!(generated, 0);
+
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    let mut generated: Vec<_> = activities
+        .values()
+        .filter(|activity| activity.name == "Dependency audit")
+        .collect();
+    generated.sort_by_key(|activity| activity.ordinal);
+    let due_dates: Vec<_> = generated.iter().map(|activity| activity.due_date).collect();
+    assert_eq!(
+        due_dates,
+        vec![Some(date(12)), Some(date(7)), Some(date(5))]
+    );
+    assert!(generated.iter().all(
+        |activity| activity.body.as_deref() == Some("Run cargo audit")
+            && activity.tags.contains(&1)
+    ));
+    assert_eq!(activities[&2].ordinal, 3);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn deleted_occurrence_is_not_generated_again(db: SqlitePool) {
+    let db = db.into();
+    let input = CreateRecurrenceInput {
+        interval_days: Some(3),
+        ..rule(RecurrenceKind::EveryNDays)
+    };
+    recurrence::Mutation::insert_recurrence(&db, input)
+        .await
+        .unwrap();
+
+    recurrence::Mutation::generate_activities(&db, date(4))
+        .await
+        .unwrap();
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    let generated: Vec<i32> = activities
+        .iter()
+        .filter(|(_, activity)| activity.due_date.is_some())
+        .map(|(id, _)| *id)
+        .collect();
+    assert_eq!(generated.len(), 2);
+    for id in generated {
+        activity::Mutation::delete_activity_by_id(&db, id)
+            .await
+            .unwrap();
+    }
+
+    let generated = recurrence::Mutation::generate_activities(&db, date(10))
+        .await
+        .unwrap();
+    assert_eq!(generated, 2);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn monthly_rule_uses_last_day_of_short_months(db: SqlitePool) {
+    let db = db.into();
+    let input = CreateRecurrenceInput {
+        day_of_month: Some(31),
+        ..rule(RecurrenceKind::Monthly)
+    };
+    recurrence::Mutation::insert_recurrence(&db, input)
+        .await
+        .unwrap();
+
+    let end = Date::from_calendar_date(2026, Month::November, 30).unwrap();
+    let generated = recurrence::Mutation::generate_activities(&db, end)
+        .await
+        .unwrap();
+    assert_eq!(generated, 2);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn reject_rule_with_hidden_or_foreign_column(db: SqlitePool) {
+    let db = db.into();
+
+    let input = CreateRecurrenceInput {
+        column_id: 3,
+        ..rule(RecurrenceKind::Daily)
+    };
+    let res = recurrence::Mutation::insert_recurrence(&db, input).await;
+    assert!(matches!(res, Err(AppError::BoardMismatch)));
+
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    let input = CreateRecurrenceInput {
+        column_id: 2,
+        ..rule(RecurrenceKind::Daily)
+    };
+    let res = recurrence::Mutation::insert_recurrence(&db, input).await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn reject_rule_without_required_fields(db: SqlitePool) {
+    let db = db.into();
+
+    for kind in [
+        RecurrenceKind::Weekly,
+        RecurrenceKind::Monthly,
+        RecurrenceKind::EveryNDays,
+    ] {
+        let res = recurrence::Mutation::insert_recurrence(&db, rule(kind)).await;
+        assert!(matches!(res, Err(AppError::InvalidRecurrence)));
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn generated_tags_are_recorded_and_respect_selection(db: SqlitePool) {
+    let db = db.into();
+    recurrence::Mutation::insert_recurrence(&db, rule(RecurrenceKind::Daily))
+        .await
+        .unwrap();
+    recurrence::Mutation::generate_activities(&db, date(1))
+        .await
+        .unwrap();
+
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    let (id, generated) = activities
+        .iter()
+        .find(|(_, activity)| activity.due_date.is_some())
+        .unwrap();
+    let mut tags = generated.tags.clone();
+    tags.sort();
+    assert_eq!(tags, vec![1, 2]);
+
+    let history = history::Query::activity_history(&db, *id).await.unwrap();
+    assert_eq!(history[0].action, EventAction::Create);
+    let mut added: Vec<i64> = history
+        .iter()
+        .filter(|event| event.action == EventAction::AddTag)
+        .map(|event| {
+            event.after.as_ref().unwrap()["categoryTagId"]
+                .as_i64()
+                .unwrap()
+        })
+        .collect();
+    added.sort();
+    assert_eq!(added, vec![1, 2]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn catch_up_is_limited(db: SqlitePool) {
+    let db = db.into();
+    recurrence::Mutation::insert_recurrence(&db, rule(RecurrenceKind::Daily))
+        .await
+        .unwrap();
+
+    let end = Date::from_calendar_date(2026, Month::December, 31).unwrap();
+    let generated = recurrence::Mutation::generate_activities(&db, end)
+        .await
+        .unwrap();
+    assert_eq!(generated, 31);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("recurrences.sql"))]
+async fn hidden_templates_are_skipped(db: SqlitePool) {
+    let db = db.into();
+    for template_id in [1, 2, 3] {
+        let input = CreateRecurrenceInput {
+            template_id,
+            ..rule(RecurrenceKind::Daily)
+        };
+        recurrence::Mutation::insert_recurrence(&db, input)
+            .await
+            .unwrap();
+    }
+
+    activity::Mutation::archive_activity_by_id(&db, 1)
+        .await
+        .unwrap();
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    let generated = recurrence::Mutation::generate_activities(&db, date(2))
+        .await
+        .unwrap();
+    assert_eq!(generated, 2);
+
+    columns::Mutation::restore_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    let generated = recurrence::Mutation::generate_activities(&db, date(2))
+        .await
+        .unwrap();
+    assert_eq!(generated, 2);
+}
@@ -0,0 +1,139 @@
+use app_lib::{
+    commands::{
+        activity::UpdateActivityColumnInput,
+        relations::{LinkActivitiesInput, UnlinkActivitiesInput},
+    },
+    database::{activity, relations},
+    errors::AppError,
+};
+use entity::sea_orm_active_enums::RelationKind;
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("relations.sql"))]
+async fn link_that_closes_cycle_is_refused(db: SqlitePool) {
+    let db = db.into();
+
+    let res = relations::Mutation::link_activities(
+        &db,
+        LinkActivitiesInput {
+            activity_id: 3,
+            related_activity_id: 1,
+            kind: RelationKind::Blocks,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RelationCycle)));
+
+    relations::Mutation::link_activities(
+        &db,
+        LinkActivitiesInput {
+            activity_id: 3,
+            related_activity_id: 1,
+            kind: RelationKind::RelatesTo,
+        },
+    )
+    .await
+    .unwrap();
+
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(activities[&2].relations.blocks, vec![3]);
+    assert_eq!(activities[&2].relations.blocked_by, vec![1]);
+    assert_eq!(activities[&1].relations.related, vec![3]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("relations.sql"))]
+async fn blocked_activity_cannot_enter_done_column(db: SqlitePool) {
+    let db = db.into();
+    let move_to_done = |id| UpdateActivityColumnInput {
+        id,
+        column_id: Some(2),
+        new_ord: 0,
+        allow_over_limit: false,
+    };
+
+    let res = activity::Mutation::update_activity_column_by_id(&db, move_to_done(2)).await;
+    assert!(matches!(res, Err(AppError::ActivityBlocked)));
+
+    activity::Mutation::update_activity_column_by_id(&db, move_to_done(1))
+        .await
+        .unwrap();
+    activity::Mutation::update_activity_column_by_id(&db, move_to_done(2))
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("relations.sql"))]
+async fn unlink_missing_relation_fails(db: SqlitePool) {
+    let db = db.into();
+
+    relations::Mutation::unlink_activities(
+        &db,
+        UnlinkActivitiesInput {
+            activity_id: 1,
+            related_activity_id: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = relations::Mutation::unlink_activities(
+        &db,
+        UnlinkActivitiesInput {
+            activity_id: 1,
+            related_activity_id: 2,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("relations.sql"))]
+async fn links_are_kept_once_per_pair(db: SqlitePool) {
+    let db = db.into();
+    let link = |activity_id, related_activity_id, kind| LinkActivitiesInput {
+        activity_id,
+        related_activity_id,
+        kind,
+    };
+
+    relations::Mutation::link_activities(&db, link(3, 1, RelationKind::RelatesTo))
+        .await
+        .unwrap();
+    relations::Mutation::link_activities(&db, link(1, 3, RelationKind::RelatesTo))
+        .await
+        .unwrap();
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(activities[&1].relations.related, vec![3]);
+    assert_eq!(activities[&3].relations.related, vec![1]);
+
+    // Reversing a link replaces it instead of closing a cycle.
+    relations::Mutation::link_activities(&db, link(2, 1, RelationKind::Blocks))
+        .await
+        .unwrap();
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(activities[&2].relations.blocks, vec![3, 1]);
+    assert!(activities[&2].relations.blocked_by.is_empty());
+
+    for (activity_id, related_activity_id) in [(3, 1), (1, 2)] {
+        relations::Mutation::unlink_activities(
+            &db,
+            UnlinkActivitiesInput {
+                activity_id,
+                related_activity_id,
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert!(activities[&1].relations.related.is_empty());
+    assert!(activities[&1].relations.blocked_by.is_empty());
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use app_lib::{
+    commands::{
+        activity::{CreateActivityInput, DuplicateActivityInput, UpdateActivityColumnInput},
+        bulk::{BulkItemOutput, BulkMoveActivitiesInput},
+        category::UpdateCategoryRequiredInput,
+        columns::UpdateColumnTriageInput,
+    },
+    database::{activity, bulk, category, columns},
+    errors::AppError,
+};
+use entity::activities;
+use sea_orm::{EntityTrait, PaginatorTrait};
+use sqlx::SqlitePool;
+
+fn move_to_triage(id: i32) -> UpdateActivityColumnInput {
+    UpdateActivityColumnInput {
+        id,
+        column_id: Some(2),
+        new_ord: 0,
+        allow_over_limit: false,
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("required_categories.sql"))]
+async fn activities_missing_required_tags_are_reported(db: SqlitePool) {
+    let db = db.into();
+
+    let res = category::Query::activities_missing_required_tags(&db, 1)
+        .await
+        .unwrap();
+    assert_eq!(res, HashMap::from([(2, vec![1]), (3, vec![1, 2])]));
+
+    let data = UpdateCategoryRequiredInput {
+        category_id: 2,
+        is_required: false,
+    };
+    category::Mutation::update_category_required(&db, data)
+        .await
+        .unwrap();
+    let res = category::Query::activities_missing_required_tags(&db, 1)
+        .await
+        .unwrap();
+    assert_eq!(res, HashMap::from([(2, vec![1]), (3, vec![1])]));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("required_categories.sql"))]
+async fn move_into_triage_column_requires_tags(db: SqlitePool) {
+    let db = db.into();
+
+    let res = activity::Mutation::update_activity_column_by_id(&db, move_to_triage(3)).await;
+    assert!(matches!(
+        res,
+        Err(AppError::MissingRequiredTags { category_ids }) if category_ids == vec![1, 2]
+    ));
+    activity::Mutation::update_activity_column_by_id(&db, move_to_triage(1))
+        .await
+        .unwrap();
+
+    let data = UpdateColumnTriageInput {
+        id: 2,
+        requires_triage: false,
+    };
+    columns::Mutation::update_column_triage(&db, data)
+        .await
+        .unwrap();
+    activity::Mutation::update_activity_column_by_id(&db, move_to_triage(3))
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("required_categories.sql"))]
+async fn bulk_move_skips_activities_missing_required_tags(db: SqlitePool) {
+    let db = db.into();
+
+    let data = BulkMoveActivitiesInput {
+        ids: vec![1, 2],
+        column_id: 2,
+        new_ord: 0,
+    };
+    let res = bulk::Mutation::move_activities(&db, data).await.unwrap();
+    assert!(matches!(res[&1], BulkItemOutput::Done));
+    assert!(matches!(
+        &res[&2],
+        BulkItemOutput::Skipped(AppError::MissingRequiredTags { category_ids }) if *category_ids == vec![1]
+    ));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("required_categories.sql"))]
+async fn new_activities_in_triage_column_require_tags(db: SqlitePool) {
+    let db = db.into();
+
+    let res = activity::Mutation::create_activity(
+        &db,
+        CreateActivityInput {
+            name: "5".into(),
+            body: None,
+            column_id: 2,
+            start_date: None,
+            due_date: None,
+            allow_over_limit: false,
+        },
+    )
+    .await;
+    assert!(matches!(
+        res,
+        Err(AppError::MissingRequiredTags { category_ids }) if category_ids == vec![1, 2]
+    ));
+
+    let res = activity::Mutation::duplicate_activity(
+        &db,
+        DuplicateActivityInput {
+            id: 2,
+            column_id: Some(2),
+            name_suffix: None,
+        },
+    )
+    .await;
+    assert!(matches!(
+        res,
+        Err(AppError::MissingRequiredTags { category_ids }) if category_ids == vec![1]
+    ));
+    assert_eq!(activities::Entity::find().count(&db).await.unwrap(), 4);
+
+    let copy = activity::Mutation::duplicate_activity(
+        &db,
+        DuplicateActivityInput {
+            id: 1,
+            column_id: Some(2),
+            name_suffix: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(copy.column_id, Some(2));
+}
@@ -0,0 +1,135 @@
+use app_lib::{
+    commands::{
+        activity::UpdateActivityContentInput, checklist::CreateChecklistItemInput,
+        comments::EditCommentInput, search::SearchActivitiesInput,
+    },
+    database::{activity, checklist, comments, search::Query},
+    utils::search::{to_fts_query, to_highlighted_html, MATCH_END, MATCH_START},
+};
+use sea_orm::DbConn;
+use sqlx::SqlitePool;
+
+async fn search(db: &DbConn, query: &str, board_id: Option<i32>) -> Vec<i32> {
+    let data = SearchActivitiesInput {
+        query: query.into(),
+        board_id,
+        limit: None,
+    };
+    Query::search_activities(db, data)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|hit| hit.activity_id)
+        .collect()
+}
+
+#[test]
+fn to_fts_query_quotes_terms() {
+    assert_eq!(
+        to_fts_query(r#"deploy* "release notes" AND"#).unwrap(),
+        r#""deploy"* "release notes" "AND""#
+    );
+    assert_eq!(
+        to_fts_query(r#""release no"* x"#).unwrap(),
+        r#""release no"* "x""#
+    );
+    assert_eq!(to_fts_query(r#" " * - ( "#), None);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("search.sql"))]
+async fn search_ranks_name_matches_first(db: SqlitePool) {
+    let db = db.into();
+
+    assert_eq!(search(&db, "deployment", Some(1)).await, vec![1, 2]);
+    assert_eq!(search(&db, "deployment", None).await.len(), 3);
+
+    let data = SearchActivitiesInput {
+        query: "deployment".into(),
+        board_id: Some(1),
+        limit: Some(1),
+    };
+    let hits = Query::search_activities(&db, data).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].name, "<mark>Deployment</mark> pipeline");
+    assert_eq!(hits[0].column_id, Some(1));
+}
+
+#[test]
+fn to_highlighted_html_escapes_text() {
+    let text = format!("a<b> & {MATCH_START}\"c\"{MATCH_END}'");
+    assert_eq!(
+        to_highlighted_html(&text),
+        "a&lt;b&gt; &amp; <mark>&quot;c&quot;</mark>&#39;"
+    );
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("search.sql"))]
+async fn search_escapes_highlighted_text(db: SqlitePool) {
+    let db = db.into();
+
+    let data = SearchActivitiesInput {
+        query: "quotes".into(),
+        board_id: None,
+        limit: None,
+    };
+    let hits = Query::search_activities(&db, data).await.unwrap();
+    assert_eq!(
+        hits[0].name,
+        "Escape &lt;b&gt; &amp; &quot;<mark>quotes</mark>&quot;"
+    );
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("search.sql"))]
+async fn search_supports_prefix_and_phrase_queries(db: SqlitePool) {
+    let db = db.into();
+
+    assert_eq!(search(&db, "deplo", Some(1)).await, Vec::<i32>::new());
+    assert_eq!(search(&db, "deplo*", Some(1)).await, vec![1, 2]);
+    assert_eq!(search(&db, r#""release notes""#, None).await, vec![1]);
+    assert_eq!(search(&db, "release notes", None).await.len(), 2);
+    assert_eq!(search(&db, r#"""#, None).await, Vec::<i32>::new());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("search.sql"))]
+async fn search_index_follows_mutations(db: SqlitePool) {
+    let db = db.into();
+
+    assert_eq!(search(&db, "welcome", None).await, vec![5]);
+    assert_eq!(search(&db, "laptops", None).await, vec![5]);
+
+    let data = EditCommentInput {
+        id: 1,
+        text: "Done".into(),
+    };
+    comments::Mutation::update_comment_text(&db, data)
+        .await
+        .unwrap();
+    assert_eq!(search(&db, "welcome", None).await, Vec::<i32>::new());
+
+    let data = CreateChecklistItemInput {
+        activity_id: 1,
+        text: "Order monitors".into(),
+    };
+    checklist::Mutation::insert_checklist_item(&db, data)
+        .await
+        .unwrap();
+    assert_eq!(search(&db, "order", None).await.len(), 2);
+
+    let data = UpdateActivityContentInput {
+        id: 2,
+        name: "Marketing site".into(),
+        body: None,
+        start_date: None,
+        due_date: None,
+    };
+    activity::Mutation::update_activity_content_by_id(&db, data)
+        .await
+        .unwrap();
+    assert_eq!(search(&db, "marketing", None).await, vec![2]);
+    assert_eq!(search(&db, "landing", None).await, Vec::<i32>::new());
+
+    activity::Mutation::delete_activity_by_id(&db, 5)
+        .await
+        .unwrap();
+    assert_eq!(search(&db, "laptops", None).await, Vec::<i32>::new());
+}
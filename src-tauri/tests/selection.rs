@@ -0,0 +1,186 @@
+use app_lib::{
+    commands::{
+        activity::AddTagToActivityInput,
+        bulk::BulkTagInput,
+        category::UpdateCategorySelectionInput,
+        tags::{AttachTagToCategoryInput, MergeTagsInput},
+    },
+    database::{
+        activity, bulk, category, tags,
+        undo::{self, UndoStack},
+    },
+    errors::AppError,
+};
+use entity::{activity_tags, categories, sea_orm_active_enums::SelectionMode};
+use sea_orm::{DbConn, EntityTrait};
+use sqlx::SqlitePool;
+
+async fn links(db: &DbConn) -> Vec<(i32, i32)> {
+    let mut res: Vec<(i32, i32)> = activity_tags::Entity::find()
+        .all(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|link| (link.activity_id, link.category_tag_id))
+        .collect();
+    res.sort();
+    res
+}
+
+async fn selection(db: &DbConn, id: i32) -> SelectionMode {
+    categories::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+        .selection
+}
+
+fn single(resolve: bool) -> UpdateCategorySelectionInput {
+    UpdateCategorySelectionInput {
+        category_id: 1,
+        selection: SelectionMode::Single,
+        resolve,
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn add_tag_replaces_tag_of_single_select_category(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let data = AddTagToActivityInput {
+        id: 3,
+        category_id: Some(2),
+        tag_name: "backend".into(),
+    };
+    stack
+        .record(&db, activity::Mutation::add_tag_to_activity(&db, data))
+        .await
+        .unwrap();
+    assert_eq!(links(&db).await, vec![(1, 1), (1, 2), (2, 2), (3, 4)]);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(links(&db).await, vec![(1, 1), (1, 2), (2, 2), (3, 3)]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn add_tag_keeps_tags_of_multi_select_category(db: SqlitePool) {
+    let db = db.into();
+
+    let data = AddTagToActivityInput {
+        id: 2,
+        category_id: Some(1),
+        tag_name: "high".into(),
+    };
+    activity::Mutation::add_tag_to_activity(&db, data)
+        .await
+        .unwrap();
+    assert_eq!(
+        links(&db).await,
+        vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 3)]
+    );
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn bulk_add_tag_replaces_tag_of_single_select_category(db: SqlitePool) {
+    let db = db.into();
+
+    let data = BulkTagInput {
+        ids: vec![1, 3],
+        tag_id: 4,
+    };
+    bulk::Mutation::add_tag(&db, data).await.unwrap();
+    assert_eq!(
+        links(&db).await,
+        vec![(1, 1), (1, 2), (1, 4), (2, 2), (3, 4)]
+    );
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn update_category_selection_reports_conflicts(db: SqlitePool) {
+    let db = db.into();
+
+    let res = category::Mutation::update_category_selection(&db, single(false)).await;
+    assert!(matches!(
+        res,
+        Err(AppError::SelectionConflict { activity_ids }) if activity_ids == vec![1]
+    ));
+    assert_eq!(selection(&db, 1).await, SelectionMode::Multi);
+    assert_eq!(links(&db).await, vec![(1, 1), (1, 2), (2, 2), (3, 3)]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn update_category_selection_resolves_conflicts(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let res = stack
+        .record(
+            &db,
+            category::Mutation::update_category_selection(&db, single(true)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res, vec![1]);
+    assert_eq!(selection(&db, 1).await, SelectionMode::Single);
+    assert_eq!(links(&db).await, vec![(1, 1), (2, 2), (3, 3)]);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(selection(&db, 1).await, SelectionMode::Multi);
+    assert_eq!(links(&db).await, vec![(1, 1), (1, 2), (2, 2), (3, 3)]);
+}
+
+async fn add_high_to_third(db: &DbConn) {
+    let data = AddTagToActivityInput {
+        id: 3,
+        category_id: Some(1),
+        tag_name: "high".into(),
+    };
+    activity::Mutation::add_tag_to_activity(db, data)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn merge_into_tag_of_single_select_category_replaces_tags(db: SqlitePool) {
+    let db = db.into();
+    add_high_to_third(&db).await;
+
+    let data = MergeTagsInput {
+        source_ids: vec![1],
+        target_id: 4,
+    };
+    tags::Mutation::merge_tags(&db, data).await.unwrap();
+    assert_eq!(links(&db).await, vec![(1, 2), (1, 4), (2, 2), (3, 4)]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("selection.sql"))]
+async fn update_tag_category_rejects_selection_conflicts(db: SqlitePool) {
+    let db = db.into();
+    add_high_to_third(&db).await;
+
+    let data = AttachTagToCategoryInput {
+        category_tag_id: 1,
+        category_id: Some(2),
+    };
+    let res = tags::Mutation::update_tag_category(&db, data).await;
+    assert!(matches!(
+        res,
+        Err(AppError::SelectionConflict { activity_ids }) if activity_ids == vec![3]
+    ));
+    let tag = entity::category_tags::Entity::find_by_id(1)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tag.category_id, Some(1));
+
+    let data = AttachTagToCategoryInput {
+        category_tag_id: 2,
+        category_id: Some(2),
+    };
+    tags::Mutation::update_tag_category(&db, data)
+        .await
+        .unwrap();
+}
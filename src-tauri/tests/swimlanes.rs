@@ -0,0 +1,136 @@
+use app_lib::{
+    commands::swimlanes::MoveActivityToLaneInput,
+    database::swimlanes::{Mutation, Query},
+    errors::AppError,
+};
+use entity::activity_tags;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("swimlanes.sql"))]
+async fn group_activities_by_tag_and_column(db: SqlitePool) {
+    let db = db.into();
+
+    let res = Query::swimlanes(&db, 1).await.unwrap();
+    let lanes: Vec<Option<i32>> = res.lanes.iter().map(|lane| lane.tag_id).collect();
+    assert_eq!(lanes, vec![Some(2), Some(1), None]);
+
+    assert_eq!(res.lanes[0].cells[&1], Vec::<i32>::new());
+    assert_eq!(res.lanes[0].cells[&2], vec![4]);
+    assert_eq!(res.lanes[1].cells[&1], vec![1, 3]);
+    assert_eq!(res.lanes[2].cells[&1], vec![2]);
+    assert_eq!(res.lanes[2].cells[&2], Vec::<i32>::new());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("swimlanes.sql"))]
+async fn move_activity_between_lanes(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::move_activity_to_lane(
+        &db,
+        MoveActivityToLaneInput {
+            id: 3,
+            category_id: 1,
+            tag_id: Some(2),
+        },
+    )
+    .await
+    .unwrap();
+    Mutation::move_activity_to_lane(
+        &db,
+        MoveActivityToLaneInput {
+            id: 1,
+            category_id: 1,
+            tag_id: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = Query::swimlanes(&db, 1).await.unwrap();
+    assert_eq!(res.lanes[0].cells[&1], vec![3]);
+    assert_eq!(res.lanes[1].cells[&1], Vec::<i32>::new());
+    assert_eq!(res.lanes[2].cells[&1], vec![1, 2]);
+
+    let size_lanes = Query::swimlanes(&db, 2).await.unwrap();
+    assert_eq!(size_lanes.lanes[0].cells[&1], vec![3]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("swimlanes.sql"))]
+async fn reject_tag_of_another_category(db: SqlitePool) {
+    let db = db.into();
+
+    let res = Mutation::move_activity_to_lane(
+        &db,
+        MoveActivityToLaneInput {
+            id: 1,
+            category_id: 1,
+            tag_id: Some(3),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("swimlane_checks.sql"))]
+async fn activity_with_several_tags_uses_first_lane(db: SqlitePool) {
+    let db = db.into();
+
+    let res = Query::swimlanes(&db, 1).await.unwrap();
+    assert_eq!(res.lanes[0].tag_id, Some(2));
+    assert_eq!(res.lanes[0].cells[&1], vec![1]);
+    assert_eq!(res.lanes[1].cells[&1], Vec::<i32>::new());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("swimlane_checks.sql"))]
+async fn move_between_lanes_keeps_trashed_links_and_triage(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::move_activity_to_lane(
+        &db,
+        MoveActivityToLaneInput {
+            id: 1,
+            category_id: 1,
+            tag_id: Some(1),
+        },
+    )
+    .await
+    .unwrap();
+    let tag_ids = |db| async move {
+        activity_tags::Entity::find()
+            .filter(activity_tags::Column::ActivityId.eq(1))
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|link| link.category_tag_id)
+            .collect::<Vec<i32>>()
+    };
+    assert_eq!(tag_ids(&db).await, vec![1, 3]);
+
+    let res = Mutation::move_activity_to_lane(
+        &db,
+        MoveActivityToLaneInput {
+            id: 1,
+            category_id: 1,
+            tag_id: None,
+        },
+    )
+    .await;
+    assert!(matches!(
+        res,
+        Err(AppError::MissingRequiredTags { category_ids }) if category_ids == vec![1]
+    ));
+    assert_eq!(tag_ids(&db).await, vec![1, 3]);
+
+    let res = Mutation::move_activity_to_lane(
+        &db,
+        MoveActivityToLaneInput {
+            id: 1,
+            category_id: 2,
+            tag_id: Some(4),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+}
@@ -0,0 +1,334 @@
+use app_lib::{
+    commands::tags::{
+        AttachTagToCategoryInput, CreateTagInput, MergeTagsInput, SetTagOrderInput,
+        UpdateTagNameInput,
+    },
+    database::{
+        tags::Mutation,
+        undo::{self, UndoStack},
+    },
+    errors::AppError,
+};
+use entity::{activity_tags, category_tags};
+use sea_orm::{DbConn, EntityTrait};
+use sqlx::SqlitePool;
+
+async fn links(db: &DbConn) -> Vec<(i32, i32)> {
+    let mut res: Vec<(i32, i32)> = activity_tags::Entity::find()
+        .all(db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|link| (link.activity_id, link.category_tag_id))
+        .collect();
+    res.sort();
+    res
+}
+
+async fn tag(db: &DbConn, id: i32) -> category_tags::Model {
+    category_tags::Entity::find_by_id(id)
+        .one(db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn merge_tags_moves_links_to_target(db: SqlitePool) {
+    let db = db.into();
+
+    let changed = Mutation::merge_tags(
+        &db,
+        MergeTagsInput {
+            source_ids: vec![3, 6, 3],
+            target_id: 1,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(changed, 3);
+    assert_eq!(links(&db).await, vec![(1, 1), (2, 1), (3, 1), (3, 5)]);
+    for id in [3, 6] {
+        let res = category_tags::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap();
+        assert!(res.is_none());
+    }
+    assert_eq!(tag(&db, 4).await.ordinal, 0);
+    assert_eq!(tag(&db, 5).await.ordinal, 0);
+    assert_eq!(tag(&db, 7).await.ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn merge_tags_can_be_undone(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+    let before = links(&db).await;
+
+    stack
+        .record(
+            &db,
+            Mutation::merge_tags(
+                &db,
+                MergeTagsInput {
+                    source_ids: vec![5, 7],
+                    target_id: 6,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(tag(&db, 6).await.ordinal, 0);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(links(&db).await, before);
+    for (id, ordinal) in [(5, 0), (6, 1), (7, 2)] {
+        let tag = tag(&db, id).await;
+        assert!(tag.deleted_at.is_none());
+        assert_eq!(tag.ordinal, ordinal);
+    }
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tag_boards.sql"))]
+async fn merge_across_boards_is_rejected(db: SqlitePool) {
+    let db = db.into();
+
+    for source_id in [2, 4] {
+        let res = Mutation::merge_tags(
+            &db,
+            MergeTagsInput {
+                source_ids: vec![source_id],
+                target_id: 1,
+            },
+        )
+        .await;
+        assert!(matches!(res, Err(AppError::BoardMismatch)));
+    }
+    assert_eq!(links(&db).await, vec![(1, 3), (2, 4)]);
+
+    Mutation::merge_tags(
+        &db,
+        MergeTagsInput {
+            source_ids: vec![3],
+            target_id: 1,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(links(&db).await, vec![(1, 1), (2, 4)]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tag_boards.sql"))]
+async fn update_tag_category_rejects_other_board_and_trash(db: SqlitePool) {
+    let db = db.into();
+
+    for (category_tag_id, category_id) in [(1, 2), (4, 1)] {
+        let res = Mutation::update_tag_category(
+            &db,
+            AttachTagToCategoryInput {
+                category_tag_id,
+                category_id: Some(category_id),
+            },
+        )
+        .await;
+        assert!(matches!(res, Err(AppError::BoardMismatch)));
+    }
+    let res = Mutation::update_tag_category(
+        &db,
+        AttachTagToCategoryInput {
+            category_tag_id: 1,
+            category_id: Some(3),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+    assert_eq!(tag(&db, 1).await.category_id, Some(1));
+
+    Mutation::update_tag_category(
+        &db,
+        AttachTagToCategoryInput {
+            category_tag_id: 3,
+            category_id: Some(1),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(tag(&db, 3).await.category_id, Some(1));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn merge_into_missing_tag_is_rejected(db: SqlitePool) {
+    let db = db.into();
+
+    let res = Mutation::merge_tags(
+        &db,
+        MergeTagsInput {
+            source_ids: vec![1],
+            target_id: 99,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+
+    let res = Mutation::merge_tags(
+        &db,
+        MergeTagsInput {
+            source_ids: vec![1, 99],
+            target_id: 2,
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::RowNotFound)));
+    assert!(tag(&db, 1).await.deleted_at.is_none());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn attach_tag_to_other_category(db: SqlitePool) {
+    let db = db.into();
+
+    Mutation::update_tag_category(
+        &db,
+        AttachTagToCategoryInput {
+            category_tag_id: 2,
+            category_id: Some(2),
+        },
+    )
+    .await
+    .unwrap();
+    let moved = tag(&db, 2).await;
+    assert_eq!(moved.category_id, Some(2));
+    assert_eq!(moved.ordinal, 2);
+    assert_eq!(tag(&db, 1).await.ordinal, 0);
+
+    Mutation::update_tag_category(
+        &db,
+        AttachTagToCategoryInput {
+            category_tag_id: 4,
+            category_id: None,
+        },
+    )
+    .await
+    .unwrap();
+    let moved = tag(&db, 4).await;
+    assert_eq!(moved.category_id, None);
+    assert_eq!(moved.ordinal, 3);
+    assert_eq!(tag(&db, 3).await.ordinal, 0);
+    assert_eq!(tag(&db, 2).await.ordinal, 1);
+
+    let res = Mutation::update_tag_category(
+        &db,
+        AttachTagToCategoryInput {
+            category_tag_id: 1,
+            category_id: Some(2),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::TagNameConflict)));
+    assert_eq!(tag(&db, 1).await.category_id, Some(1));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn undo_attach_tag_to_category(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(
+            &db,
+            Mutation::update_tag_category(
+                &db,
+                AttachTagToCategoryInput {
+                    category_tag_id: 1,
+                    category_id: None,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(tag(&db, 2).await.ordinal, 0);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let restored = tag(&db, 1).await;
+    assert_eq!(restored.category_id, Some(1));
+    assert_eq!(restored.ordinal, 0);
+    assert_eq!(tag(&db, 2).await.ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn set_tag_order_reorders_non_category_tags(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let data = SetTagOrderInput {
+        category_id: None,
+        ids: vec![7, 5, 6],
+    };
+    stack
+        .record(&db, Mutation::set_tag_order(&db, data))
+        .await
+        .unwrap();
+    assert_eq!(tag(&db, 7).await.ordinal, 0);
+    assert_eq!(tag(&db, 5).await.ordinal, 1);
+    assert_eq!(tag(&db, 6).await.ordinal, 2);
+    assert_eq!(tag(&db, 1).await.ordinal, 0);
+
+    undo::Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(tag(&db, 5).await.ordinal, 0);
+    assert_eq!(tag(&db, 6).await.ordinal, 1);
+    assert_eq!(tag(&db, 7).await.ordinal, 2);
+
+    let data = SetTagOrderInput {
+        category_id: Some(1),
+        ids: vec![2, 3],
+    };
+    let res = Mutation::set_tag_order(&db, data).await;
+    assert!(matches!(res, Err(AppError::InvalidOrder)));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("tags.sql"))]
+async fn names_of_trashed_tags_stay_taken(db: SqlitePool) {
+    let db = db.into();
+    Mutation::delete_tag_by_id(&db, 2).await.unwrap();
+
+    let res = Mutation::create_tag(
+        &db,
+        CreateTagInput {
+            tag_name: "backend".to_owned(),
+            category_id: Some(1),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::TagNameConflict)));
+    let res = Mutation::update_tag_name(
+        &db,
+        UpdateTagNameInput {
+            category_tag_id: 1,
+            tag_name: "backend".to_owned(),
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(AppError::TagNameConflict)));
+    assert_eq!(tag(&db, 1).await.tag_name, "frontend");
+
+    // The name is only reserved within the category, and a tag keeps its own name.
+    Mutation::create_tag(
+        &db,
+        CreateTagInput {
+            tag_name: "backend".to_owned(),
+            category_id: Some(2),
+        },
+    )
+    .await
+    .unwrap();
+    Mutation::update_tag_name(
+        &db,
+        UpdateTagNameInput {
+            category_tag_id: 1,
+            tag_name: "frontend".to_owned(),
+        },
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,117 @@
+use app_lib::database::{activity, category, columns, tags, trash};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("trash.sql"))]
+async fn delete_and_restore_column(db: SqlitePool) {
+    let db = db.into();
+
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+
+    let live_columns = columns::Query::all_columns(&db, None).await.unwrap();
+    assert!(!live_columns.contains_key(&2));
+    assert_eq!(live_columns[&3].ordinal, 1);
+    let column_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(column_activities.len(), 1);
+    let trashed = columns::Query::all_trashed_columns(&db).await.unwrap();
+    assert_eq!(trashed[&2].activities.len(), 2);
+
+    columns::Mutation::restore_column_by_id(&db, 2)
+        .await
+        .unwrap();
+
+    let live_columns = columns::Query::all_columns(&db, None).await.unwrap();
+    assert_eq!(live_columns[&2].ordinal, 1);
+    assert_eq!(live_columns[&3].ordinal, 2);
+    let column_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(column_activities.len(), 3);
+    assert_eq!(column_activities[&3].ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("trash.sql"))]
+async fn restore_category_keeps_tag_links(db: SqlitePool) {
+    let db = db.into();
+
+    category::Mutation::delete_category_by_id(&db, 1)
+        .await
+        .unwrap();
+
+    let (categories, category_tags) = category::Query::all_with_category_tags(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[&2].ordinal, 0);
+    assert!(!category_tags.contains_key(&1));
+    let column_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(column_activities[&1].tags, vec![3]);
+
+    category::Mutation::restore_category_by_id(&db, 1)
+        .await
+        .unwrap();
+
+    let (categories, category_tags) = category::Query::all_with_category_tags(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(categories[&1].ordinal, 0);
+    assert_eq!(categories[&2].ordinal, 1);
+    assert_eq!(category_tags.len(), 3);
+    let column_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    let mut tags = column_activities[&1].tags.clone();
+    tags.sort();
+    assert_eq!(tags, vec![1, 3]);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("trash.sql"))]
+async fn restore_tag_to_previous_position(db: SqlitePool) {
+    let db = db.into();
+
+    tags::Mutation::delete_tag_by_id(&db, 1).await.unwrap();
+    let (_, category_tags) = category::Query::all_with_category_tags(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(category_tags[&2].ordinal, 0);
+
+    tags::Mutation::restore_tag_by_id(&db, 1).await.unwrap();
+    let (_, category_tags) = category::Query::all_with_category_tags(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(category_tags[&1].ordinal, 0);
+    assert_eq!(category_tags[&2].ordinal, 1);
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("trash.sql"))]
+async fn purge_trash_respects_age(db: SqlitePool) {
+    let db = db.into();
+
+    columns::Mutation::delete_column_by_id(&db, 2)
+        .await
+        .unwrap();
+    tags::Mutation::delete_tag_by_id(&db, 4).await.unwrap();
+
+    let purged = trash::Mutation::purge_trash(&db, 1).await.unwrap();
+    assert_eq!(purged.columns, 0);
+    assert_eq!(purged.tags, 0);
+
+    let purged = trash::Mutation::purge_trash(&db, -1).await.unwrap();
+    assert_eq!(purged.columns, 1);
+    assert_eq!(purged.tags, 1);
+
+    let trashed = columns::Query::all_trashed_columns(&db).await.unwrap();
+    assert!(trashed.is_empty());
+    let other_activities = activity::Query::all_other_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(other_activities.len(), 3);
+    assert_eq!(other_activities[&2].ordinal, 1);
+    assert_eq!(other_activities[&3].ordinal, 2);
+    assert!(tags::Query::all_trashed_tags(&db).await.unwrap().is_empty());
+}
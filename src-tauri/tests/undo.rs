@@ -0,0 +1,384 @@
+use app_lib::{
+    commands::{
+        activity::{CreateActivityInput, UpdateActivityColumnInput},
+        boards::RenameBoardInput,
+        bulk::BulkMoveActivitiesInput,
+        checklist::CreateChecklistItemInput,
+        columns::{RenameColumnInput, UpdateColumnWipLimitInput},
+    },
+    database::{
+        activity, boards, bulk, checklist, columns, tags,
+        undo::{Mutation, UndoStack},
+    },
+};
+use entity::{
+    activities, activity_comments, activity_relations, activity_tags, attachments, category_tags,
+    checklist_items,
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sqlx::SqlitePool;
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo.sql"))]
+async fn undo_and_redo_activity_move(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(
+            &db,
+            activity::Mutation::update_activity_column_by_id(
+                &db,
+                UpdateActivityColumnInput {
+                    id: 1,
+                    column_id: Some(2),
+                    new_ord: 1,
+                    allow_over_limit: false,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let moved = out.activities[&1].as_ref().unwrap();
+    assert_eq!(moved.column_id, Some(1));
+    assert_eq!(moved.ordinal, 0);
+    let all_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(all_activities[&2].ordinal, 1);
+    assert_eq!(all_activities[&3].ordinal, 2);
+    assert_eq!(all_activities[&4].ordinal, 0);
+
+    let out = Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    let moved = out.activities[&1].as_ref().unwrap();
+    assert_eq!(moved.column_id, Some(2));
+    assert_eq!(moved.ordinal, 1);
+    let all_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(all_activities[&2].ordinal, 0);
+    assert_eq!(all_activities[&4].ordinal, 0);
+
+    assert!(Mutation::redo(&db, &stack).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo.sql"))]
+async fn undo_activity_deletion_restores_position_and_tags(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(&db, activity::Mutation::delete_activity_by_id(&db, 2))
+        .await
+        .unwrap();
+    let all_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(all_activities[&3].ordinal, 1);
+
+    Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let all_activities = activity::Query::all_column_activities(&db, None)
+        .await
+        .unwrap();
+    assert_eq!(all_activities[&2].name, "2");
+    assert_eq!(all_activities[&2].ordinal, 1);
+    assert_eq!(all_activities[&3].ordinal, 2);
+    let mut tag_ids: Vec<i32> = activity_tags::Entity::find()
+        .filter(activity_tags::Column::ActivityId.eq(2))
+        .all(&db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|link| link.category_tag_id)
+        .collect();
+    tag_ids.sort();
+    assert_eq!(tag_ids, vec![1, 2]);
+
+    let out = Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.activities[&2], None);
+    assert!(activity::Query::find_activity_by_id(&db, 2)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo.sql"))]
+async fn undo_column_and_tag_changes(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(
+            &db,
+            columns::Mutation::update_column_name(
+                &db,
+                RenameColumnInput {
+                    id: 1,
+                    new_name: "Backlog".into(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+    stack
+        .record(
+            &db,
+            columns::Mutation::update_column_wip_limit(
+                &db,
+                UpdateColumnWipLimitInput {
+                    id: 1,
+                    wip_limit: Some(3),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+    stack
+        .record(&db, tags::Mutation::delete_tag_by_id(&db, 1))
+        .await
+        .unwrap();
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let tag = out.tags[&1].as_ref().unwrap();
+    assert_eq!(tag.deleted_at, None);
+    assert_eq!(tag.ordinal, 0);
+    let other = category_tags::Entity::find_by_id(2)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(other.ordinal, 1);
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let column = out.columns[&1].as_ref().unwrap();
+    assert_eq!(column.name, "Backlog");
+    assert_eq!(column.wip_limit, None);
+
+    // Recording a new operation discards the undone ones.
+    stack
+        .record(&db, columns::Mutation::delete_column_by_id(&db, 2))
+        .await
+        .unwrap();
+    assert!(Mutation::redo(&db, &stack).await.unwrap().is_none());
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.columns[&2].as_ref().unwrap().deleted_at, None);
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.columns[&1].as_ref().unwrap().name, "Todo");
+    assert!(Mutation::undo(&db, &stack).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo_children.sql"))]
+async fn undo_activity_deletion_restores_children(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(&db, activity::Mutation::delete_activity_by_id(&db, 1))
+        .await
+        .unwrap();
+    assert!(stack.attachment_hashes().await.contains("abc123"));
+    assert!(attachments::Entity::find_by_id(1)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_none());
+
+    Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    let checklist = checklist_items::Entity::find()
+        .filter(checklist_items::Column::ActivityId.eq(1))
+        .all(&db)
+        .await
+        .unwrap();
+    assert_eq!(checklist.len(), 2);
+    let comment = activity_comments::Entity::find_by_id(1)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(comment.text, "first");
+    let relations = activity_relations::Entity::find()
+        .filter(activity_relations::Column::ActivityId.eq(1))
+        .all(&db)
+        .await
+        .unwrap();
+    assert_eq!(relations.len(), 1);
+    assert_eq!(relations[0].related_activity_id, 2);
+    let attachment = attachments::Entity::find_by_id(1)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(attachment.content_hash, "abc123");
+
+    // The redone deletion can be undone again, so its files must be kept.
+    Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    assert!(stack.attachment_hashes().await.contains("abc123"));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo_children.sql"))]
+async fn undo_creation_removes_row(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let column = stack
+        .record(&db, columns::Mutation::insert_column(&db, 1, "Done".into()))
+        .await
+        .unwrap();
+    let board = stack
+        .record(&db, boards::Mutation::insert_board(&db, "Home".into()))
+        .await
+        .unwrap();
+    stack
+        .record(
+            &db,
+            boards::Mutation::update_board_name(
+                &db,
+                RenameBoardInput {
+                    id: board.id,
+                    new_name: "Chores".into(),
+                },
+            ),
+        )
+        .await
+        .unwrap();
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.boards[&board.id].as_ref().unwrap().name, "Home");
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert!(out.boards[&board.id].is_none());
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert!(out.columns[&column.id].is_none());
+    assert!(entity::columns::Entity::find_by_id(column.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .is_none());
+
+    let out = Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    let restored = out.columns[&column.id].as_ref().unwrap();
+    assert_eq!(restored.name, "Done");
+    assert_eq!(restored.ordinal, 3);
+    assert_eq!(restored.deleted_at, None);
+    let out = Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.boards[&board.id].as_ref().unwrap().name, "Home");
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo_children.sql"))]
+async fn redo_activity_creation_restores_children(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    let created = stack
+        .record(
+            &db,
+            activity::Mutation::create_activity(
+                &db,
+                CreateActivityInput {
+                    name: "5".into(),
+                    body: None,
+                    column_id: 1,
+                    start_date: None,
+                    due_date: None,
+                    allow_over_limit: false,
+                },
+            ),
+        )
+        .await
+        .unwrap()
+        .activity;
+    checklist::Mutation::insert_checklist_item(
+        &db,
+        CreateChecklistItemInput {
+            activity_id: created.id,
+            text: "c".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert!(out.activities[&created.id].is_none());
+    let out = Mutation::redo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.activities[&created.id].as_ref().unwrap().name, "5");
+    let checklist = checklist_items::Entity::find()
+        .filter(checklist_items::Column::ActivityId.eq(created.id))
+        .all(&db)
+        .await
+        .unwrap();
+    assert_eq!(checklist.len(), 1);
+    assert_eq!(checklist[0].text, "c");
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo_children.sql"))]
+async fn undo_ignores_wip_limit(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(
+            &db,
+            activity::Mutation::update_activity_column_by_id(
+                &db,
+                UpdateActivityColumnInput {
+                    id: 3,
+                    column_id: Some(3),
+                    new_ord: 1,
+                    allow_over_limit: false,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+    activity::Mutation::create_activity(
+        &db,
+        CreateActivityInput {
+            name: "5".into(),
+            body: None,
+            column_id: 2,
+            start_date: None,
+            due_date: None,
+            allow_over_limit: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    let out = Mutation::undo(&db, &stack).await.unwrap().unwrap();
+    assert_eq!(out.activities[&3].as_ref().unwrap().column_id, Some(2));
+}
+
+#[sqlx::test(migrations = "../migrations", fixtures("undo_children.sql"))]
+async fn failed_undo_changes_nothing_and_keeps_entry(db: SqlitePool) {
+    let db = db.into();
+    let stack = UndoStack::default();
+
+    stack
+        .record(
+            &db,
+            bulk::Mutation::move_activities(
+                &db,
+                BulkMoveActivitiesInput {
+                    ids: vec![3, 4],
+                    column_id: 1,
+                    new_ord: 0,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+    entity::columns::Entity::delete_by_id(2)
+        .exec(&db)
+        .await
+        .unwrap();
+
+    assert!(Mutation::undo(&db, &stack).await.is_err());
+    let fourth = activities::Entity::find_by_id(4)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fourth.column_id, Some(1));
+    assert!(Mutation::undo(&db, &stack).await.is_err());
+}